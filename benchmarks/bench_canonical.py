@@ -0,0 +1,104 @@
+"""
+Project JADE - canonical serialization benchmark
+Wall time and peak memory of producing a skill's hash and signable content.
+
+    python benchmarks/bench_canonical.py                 # 2000 nodes, 20 runs
+    python benchmarks/bench_canonical.py --nodes 10000 --runs 5
+
+Compares serializing the skill twice (canonical_json for the hash, then
+signable_content for signatures) with canonical_skill, which serializes
+each top-level member once, and signable_and_hash, which the validator
+uses and which hashes the full form without building it. Times are the
+total over --runs calls; peak memory is what tracemalloc sees during one.
+"""
+
+from __future__ import annotations
+
+import argparse
+import hashlib
+import os
+import sys
+import time
+import tracemalloc
+
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), ".."))
+
+from jade_core.crypto import (  # noqa: E402
+    canonical_json,
+    canonical_skill,
+    signable_and_hash,
+    signable_content,
+)
+
+
+def chain_skill(count: int) -> dict:
+    """A signed-looking skill with a chain of `count` http_get nodes."""
+    nodes = [
+        {
+            "id": f"step_{i}",
+            "action": "http_get",
+            "params": {"url": f"https://api.example.com/items/{i}", "headers": {"Accept": "application/json"}},
+            "timeout_ms": 5000,
+        }
+        for i in range(count)
+    ]
+    ids = [n["id"] for n in nodes]
+    return {
+        "jade_version": "1.0.0",
+        "skill_id": "bench_canonical",
+        "metadata": {"name": "Bench Canonical", "version": "1.0.0", "description": "benchmark", "tags": ["bench"]},
+        "trigger": {"type": "manual", "conditions": []},
+        "execution_dag": {
+            "nodes": nodes,
+            "edges": [{"from": a, "to": b} for a, b in zip(ids, ids[1:])],
+            "entry_node": ids[0],
+            "exit_node": [ids[-1]],
+        },
+        "security": {"network_whitelist": ["api.example.com"], "sandbox_level": "strict"},
+        "signature": {"public_key": "jade-pk-" + "A" * 43, "signature": "B" * 86},
+    }
+
+
+def twice(skill: dict) -> tuple:
+    """The hash and signable content from two independent serializations."""
+    return signable_content(skill), hashlib.sha256(canonical_json(skill).encode("utf-8")).hexdigest()
+
+
+def shared(skill: dict) -> tuple:
+    """The hash and signable content from canonical_skill's shared members."""
+    full, signable = canonical_skill(skill)
+    return signable, hashlib.sha256(full.encode("utf-8")).hexdigest()
+
+
+def measure(fn, skill: dict, runs: int) -> tuple:
+    """(seconds for `runs` calls, peak bytes of one call, result)."""
+    started = time.perf_counter()
+    for _ in range(runs):
+        result = fn(skill)
+    elapsed = time.perf_counter() - started
+    tracemalloc.start()
+    fn(skill)
+    _, peak = tracemalloc.get_traced_memory()
+    tracemalloc.stop()
+    return elapsed, peak, result
+
+
+def main() -> int:
+    parser = argparse.ArgumentParser(description=__doc__.split("\n")[2])
+    parser.add_argument("--nodes", type=int, default=2000, help="Nodes in the generated skill")
+    parser.add_argument("--runs", type=int, default=20, help="Calls timed per approach")
+    args = parser.parse_args()
+
+    skill = chain_skill(args.nodes)
+    print(f"skill: {args.nodes} nodes, {len(canonical_json(skill)) / 1024 / 1024:.1f} MB canonical, {args.runs} runs")
+    expected = None
+    for label, fn in (("serialize twice", twice), ("canonical_skill", shared), ("signable_and_hash", signable_and_hash)):
+        elapsed, peak, result = measure(fn, skill, args.runs)
+        expected = expected or result
+        assert result == expected, f"{label} disagrees with serializing twice"
+        print(f"  {label:<17}  {elapsed:6.2f} s  peak {peak / 1024 / 1024:6.1f} MB")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
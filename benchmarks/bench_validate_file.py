@@ -0,0 +1,119 @@
+"""
+Project JADE - validate_file benchmark
+Peak memory and wall time of validating one large skill file.
+
+    python benchmarks/bench_validate_file.py             # 10 MB skill
+    python benchmarks/bench_validate_file.py --mb 50 --runs 5
+
+The skill is generated into a temporary directory: make_skill-style
+nodes whose params carry long text, sized to roughly --mb megabytes.
+Peak memory is what tracemalloc sees Python allocate during one
+validate_file call, so it excludes the interpreter and imported modules.
+Resource limits are lifted so the whole file goes through every layer;
+--limit-bytes restores a byte limit to time the early rejection instead.
+"""
+
+from __future__ import annotations
+
+import argparse
+import json
+import os
+import sys
+import tempfile
+import time
+import tracemalloc
+
+sys.path.insert(0, os.path.join(os.path.dirname(os.path.abspath(__file__)), ".."))
+
+from jade_core.cache import MemoryValidationCache  # noqa: E402
+from jade_core.models import ResourceLimits  # noqa: E402
+from jade_core.validator import JadeValidator  # noqa: E402
+
+NODE_TEXT_BYTES = 20_000
+
+
+def large_skill(megabytes: float) -> dict:
+    """A valid skill of about `megabytes` MB: a chain of json_parse nodes."""
+    count = max(2, int(megabytes * 1024 * 1024 // NODE_TEXT_BYTES))
+    text = ("lorem ipsum dolor sit amet " * (NODE_TEXT_BYTES // 27 + 1))[:NODE_TEXT_BYTES]
+    nodes = [
+        {"id": f"step_{i}", "action": "json_parse", "params": {"input": text}}
+        for i in range(count - 1)
+    ]
+    nodes.append({"id": "done", "action": "return_result", "params": {"result": "ok"}})
+    ids = [n["id"] for n in nodes]
+    return {
+        "jade_version": "1.0.0",
+        "skill_id": "bench_large_skill",
+        "metadata": {
+            "name": "Bench Large Skill",
+            "version": "1.0.0",
+            "description": "Generated by benchmarks/bench_validate_file.py",
+            "author": "bench",
+            "tags": ["bench"],
+            "created_at": "2025-01-01T00:00:00Z",
+        },
+        "trigger": {
+            "type": "manual",
+            "conditions": [{"field": "task.type", "operator": "equals", "value": "bench"}],
+        },
+        "input_schema": {"required_params": [], "optional_params": []},
+        "output_schema": {"fields": [{"name": "result", "type": "string"}]},
+        "execution_dag": {
+            "nodes": nodes,
+            "edges": [{"from": a, "to": b} for a, b in zip(ids, ids[1:])],
+            "entry_node": ids[0],
+            "exit_node": ["done"],
+        },
+        "security": {
+            "network_whitelist": [],
+            "file_permissions": {"read": [], "write": []},
+            "max_execution_time_ms": 30000,
+            "max_retries": 0,
+            "sandbox_level": "strict",
+            "dangerous_patterns": [],
+        },
+    }
+
+
+def measure(validator: JadeValidator, path: str) -> tuple:
+    """(peak bytes, seconds, valid) for one validate_file call."""
+    tracemalloc.start()
+    started = time.perf_counter()
+    result = validator.validate_file(path)
+    elapsed = time.perf_counter() - started
+    _, peak = tracemalloc.get_traced_memory()
+    tracemalloc.stop()
+    return peak, elapsed, result.valid
+
+
+def main() -> int:
+    parser = argparse.ArgumentParser(description=__doc__.split("\n")[2])
+    parser.add_argument("--mb", type=float, default=10.0, help="Approximate skill size in MB")
+    parser.add_argument("--runs", type=int, default=3, help="Runs per configuration; the best is shown")
+    parser.add_argument("--limit-bytes", type=int, help="Keep ResourceLimits.max_bytes at this value")
+    args = parser.parse_args()
+
+    skill = large_skill(args.mb)
+    limits = ResourceLimits(max_bytes=args.limit_bytes, max_depth=64, max_nodes=None, max_edges=None)
+    with tempfile.TemporaryDirectory() as tmp:
+        path = os.path.join(tmp, "large_skill.json")
+        with open(path, "w", encoding="utf-8") as f:
+            json.dump(skill, f, indent=2)
+        size = os.path.getsize(path)
+        print(f"skill file: {size / 1024 / 1024:.1f} MB, {len(skill['execution_dag']['nodes'])} nodes")
+
+        for label, cache in (("no cache", None), ("with cache", MemoryValidationCache)):
+            best = None
+            for _ in range(args.runs):
+                validator = JadeValidator(resource_limits=limits, cache=cache() if cache else None)
+                peak, elapsed, valid = measure(validator, path)
+                best = (peak, elapsed, valid) if best is None or elapsed < best[1] else best
+            peak, elapsed, valid = best
+            print(f"  {label:<10}  peak {peak / 1024 / 1024:7.1f} MB  "
+                  f"({peak / size:4.1f}x file)  {elapsed * 1000:8.0f} ms  valid={valid}")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
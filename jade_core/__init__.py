@@ -9,63 +9,165 @@ Core modules:
 - validator: Schema and security validation
 - security: Zero-trust security engine
 - dag: DAG structural analysis
+- crypto: Canonical JSON and Ed25519 signatures
+- errors: Structured exception types (JadeError and subclasses)
+- executor: Runs a validated skill's execution DAG
+- builder: Fluent construction of skills from code
 - client: SDK for agents to fetch and use skills
 - registry: Index management and confidence scoring
+- events: Structured log events (logging, silent unless configured)
 """
 
+import logging
+
 __version__ = "1.0.0"
 __protocol_version__ = "1.0.0"
 
+# Library code only logs; applications decide whether and where to output
+logging.getLogger("jade_core").addHandler(logging.NullHandler())
+
 from .models import (
     JadeSkill,
     ValidationResult,
     ValidationIssue,
+    ValidationLayer,
     ValidationSeverity,
+    IssueCode,
+    Suppression,
     SecurityPolicy,
     ExecutionDAG,
     DAGNode,
     DAGEdge,
+    RetryPolicy,
     Trigger,
     TriggerCondition,
     SkillMetadata,
     SandboxLevel,
+    SandboxCapabilities,
     TriggerType,
     ConditionOperator,
     RiskLevel,
     RegistryEntry,
     Attestation,
     AttestationType,
+    JadeSignature,
+    CommunitySignature,
+    CommunitySigResult,
+    TrustLevel,
+    TrustPolicy,
+    SignatureRecord,
+    SignerRole,
+    SkillIdPolicy,
+    ResourceLimits,
+    FreshnessPolicy,
+)
+from .validator import JadeValidator, normalize_tags, validate_str
+from .security import CapabilitySummary, InjectionRule, RiskFactor, SecurityEngine, SecurityRules
+from .executor import ExecutionError, ExecutionResult, JadeExecutor, NodePlan
+from .dag import DAGAnalyzer, DAGMetrics
+from .builder import SkillBuildError, SkillBuilder
+from .diff import Escalation, FieldChange, SkillDiff, diff_skills
+from .layers import FIRST_CUSTOM_LAYER, CustomLayer
+from .locations import SourceMap
+from .report import ReportFormat, render, sarif_log
+from .cache import MemoryValidationCache, ValidationCache
+from .errors import CryptoError, CycleError, JadeError, JadeIOError, JadeParseError, NotFoundError
+from .crypto import (
+    JadeKeyPair,
+    KeyRegistry,
+    SignatureAlgorithm,
+    SignatureReport,
+    TrustedKey,
+    canonical_json,
+    inspect_signature,
+    parse_public_key,
+    signable_bytes,
+    signable_content,
 )
-from .validator import JadeValidator
-from .security import SecurityEngine
-from .dag import DAGAnalyzer
 from .client import JadeClient
 from .registry import JadeRegistry
 
 __all__ = [
     # Core classes
     "JadeValidator",
+    "validate_str",
+    "normalize_tags",
     "SecurityEngine",
+    "SecurityRules",
+    "InjectionRule",
+    "CapabilitySummary",
+    "RiskFactor",
     "DAGAnalyzer",
+    "DAGMetrics",
+    "SkillBuilder",
+    "SkillBuildError",
+    "SkillDiff",
+    "FieldChange",
+    "Escalation",
+    "diff_skills",
+    "SourceMap",
+    "ReportFormat",
+    "render",
+    "sarif_log",
+    "ValidationCache",
+    "MemoryValidationCache",
+    "CustomLayer",
+    "FIRST_CUSTOM_LAYER",
+    "JadeExecutor",
+    "ExecutionResult",
+    "ExecutionError",
+    "NodePlan",
     "JadeClient",
     "JadeRegistry",
+    "JadeKeyPair",
+    "KeyRegistry",
+    "TrustedKey",
+    "SignatureAlgorithm",
+    "SignatureReport",
+    "canonical_json",
+    "inspect_signature",
+    "parse_public_key",
+    "signable_content",
+    "signable_bytes",
+    # Errors
+    "JadeError",
+    "JadeIOError",
+    "JadeParseError",
+    "CycleError",
+    "CryptoError",
+    "NotFoundError",
     # Models
     "JadeSkill",
     "ValidationResult",
     "ValidationIssue",
+    "ValidationLayer",
     "ValidationSeverity",
+    "IssueCode",
+    "Suppression",
     "SecurityPolicy",
     "ExecutionDAG",
     "DAGNode",
     "DAGEdge",
+    "RetryPolicy",
     "Trigger",
     "TriggerCondition",
     "SkillMetadata",
     "SandboxLevel",
+    "SandboxCapabilities",
     "TriggerType",
     "ConditionOperator",
     "RiskLevel",
     "RegistryEntry",
     "Attestation",
     "AttestationType",
+    "JadeSignature",
+    "CommunitySignature",
+    "CommunitySigResult",
+    "TrustLevel",
+    "TrustPolicy",
+    "SignatureRecord",
+    "SignerRole",
+    "SkillIdPolicy",
+    "ResourceLimits",
+    "FreshnessPolicy",
 ]
@@ -0,0 +1,232 @@
+"""
+Project JADE - Skill Builder
+Fluent construction of JADE skills from code.
+
+    skill = (
+        SkillBuilder("weather_lookup")
+        .metadata(name="Weather", description="Current weather", tags=["weather"])
+        .trigger("task.type", "equals", "weather")
+        .node("fetch", "http_get", {"url": "https://api.example.com/{{input.city}}"})
+        .node("done", "return_result", {"result": "{{fetch.output.body}}"})
+        .edge("fetch", "done")
+        .whitelist("api.example.com")
+        .build()
+    )
+
+Each call checks what it can on its own (unknown sandbox levels, malformed
+whitelist entries, duplicate node IDs, edges to undeclared nodes) and raises
+ValueError straight away. build() then runs the schema and DAG layers of the
+validator over the assembled document, so a builder never hands back a skill
+those layers would reject.
+"""
+
+from __future__ import annotations
+
+import copy
+from typing import Any, Dict, List, Optional
+
+from .errors import JadeError
+from .models import (
+    ConditionOperator,
+    JadeSkill,
+    RetryPolicy,
+    SandboxLevel,
+    TriggerType,
+    ValidationIssue,
+    ValidationLayer,
+)
+from .security import whitelist_syntax_error
+from .validator import JadeValidator
+
+# jade_version written into built skills
+BUILDER_JADE_VERSION = "1.0.0"
+
+
+class SkillBuildError(JadeError, ValueError):
+    """build() found errors in the assembled skill; see `issues`."""
+
+    def __init__(self, issues: List[ValidationIssue]):
+        summary = "; ".join(f"{i.code}: {i.message}" for i in issues[:3])
+        more = f" (and {len(issues) - 3} more)" if len(issues) > 3 else ""
+        super().__init__(f"Skill has {len(issues)} error(s): {summary}{more}")
+        self.issues = list(issues)
+
+    @property
+    def codes(self) -> List[str]:
+        return [i.code for i in self.issues]
+
+
+class SkillBuilder:
+    """
+    Assembles a JADE skill document step by step.
+
+    Entry and exit nodes default to the DAG's roots and sinks, in
+    declaration order, unless set with entry() and exit().
+    """
+
+    def __init__(self, skill_id: str):
+        self._skill_id = skill_id
+        self._metadata: Dict[str, Any] = {
+            "name": skill_id,
+            "version": "1.0.0",
+            "description": "",
+            "author": "",
+            "tags": [],
+            "license": "MIT",
+        }
+        self._trigger_type = TriggerType.MANUAL
+        self._conditions: List[Dict[str, Any]] = []
+        self._nodes: List[Dict[str, Any]] = []
+        self._edges: List[Dict[str, Any]] = []
+        self._entry: Optional[str] = None
+        self._exit: List[str] = []
+        self._security: Dict[str, Any] = {
+            "network_whitelist": [],
+            "file_permissions": {"read": [], "write": []},
+            "max_execution_time_ms": 30000,
+            "max_retries": 0,
+            "sandbox_level": SandboxLevel.STRICT.value,
+            "dangerous_patterns": [],
+        }
+        self._extra: Dict[str, Any] = {}
+
+    # ─── Metadata & Trigger ─────────────────────────────────────────
+
+    def metadata(self, **fields: Any) -> SkillBuilder:
+        """Set metadata fields (name, version, description, author, tags, license, ...)."""
+        self._metadata.update(fields)
+        return self
+
+    def trigger(self, field: str, operator: str, value: Any) -> SkillBuilder:
+        """Add a trigger condition, e.g. trigger("task.type", "equals", "weather")."""
+        self._conditions.append({
+            "field": field, "operator": ConditionOperator(operator).value, "value": value,
+        })
+        return self
+
+    def trigger_type(self, trigger_type: str) -> SkillBuilder:
+        self._trigger_type = TriggerType(trigger_type)
+        return self
+
+    def input_schema(self, schema: Dict[str, Any]) -> SkillBuilder:
+        self._extra["input_schema"] = copy.deepcopy(schema)
+        return self
+
+    def output_schema(self, schema: Dict[str, Any]) -> SkillBuilder:
+        self._extra["output_schema"] = copy.deepcopy(schema)
+        return self
+
+    # ─── DAG ────────────────────────────────────────────────────────
+
+    def node(
+        self,
+        node_id: str,
+        action: str,
+        params: Optional[Dict[str, Any]] = None,
+        timeout_ms: Optional[int] = None,
+        retry: Optional[RetryPolicy] = None,
+    ) -> SkillBuilder:
+        """Add a DAG node. Raises ValueError if the ID is already used."""
+        if any(n["id"] == node_id for n in self._nodes):
+            raise ValueError(f"Duplicate node ID '{node_id}'")
+        node: Dict[str, Any] = {"id": node_id, "action": action, "params": copy.deepcopy(params or {})}
+        if timeout_ms is not None:
+            node["timeout_ms"] = timeout_ms
+        if retry is not None:
+            node["retry"] = retry.to_dict()
+        self._nodes.append(node)
+        return self
+
+    def edge(self, from_node: str, to_node: str, condition: Optional[str] = None) -> SkillBuilder:
+        """Add a DAG edge between declared nodes. Raises ValueError for unknown nodes."""
+        for node_id in (from_node, to_node):
+            self._require_node(node_id)
+        edge: Dict[str, Any] = {"from": from_node, "to": to_node}
+        if condition:
+            edge["condition"] = condition
+        self._edges.append(edge)
+        return self
+
+    def entry(self, node_id: str) -> SkillBuilder:
+        self._require_node(node_id)
+        self._entry = node_id
+        return self
+
+    def exit(self, *node_ids: str) -> SkillBuilder:
+        for node_id in node_ids:
+            self._require_node(node_id)
+        self._exit = list(node_ids)
+        return self
+
+    def _require_node(self, node_id: str) -> None:
+        if not any(n["id"] == node_id for n in self._nodes):
+            raise ValueError(f"Unknown node '{node_id}'; add it with node() first")
+
+    # ─── Security ───────────────────────────────────────────────────
+
+    def sandbox(self, level: str) -> SkillBuilder:
+        """Set the sandbox level ('strict', 'standard' or 'permissive')."""
+        self._security["sandbox_level"] = SandboxLevel(level).value
+        return self
+
+    def whitelist(self, *domains: str) -> SkillBuilder:
+        """Add network_whitelist entries. Raises ValueError for malformed entries."""
+        for domain in domains:
+            error = whitelist_syntax_error(domain)
+            if error:
+                raise ValueError(f"Malformed whitelist entry '{domain}': {error}")
+            self._security["network_whitelist"].append(domain)
+        return self
+
+    def file_permissions(self, read: Optional[List[str]] = None, write: Optional[List[str]] = None) -> SkillBuilder:
+        self._security["file_permissions"] = {"read": list(read or []), "write": list(write or [])}
+        return self
+
+    def limits(self, max_execution_time_ms: Optional[int] = None, max_retries: Optional[int] = None) -> SkillBuilder:
+        if max_execution_time_ms is not None:
+            self._security["max_execution_time_ms"] = max_execution_time_ms
+        if max_retries is not None:
+            self._security["max_retries"] = max_retries
+        return self
+
+    # ─── Output ─────────────────────────────────────────────────────
+
+    def to_dict(self) -> Dict[str, Any]:
+        """The skill document as it stands, without validating it."""
+        targets = {e["to"] for e in self._edges}
+        sources = {e["from"] for e in self._edges}
+        roots = [n["id"] for n in self._nodes if n["id"] not in targets]
+        entry = self._entry or next(iter(roots or [n["id"] for n in self._nodes]), "")
+        exits = self._exit or [n["id"] for n in self._nodes if n["id"] not in sources]
+        data: Dict[str, Any] = {
+            "jade_version": BUILDER_JADE_VERSION,
+            "skill_id": self._skill_id,
+            "metadata": copy.deepcopy(self._metadata),
+            "trigger": {"type": self._trigger_type.value, "conditions": copy.deepcopy(self._conditions)},
+            "execution_dag": {
+                "nodes": copy.deepcopy(self._nodes),
+                "edges": copy.deepcopy(self._edges),
+                "entry_node": entry,
+                "exit_node": exits,
+            },
+            "security": copy.deepcopy(self._security),
+        }
+        data.update(copy.deepcopy(self._extra))
+        return data
+
+    def build(self, validator: Optional[JadeValidator] = None) -> JadeSkill:
+        """
+        Validate the schema and DAG layers and return the skill.
+
+        Raises SkillBuildError listing every error found, e.g. a
+        DAG_CYCLE_DETECTED issue for a cyclic DAG. Warnings do not fail
+        the build.
+        """
+        data = self.to_dict()
+        validator = validator or JadeValidator()
+        result = validator.validate_with_layers(
+            data, [ValidationLayer.SCHEMA, ValidationLayer.DAG], continue_on_error=True,
+        )
+        if result.errors:
+            raise SkillBuildError(result.errors)
+        return JadeSkill.from_dict(data)
@@ -0,0 +1,85 @@
+"""
+Project JADE - Validation Cache
+Skip re-validating skill files that have not changed.
+
+    validator = JadeValidator(cache=MemoryValidationCache())
+    validator.validate_file("weather.json")  # Runs every layer
+    validator.validate_file("weather.json")  # Same bytes: cached result
+
+Keys combine crypto.content_hash of the raw text with a digest of the
+validator's settings (engine version, schema, actions, rules, policies,
+suppressions, trusted keys), so changing any of them misses the cache.
+Only valid results are stored: a failing skill is always re-checked.
+Custom layers take part through CustomLayer.cache_token(); a validator
+with a layer that has none does not cache at all.
+
+A hit is returned with checked_at set to the time of the lookup. No layer
+runs, so a hit emits none of the validation events (validation_started,
+layer_started, issue_found, ...) that a full validation logs.
+
+With JadeValidator(trust_signed_scan=True), which also needs a key
+registry, the cache additionally holds the Layer 3 and 4 issues of skills
+signed by a registered key, under their content hash. Re-validating the
+same signed content (even reformatted, or from validate_dict) then skips
+the injection scan and semantic checks; content that no longer matches its
+signature's content_hash is scanned in full.
+
+Implement ValidationCache to keep results on disk or in a shared store.
+"""
+
+from __future__ import annotations
+
+import copy
+import threading
+from abc import ABC, abstractmethod
+from collections import OrderedDict
+from typing import Optional
+
+from .models import ValidationResult
+
+
+class ValidationCache(ABC):
+    """Storage for valid ValidationResults, by opaque string key."""
+
+    @abstractmethod
+    def get(self, key: str) -> Optional[ValidationResult]:
+        """The result stored under `key`, or None."""
+
+    @abstractmethod
+    def put(self, key: str, result: ValidationResult) -> None:
+        """Store `result`, independent of later changes to the caller's copy."""
+
+
+class MemoryValidationCache(ValidationCache):
+    """In-process cache, least recently used entries evicted first. Thread-safe."""
+
+    def __init__(self, max_entries: int = 1024):
+        self.max_entries = max_entries
+        self.hits = 0
+        self.misses = 0
+        self._entries: OrderedDict = OrderedDict()
+        self._lock = threading.Lock()
+
+    def get(self, key: str) -> Optional[ValidationResult]:
+        with self._lock:
+            result = self._entries.get(key)
+            if result is None:
+                self.misses += 1
+                return None
+            self.hits += 1
+            self._entries.move_to_end(key)
+        return copy.deepcopy(result)  # Callers may annotate what they get back
+
+    def put(self, key: str, result: ValidationResult) -> None:
+        with self._lock:
+            self._entries[key] = copy.deepcopy(result)  # The caller keeps `result`
+            self._entries.move_to_end(key)
+            while len(self._entries) > self.max_entries:
+                self._entries.popitem(last=False)
+
+    def clear(self) -> None:
+        with self._lock:
+            self._entries.clear()
+
+    def __len__(self) -> int:
+        return len(self._entries)
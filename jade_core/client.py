@@ -25,6 +25,7 @@ from urllib.request import urlopen, Request
 from urllib.error import URLError, HTTPError
 
 from .models import (
+    IssueCode,
     JadeSkill,
     ValidationResult,
     ValidationIssue,
@@ -153,7 +154,7 @@ class JadeClient:
                 valid=False,
                 issues=[ValidationIssue(
                     severity=ValidationSeverity.ERROR,
-                    code="FETCH_FAILED",
+                    code=IssueCode.FETCH_FAILED,
                     message=f"Failed to fetch skill '{skill_id}': {e}",
                 )],
             )
@@ -163,7 +164,7 @@ class JadeClient:
                 valid=False,
                 issues=[ValidationIssue(
                     severity=ValidationSeverity.ERROR,
-                    code="SKILL_NOT_FOUND",
+                    code=IssueCode.SKILL_NOT_FOUND,
                     message=f"Skill '{skill_id}' not found in remote registry",
                 )],
             )
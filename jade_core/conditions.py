@@ -0,0 +1,387 @@
+"""
+Project JADE - Edge Conditions
+A tiny, non-Turing-complete expression language for DAG edge conditions.
+
+Grammar:
+    expr       := or_expr
+    or_expr    := and_expr ('||' and_expr)*
+    and_expr   := unary ('&&' unary)*
+    unary      := '!' unary | primary
+    primary    := '(' expr ')' | comparison
+    comparison := operand (('==' | '!=' | '<' | '<=' | '>' | '>=') operand)?
+    operand    := reference | string | number | 'true' | 'false' | 'null'
+    reference  := IDENT ('.' IDENT | '[' INT ']')+
+
+The bare keywords 'success' and 'failure' refer to the outcome of the
+edge's source node. Any other reference must be dotted, e.g.
+`check.output.status == "ok"`; the '.output' segment is optional, so
+`check.status == "ok"` reads the same field. References rooted at
+'input' read the skill's inputs instead: `input.mode == "fast"`.
+
+evaluate_condition() resolves references against the outputs of nodes
+that already ran and the run's inputs. A reference that cannot be
+resolved evaluates to null, so comparisons against it are false rather
+than errors.
+"""
+
+from __future__ import annotations
+
+import re
+from typing import Any, Dict, List, Optional, Tuple
+
+from .errors import JadeParseError
+from .templates import INPUT_ROOT
+
+# Bare keywords accepted as whole conditions
+STATUS_KEYWORDS = ("success", "failure")
+COMPARISON_OPERATORS = ("==", "!=", "<=", ">=", "<", ">")
+
+_TOKEN_RE = re.compile(r"""
+    (?P<ws>\s+)
+  | (?P<string>"(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*')
+  | (?P<number>-?\d+(?:\.\d+)?)
+  | (?P<ident>[A-Za-z_][A-Za-z0-9_]*)
+  | (?P<op>==|!=|<=|>=|&&|\|\||[<>!()\[\].])
+""", re.VERBOSE)
+
+_OPERATOR_RUN_RE = re.compile(r"[=!<>&|~^%*+/]+")
+_BINARY_OPERATORS = COMPARISON_OPERATORS + ("&&", "||")
+
+_LITERALS = {"true": True, "false": False, "null": None}
+
+# Deepest nesting of '!' and '(' accepted, far above any real condition;
+# the parser and evaluator recurse once per level
+MAX_CONDITION_DEPTH = 64
+
+# AST nodes are plain tuples:
+#   ("or", [expr, ...]) | ("and", [expr, ...]) | ("not", expr)
+#   ("cmp", op, left, right) | ("ref", [segment, ...]) | ("lit", value)
+#   ("status", "success" | "failure")
+Expr = Tuple[Any, ...]
+
+
+class ConditionSyntaxError(JadeParseError):
+    """Raised when an edge condition cannot be parsed."""
+
+
+def tokenize(text: str) -> List[Tuple[str, str]]:
+    """Split a condition into (kind, text) tokens."""
+    tokens: List[Tuple[str, str]] = []
+    pos = 0
+    while pos < len(text):
+        match = _TOKEN_RE.match(text, pos)
+        if not match:
+            _check_operator_run(text, pos)
+            raise ConditionSyntaxError(f"Unexpected character '{text[pos]}' at position {pos}")
+        kind = match.lastgroup or ""
+        if kind == "op":
+            _check_operator_run(text, pos)
+        if kind != "ws":
+            tokens.append((kind, match.group()))
+        pos = match.end()
+    return tokens
+
+
+def _check_operator_run(text: str, pos: int) -> None:
+    """Reject runs of operator characters such as '===' or '~=' as a whole."""
+    run = _OPERATOR_RUN_RE.match(text, pos)
+    if not run:
+        return
+    # Trailing '!' is a negation of the following operand, e.g. '&&!'
+    body = run.group().rstrip("!")
+    if body and body not in _BINARY_OPERATORS:
+        raise ConditionSyntaxError(f"Unknown operator '{run.group()}' at position {pos}")
+
+
+def parse_condition(text: str) -> Expr:
+    """Parse a condition string into an AST, raising ConditionSyntaxError."""
+    if not text.strip():
+        raise ConditionSyntaxError("Condition is empty")
+    parser = _Parser(tokenize(text))
+    expr = parser.parse_or()
+    if parser.pos != len(parser.tokens):
+        raise ConditionSyntaxError(f"Unexpected token '{parser.tokens[parser.pos][1]}'")
+    return expr
+
+
+class _Parser:
+    def __init__(self, tokens: List[Tuple[str, str]]):
+        self.tokens = tokens
+        self.pos = 0
+        self.depth = 0
+
+    def _peek(self) -> str:
+        return self.tokens[self.pos][1] if self.pos < len(self.tokens) else ""
+
+    def _next(self) -> Tuple[str, str]:
+        if self.pos >= len(self.tokens):
+            raise ConditionSyntaxError("Unexpected end of condition")
+        token = self.tokens[self.pos]
+        self.pos += 1
+        return token
+
+    def _expect(self, text: str) -> None:
+        kind, value = self._next()
+        if value != text:
+            raise ConditionSyntaxError(f"Expected '{text}' but found '{value}'")
+
+    def parse_or(self) -> Expr:
+        parts = [self.parse_and()]
+        while self._peek() == "||":
+            self._next()
+            parts.append(self.parse_and())
+        return parts[0] if len(parts) == 1 else ("or", parts)
+
+    def parse_and(self) -> Expr:
+        parts = [self.parse_unary()]
+        while self._peek() == "&&":
+            self._next()
+            parts.append(self.parse_unary())
+        return parts[0] if len(parts) == 1 else ("and", parts)
+
+    def _descend(self) -> None:
+        self.depth += 1
+        if self.depth > MAX_CONDITION_DEPTH:
+            raise ConditionSyntaxError(
+                f"Condition nested too deeply (more than {MAX_CONDITION_DEPTH} levels of '!' and '(')"
+            )
+
+    def parse_unary(self) -> Expr:
+        if self._peek() == "!":
+            self._next()
+            self._descend()
+            expr = ("not", self.parse_unary())
+            self.depth -= 1
+            return expr
+        return self.parse_primary()
+
+    def parse_primary(self) -> Expr:
+        if self._peek() == "(":
+            self._next()
+            self._descend()
+            expr = self.parse_or()
+            if self._peek() != ")":
+                raise ConditionSyntaxError("Unbalanced parentheses: missing ')'")
+            self._next()
+            self.depth -= 1
+            return expr
+        if self._peek() == ")":
+            raise ConditionSyntaxError("Unbalanced parentheses: unexpected ')'")
+        left = self.parse_operand()
+        if self._peek() in COMPARISON_OPERATORS:
+            op = self._next()[1]
+            return ("cmp", op, left, self.parse_operand())
+        if left[0] == "ref" and len(left[1]) == 1:
+            name = left[1][0]
+            if name in STATUS_KEYWORDS:
+                return ("status", name)
+            raise ConditionSyntaxError(
+                f"Bare identifier '{name}' is not a condition; expected 'success', "
+                f"'failure' or a reference such as '{name}.field'"
+            )
+        return left
+
+    def parse_operand(self) -> Expr:
+        kind, value = self._next()
+        if kind == "string":
+            return ("lit", _unquote(value))
+        if kind == "number":
+            return ("lit", float(value) if "." in value else int(value))
+        if kind == "ident":
+            if value in _LITERALS:
+                return ("lit", _LITERALS[value])
+            segments: List[Any] = [value]
+            while self._peek() in (".", "["):
+                if self._next()[1] == ".":
+                    seg_kind, seg = self._next()
+                    if seg_kind != "ident":
+                        raise ConditionSyntaxError(f"Expected field name after '.', found '{seg}'")
+                    segments.append(seg)
+                else:
+                    idx_kind, idx = self._next()
+                    if idx_kind != "number" or not idx.isdigit():
+                        raise ConditionSyntaxError(f"Expected list index, found '{idx}'")
+                    segments.append(int(idx))
+                    self._expect("]")
+            return ("ref", segments)
+        raise ConditionSyntaxError(f"Expected a value or reference, found '{value}'")
+
+
+def _unquote(token: str) -> str:
+    body = token[1:-1]
+    return re.sub(r"\\(.)", r"\1", body)
+
+
+# ─── Evaluation ─────────────────────────────────────────────────────
+
+
+def condition_references(expr: Expr) -> List[List[Any]]:
+    """Every reference in a parsed condition, as segment lists, in source order."""
+    kind = expr[0]
+    if kind in ("or", "and"):
+        return [ref for e in expr[1] for ref in condition_references(e)]
+    if kind == "not":
+        return condition_references(expr[1])
+    if kind == "cmp":
+        return condition_references(expr[2]) + condition_references(expr[3])
+    if kind == "ref":
+        return [list(expr[1])]
+    return []
+
+
+def evaluate_condition(
+    condition: Optional[str],
+    outputs: Dict[str, Any],
+    succeeded: bool = True,
+    inputs: Optional[Dict[str, Any]] = None,
+) -> bool:
+    """
+    Evaluate an edge condition.
+
+    An absent or empty condition is always true. `succeeded` is the outcome
+    of the edge's source node and backs the 'success'/'failure' keywords;
+    `inputs` backs 'input.*' references.
+    """
+    if condition is None or condition == "":
+        return True
+    return _truthy(_evaluate(parse_condition(condition), outputs, succeeded, inputs or {}))
+
+
+def _evaluate(expr: Expr, outputs: Dict[str, Any], succeeded: bool, inputs: Dict[str, Any]) -> Any:
+    kind = expr[0]
+    if kind == "or":
+        return any(_truthy(_evaluate(e, outputs, succeeded, inputs)) for e in expr[1])
+    if kind == "and":
+        return all(_truthy(_evaluate(e, outputs, succeeded, inputs)) for e in expr[1])
+    if kind == "not":
+        return not _truthy(_evaluate(expr[1], outputs, succeeded, inputs))
+    if kind == "status":
+        return succeeded if expr[1] == "success" else not succeeded
+    if kind == "lit":
+        return expr[1]
+    if kind == "ref":
+        return resolve_reference(expr[1], outputs, inputs)
+    if kind == "cmp":
+        left = _evaluate(expr[2], outputs, succeeded, inputs)
+        right = _evaluate(expr[3], outputs, succeeded, inputs)
+        return _compare(expr[1], left, right)
+    raise ValueError(f"Unknown condition node: {kind}")
+
+
+def resolve_reference(
+    segments: List[Any],
+    outputs: Dict[str, Any],
+    inputs: Optional[Dict[str, Any]] = None,
+) -> Any:
+    """
+    Resolve ['node', 'output', 'field', 0] against node outputs, or
+    ['input', 'field'] against the inputs; None if missing.
+    """
+    if segments[0] == INPUT_ROOT:
+        value: Any = inputs or {}
+        rest = list(segments[1:])
+    elif segments[0] in outputs:
+        value = outputs[segments[0]]
+        rest = list(segments[1:])
+        # 'node.output.x' and 'node.x' both address the node's output
+        if rest and rest[0] == "output" and not (isinstance(value, dict) and "output" in value):
+            rest = rest[1:]
+    else:
+        return None
+    for segment in rest:
+        if isinstance(segment, int) and isinstance(value, list) and 0 <= segment < len(value):
+            value = value[segment]
+        elif isinstance(segment, str) and isinstance(value, dict) and segment in value:
+            value = value[segment]
+        else:
+            return None
+    return value
+
+
+def _compare(op: str, left: Any, right: Any) -> bool:
+    if op == "==":
+        return _loose_equal(left, right)
+    if op == "!=":
+        return not _loose_equal(left, right)
+    # Ordering only between two numbers or two strings
+    numeric = _is_number(left) and _is_number(right)
+    if not numeric and not (isinstance(left, str) and isinstance(right, str)):
+        return False
+    if op == "<":
+        return left < right
+    if op == "<=":
+        return left <= right
+    if op == ">":
+        return left > right
+    return left >= right
+
+
+def _loose_equal(left: Any, right: Any) -> bool:
+    # Booleans are not numbers here: true != 1
+    if isinstance(left, bool) or isinstance(right, bool):
+        return left is right
+    return left == right
+
+
+def _is_number(value: Any) -> bool:
+    return isinstance(value, (int, float)) and not isinstance(value, bool)
+
+
+def _truthy(value: Any) -> bool:
+    return bool(value)
+
+
+# ─── Static analysis ────────────────────────────────────────────────
+
+
+def conditions_contradict(a: Expr, b: Expr, same_source: bool) -> bool:
+    """
+    Whether two parsed conditions can never both hold.
+
+    Conservative: only the `ref == literal` and `ref != literal`
+    comparisons ANDed at the top of each condition are compared, so a
+    False means "could not prove it", not "compatible". The status
+    keywords are compared only when both conditions are on edges from the
+    same source node, since they refer to that node's outcome.
+    """
+    for key, op, value in _facts(a, same_source):
+        for other_key, other_op, other_value in _facts(b, same_source):
+            if key != other_key or "==" not in (op, other_op):
+                continue
+            equal = _loose_equal(value, other_value)
+            if (op == other_op and not equal) or (op != other_op and equal):
+                return True
+    return False
+
+
+def conditions_equivalent(a: Expr, b: Expr, same_source: bool) -> bool:
+    """
+    Whether two parsed conditions are the same expression. Ones using the
+    status keywords are only the same on edges from the same source node.
+    """
+    return a == b and (same_source or not _uses_status(a))
+
+
+def _facts(expr: Expr, same_source: bool) -> List[Tuple[Any, str, Any]]:
+    """The (reference, '==' | '!=', literal) comparisons a condition requires."""
+    conjuncts = expr[1] if expr[0] == "and" else [expr]
+    facts: List[Tuple[Any, str, Any]] = []
+    for conjunct in conjuncts:
+        if conjunct[0] == "status" and same_source:
+            facts.append((("status",), "==", conjunct[1]))
+        elif conjunct[0] == "cmp" and conjunct[1] in ("==", "!="):
+            _, op, left, right = conjunct
+            if left[0] == "lit" and right[0] == "ref":
+                left, right = right, left
+            if left[0] == "ref" and right[0] == "lit":
+                facts.append((("ref", tuple(left[1])), op, right[1]))
+    return facts
+
+
+def _uses_status(expr: Expr) -> bool:
+    kind = expr[0]
+    if kind in ("or", "and"):
+        return any(_uses_status(e) for e in expr[1])
+    if kind == "not":
+        return _uses_status(expr[1])
+    return kind == "status"
@@ -0,0 +1,1048 @@
+"""
+Project JADE - Crypto
+Signing primitives for the JADE protocol.
+
+Provides:
+1. Canonical JSON serialization (RFC 8785 style) for stable hashing
+2. Pure-Python Ed25519 signing and verification (RFC 8032)
+3. Pure-Python ECDSA over secp256k1 (SHA-256, RFC 6979 nonces)
+4. Key encoding, prefixes and fingerprints
+5. KeyRegistry of trusted signer keys, pinned by fingerprint
+
+Zero external dependencies: the Ed25519 implementation follows the
+reference code in RFC 8032 section 6. Neither implementation is
+constant-time, which is acceptable for verification and offline signing
+of skill files. Ed448 is recognised but not implemented; signatures
+using it are reported as unsupported rather than misverified.
+"""
+
+from __future__ import annotations
+
+import base64
+import hashlib
+import hmac
+import json
+import math
+import os
+import re
+import time
+from dataclasses import dataclass
+from decimal import Decimal
+from enum import Enum
+from typing import Any, Dict, Iterable, List, Optional, Tuple, Union
+
+from .errors import CryptoError, JadeIOError, JadeParseError
+from .models import JadeSkill, SignerRole
+
+# Public key prefixes that encode the signer role, e.g. "jade-pk-root-<base64>"
+PUBLIC_KEY_PREFIXES = ("jade-pk-root-", "jade-pk-ci-")
+PRIVATE_KEY_PREFIX = "jade-sk-"
+
+SIGNATURE_ALGORITHM = "ed25519"
+
+
+class SignatureAlgorithm(str, Enum):
+    """Values accepted in JadeSignature.algorithm."""
+    ED25519 = "ed25519"
+    ECDSA_SECP256K1 = "ecdsa-secp256k1"
+    ED448 = "ed448"
+
+    @classmethod
+    def parse(cls, value: str) -> SignatureAlgorithm:
+        """Parse an algorithm name case-insensitively; empty means Ed25519."""
+        name = (value or SIGNATURE_ALGORITHM).strip().lower()
+        name = _ALGORITHM_ALIASES.get(name, name)
+        for algorithm in cls:
+            if algorithm.value == name:
+                return algorithm
+        raise CryptoError(
+            CryptoError.UNSUPPORTED_ALGORITHM,
+            f"Unknown signature algorithm '{value}'. Expected one of: "
+            f"{', '.join(a.value for a in cls)}",
+        )
+
+
+_ALGORITHM_ALIASES = {"secp256k1": "ecdsa-secp256k1", "es256k": "ecdsa-secp256k1"}
+
+# Algorithms this build can verify; others yield UNSUPPORTED_ALGORITHM
+IMPLEMENTED_ALGORITHMS = frozenset({SignatureAlgorithm.ED25519, SignatureAlgorithm.ECDSA_SECP256K1})
+
+# Raw public key lengths: Ed25519, compressed/uncompressed SEC1, Ed448
+PUBLIC_KEY_LENGTHS = {
+    SignatureAlgorithm.ED25519: (32,),
+    SignatureAlgorithm.ECDSA_SECP256K1: (33, 65),
+    SignatureAlgorithm.ED448: (57,),
+}
+
+# Multibase prefix of base58btc, and the multicodec prefixes of the key
+# types a multibase public key may carry (as in did:key)
+MULTIBASE_BASE58BTC = "z"
+MULTICODEC_PUBLIC_KEYS = {
+    b"\xed\x01": SignatureAlgorithm.ED25519,
+    b"\xe7\x01": SignatureAlgorithm.ECDSA_SECP256K1,
+}
+_B58_ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+# SubjectPublicKeyInfo algorithm OIDs, to name the keys PEM input may hold
+_ED25519_OID = "1.3.101.112"
+_SPKI_ALGORITHM_NAMES = {
+    _ED25519_OID: "Ed25519",
+    "1.3.101.113": "Ed448",
+    "1.3.101.110": "X25519",
+    "1.2.840.10045.2.1": "EC",
+    "1.2.840.113549.1.1.1": "RSA",
+}
+_PEM_RE = re.compile(r"-----BEGIN ([A-Z0-9 ]+)-----(.*?)-----END \1-----", re.DOTALL)
+
+
+class UnsupportedAlgorithmError(CryptoError):
+    """Raised when asked to verify with an algorithm that is not implemented."""
+
+    def __init__(self, message: str):
+        super().__init__(CryptoError.UNSUPPORTED_ALGORITHM, message)
+
+# Fields excluded from the signed content (a signature cannot sign itself)
+SIGNATURE_FIELDS = ("jade_signature", "community_signatures")
+
+
+# ─── Canonical JSON ─────────────────────────────────────────────────
+
+# Largest magnitude up to which every integer is exactly a double
+_MAX_SAFE_INTEGER = 2 ** 53
+
+
+def canonical_json(value: Any) -> str:
+    """
+    Serialize a JSON value canonically (RFC 8785 style).
+
+    Object keys are sorted by UTF-16 code units, no insignificant whitespace
+    is emitted and numbers use the shortest round-trip form, with integral
+    floats rendered as integers. Integers beyond ±2**53 are written as the
+    IEEE 754 double they parse to elsewhere, as RFC 8785 requires. Two
+    semantically equal documents always produce the same string regardless
+    of key order or number spelling.
+    """
+    parts: list = []
+    _write_canonical(value, parts)
+    return "".join(parts)
+
+
+def _write_canonical(value: Any, out: list) -> None:
+    if value is None:
+        out.append("null")
+    elif value is True:
+        out.append("true")
+    elif value is False:
+        out.append("false")
+    elif isinstance(value, int):
+        out.append(str(value) if abs(value) <= _MAX_SAFE_INTEGER else _canonical_number(_to_double(value)))
+    elif isinstance(value, float):
+        out.append(_canonical_number(value))
+    elif isinstance(value, str):
+        out.append(json.dumps(value, ensure_ascii=False))
+    elif isinstance(value, dict):
+        out.append("{")
+        for i, key in enumerate(sorted(value, key=lambda k: str(k).encode("utf-16-be"))):
+            if i:
+                out.append(",")
+            out.append(json.dumps(str(key), ensure_ascii=False))
+            out.append(":")
+            _write_canonical(value[key], out)
+        out.append("}")
+    elif isinstance(value, (list, tuple)):
+        out.append("[")
+        for i, item in enumerate(value):
+            if i:
+                out.append(",")
+            _write_canonical(item, out)
+        out.append("]")
+    else:
+        raise TypeError(f"Value of type {type(value).__name__} is not JSON serializable")
+
+
+def _to_double(value: int) -> float:
+    """An integer as the double a JSON parser reads it as; too large for one is rejected."""
+    try:
+        return float(value)
+    except OverflowError:
+        raise ValueError(f"Integer {value} is out of range for a JSON number") from None
+
+
+def _canonical_number(value: float) -> str:
+    """Format a float the way ECMAScript's Number.prototype.toString does."""
+    if math.isnan(value) or math.isinf(value):
+        raise ValueError("Non-finite numbers cannot be canonicalized")
+    if value == 0:
+        return "0"
+    # repr gives the shortest digits that round-trip, as ECMAScript requires;
+    # only the placement of the decimal point differs
+    _, digit_tuple, exponent = Decimal(repr(abs(value))).as_tuple()
+    digits = "".join(map(str, digit_tuple)).rstrip("0")
+    point = len(digit_tuple) + exponent  # value = 0.<digits> * 10**point
+    sign = "-" if value < 0 else ""
+    if len(digits) <= point <= 21:
+        return sign + digits + "0" * (point - len(digits))
+    if 0 < point <= 21:
+        return f"{sign}{digits[:point]}.{digits[point:]}"
+    if -6 < point <= 0:
+        return f"{sign}0.{'0' * -point}{digits}"
+    mantissa = digits[0] + ("." + digits[1:] if len(digits) > 1 else "")
+    return f"{sign}{mantissa}e{'+' if point > 0 else '-'}{abs(point - 1)}"
+
+
+class HashAlgorithm(str, Enum):
+    """Digests a content_hash may use, named by its '<algorithm>:' prefix."""
+    SHA256 = "sha256"
+    SHA512 = "sha512"
+    BLAKE2B = "blake2b"
+
+    @property
+    def hex_length(self) -> int:
+        return _HASH_HEX_LENGTHS[self]
+
+    def new(self) -> Any:
+        """A fresh hashlib object for this algorithm."""
+        return hashlib.new(self.value)
+
+
+_HASH_HEX_LENGTHS = {HashAlgorithm.SHA256: 64, HashAlgorithm.SHA512: 128, HashAlgorithm.BLAKE2B: 128}
+
+# Algorithm of content hashes written without an explicit choice
+DEFAULT_HASH_ALGORITHM = HashAlgorithm.SHA256
+
+
+def content_hash(content: str, algorithm: HashAlgorithm = DEFAULT_HASH_ALGORITHM) -> str:
+    """Hash canonical content, returning an algorithm-prefixed digest ('sha256:<hex>')."""
+    algorithm = HashAlgorithm(algorithm)
+    digest = algorithm.new()
+    _update_text(digest, content)
+    return f"{algorithm.value}:{digest.hexdigest()}"
+
+
+# Characters encoded at a time when hashing text, so a large skill is never
+# held a second time as bytes
+_HASH_CHUNK_CHARS = 64 * 1024
+
+
+def _update_text(digest: Any, text: str) -> None:
+    """Feed the UTF-8 encoding of `text` to a hashlib object, a chunk at a time."""
+    for start in range(0, len(text), _HASH_CHUNK_CHARS):
+        digest.update(text[start:start + _HASH_CHUNK_CHARS].encode("utf-8"))
+
+
+# Algorithms a declared content_hash may name, with their hex digest length
+CONTENT_HASH_ALGORITHMS = {a.value: a.hex_length for a in HashAlgorithm}
+
+_HEX_DIGEST_RE = re.compile(r"^[0-9a-f]+$")
+
+
+def parse_content_hash(value: str) -> Tuple[HashAlgorithm, str]:
+    """
+    Split 'sha256:<hex>' into (algorithm, digest), raising ValueError if the
+    prefix is missing or unsupported, or the digest is not lowercase hex of
+    the algorithm's length. The prefix must be lowercase, as content_hash
+    writes it.
+    """
+    if not isinstance(value, str) or ":" not in value:
+        raise ValueError("expected '<algorithm>:<hex digest>', e.g. 'sha256:...'")
+    algorithm, digest = value.split(":", 1)
+    length = CONTENT_HASH_ALGORITHMS.get(algorithm)
+    if length is None:
+        supported = ", ".join(sorted(CONTENT_HASH_ALGORITHMS))
+        raise ValueError(f"unsupported hash algorithm '{algorithm}' (supported: {supported})")
+    if len(digest) != length or not _HEX_DIGEST_RE.match(digest):
+        raise ValueError(f"{algorithm} digest must be {length} lowercase hex characters")
+    return HashAlgorithm(algorithm), digest
+
+
+# ─── Ed25519 (RFC 8032 reference implementation) ─────────────────────
+
+_P = 2 ** 255 - 19
+_L = 2 ** 252 + 27742317777372353535851937790883648493
+_D = -121665 * pow(121666, _P - 2, _P) % _P
+_SQRT_M1 = pow(2, (_P - 1) // 4, _P)
+
+_Point = Tuple[int, int, int, int]
+
+
+def _recover_x(y: int, sign: int) -> Optional[int]:
+    if y >= _P:
+        return None
+    x2 = (y * y - 1) * pow(_D * y * y + 1, _P - 2, _P)
+    if x2 == 0:
+        return None if sign else 0
+    x = pow(x2, (_P + 3) // 8, _P)
+    if (x * x - x2) % _P != 0:
+        x = x * _SQRT_M1 % _P
+    if (x * x - x2) % _P != 0:
+        return None
+    if (x & 1) != sign:
+        x = _P - x
+    return x
+
+
+_GY = 4 * pow(5, _P - 2, _P) % _P
+_GX = _recover_x(_GY, 0)
+_G: _Point = (_GX, _GY, 1, _GX * _GY % _P)  # type: ignore[operator]
+
+
+def _point_add(p: _Point, q: _Point) -> _Point:
+    a = (p[1] - p[0]) * (q[1] - q[0]) % _P
+    b = (p[1] + p[0]) * (q[1] + q[0]) % _P
+    c = 2 * p[3] * q[3] * _D % _P
+    d = 2 * p[2] * q[2] % _P
+    e, f, g, h = b - a, d - c, d + c, b + a
+    return (e * f, g * h, f * g, e * h)
+
+
+def _point_mul(s: int, p: _Point) -> _Point:
+    q: _Point = (0, 1, 1, 0)
+    while s > 0:
+        if s & 1:
+            q = _point_add(q, p)
+        p = _point_add(p, p)
+        s >>= 1
+    return q
+
+
+def _point_equal(p: _Point, q: _Point) -> bool:
+    if (p[0] * q[2] - q[0] * p[2]) % _P != 0:
+        return False
+    return (p[1] * q[2] - q[1] * p[2]) % _P == 0
+
+
+def _point_compress(p: _Point) -> bytes:
+    zinv = pow(p[2], _P - 2, _P)
+    x = p[0] * zinv % _P
+    y = p[1] * zinv % _P
+    return int.to_bytes(y | ((x & 1) << 255), 32, "little")
+
+
+def _point_decompress(s: bytes) -> Optional[_Point]:
+    if len(s) != 32:
+        return None
+    y = int.from_bytes(s, "little")
+    sign = y >> 255
+    y &= (1 << 255) - 1
+    x = _recover_x(y, sign)
+    if x is None:
+        return None
+    return (x, y, 1, x * y % _P)
+
+
+def _sha512_modq(data: bytes) -> int:
+    return int.from_bytes(hashlib.sha512(data).digest(), "little") % _L
+
+
+def _secret_expand(seed: bytes) -> Tuple[int, bytes]:
+    if len(seed) != 32:
+        raise CryptoError(CryptoError.INVALID_KEY, "Ed25519 private key must be 32 bytes")
+    h = hashlib.sha512(seed).digest()
+    a = int.from_bytes(h[:32], "little")
+    a &= (1 << 254) - 8
+    a |= 1 << 254
+    return a, h[32:]
+
+
+def ed25519_public_key(seed: bytes) -> bytes:
+    """Derive the 32-byte Ed25519 public key from a 32-byte seed."""
+    a, _ = _secret_expand(seed)
+    return _point_compress(_point_mul(a, _G))
+
+
+def ed25519_sign(seed: bytes, message: bytes) -> bytes:
+    """Produce a 64-byte Ed25519 signature."""
+    a, prefix = _secret_expand(seed)
+    public = _point_compress(_point_mul(a, _G))
+    r = _sha512_modq(prefix + message)
+    r_enc = _point_compress(_point_mul(r, _G))
+    h = _sha512_modq(r_enc + public + message)
+    s = (r + h * a) % _L
+    return r_enc + int.to_bytes(s, 32, "little")
+
+
+def ed25519_verify(public: bytes, message: bytes, signature: bytes) -> bool:
+    """Verify a 64-byte Ed25519 signature. Never raises on malformed input."""
+    if len(public) != 32 or len(signature) != 64:
+        return False
+    a = _point_decompress(public)
+    if a is None:
+        return False
+    r = _point_decompress(signature[:32])
+    if r is None:
+        return False
+    s = int.from_bytes(signature[32:], "little")
+    if s >= _L:
+        return False
+    h = _sha512_modq(signature[:32] + public + message)
+    return _point_equal(_point_mul(s, _G), _point_add(r, _point_mul(h, a)))
+
+
+# ─── ECDSA secp256k1 (SEC 2, RFC 6979) ─────────────────────────────
+
+_K1_P = 2 ** 256 - 2 ** 32 - 977
+_K1_N = 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141
+_K1_G = (
+    0x79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798,
+    0x483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8,
+)
+
+_AffinePoint = Optional[Tuple[int, int]]  # None is the point at infinity
+
+
+def _k1_add(p: _AffinePoint, q: _AffinePoint) -> _AffinePoint:
+    if p is None:
+        return q
+    if q is None:
+        return p
+    if p[0] == q[0]:
+        if (p[1] + q[1]) % _K1_P == 0:
+            return None
+        slope = 3 * p[0] * p[0] * pow(2 * p[1], _K1_P - 2, _K1_P) % _K1_P
+    else:
+        slope = (q[1] - p[1]) * pow(q[0] - p[0], _K1_P - 2, _K1_P) % _K1_P
+    x = (slope * slope - p[0] - q[0]) % _K1_P
+    return x, (slope * (p[0] - x) - p[1]) % _K1_P
+
+
+def _k1_mul(k: int, p: _AffinePoint) -> _AffinePoint:
+    result: _AffinePoint = None
+    while k > 0:
+        if k & 1:
+            result = _k1_add(result, p)
+        p = _k1_add(p, p)
+        k >>= 1
+    return result
+
+
+def _k1_decode_point(data: bytes) -> _AffinePoint:
+    """Decode a SEC1 compressed (33-byte) or uncompressed (65-byte) point."""
+    if len(data) == 65 and data[0] == 4:
+        x, y = int.from_bytes(data[1:33], "big"), int.from_bytes(data[33:], "big")
+    elif len(data) == 33 and data[0] in (2, 3):
+        x = int.from_bytes(data[1:], "big")
+        if x >= _K1_P:
+            return None
+        y = pow((x ** 3 + 7) % _K1_P, (_K1_P + 1) // 4, _K1_P)
+        if (y & 1) != (data[0] & 1):
+            y = _K1_P - y
+    else:
+        return None
+    if x >= _K1_P or y >= _K1_P or (y * y - x ** 3 - 7) % _K1_P != 0:
+        return None
+    return x, y
+
+
+def _k1_secret(secret: bytes) -> int:
+    d = int.from_bytes(secret, "big")
+    if len(secret) != 32 or not 1 <= d < _K1_N:
+        raise CryptoError(CryptoError.INVALID_KEY, "secp256k1 private key must be 32 bytes in [1, n)")
+    return d
+
+
+def _rfc6979_nonce(d: int, digest: bytes) -> int:
+    """Deterministic ECDSA nonce (RFC 6979 section 3.2, HMAC-SHA256)."""
+    x = d.to_bytes(32, "big")
+    h1 = (int.from_bytes(digest, "big") % _K1_N).to_bytes(32, "big")
+    v, k = b"\x01" * 32, b"\x00" * 32
+    k = hmac.new(k, v + b"\x00" + x + h1, hashlib.sha256).digest()
+    v = hmac.new(k, v, hashlib.sha256).digest()
+    k = hmac.new(k, v + b"\x01" + x + h1, hashlib.sha256).digest()
+    v = hmac.new(k, v, hashlib.sha256).digest()
+    while True:
+        v = hmac.new(k, v, hashlib.sha256).digest()
+        nonce = int.from_bytes(v, "big")
+        if 1 <= nonce < _K1_N:
+            return nonce
+        k = hmac.new(k, v + b"\x00", hashlib.sha256).digest()
+        v = hmac.new(k, v, hashlib.sha256).digest()
+
+
+def secp256k1_public_key(secret: bytes) -> bytes:
+    """Derive the 33-byte compressed secp256k1 public key from a 32-byte secret."""
+    point = _k1_mul(_k1_secret(secret), _K1_G)
+    assert point is not None
+    return bytes([2 + (point[1] & 1)]) + point[0].to_bytes(32, "big")
+
+
+def secp256k1_sign(secret: bytes, message: bytes) -> bytes:
+    """Produce a 64-byte r||s ECDSA signature over SHA-256(message), low-s normalised."""
+    d = _k1_secret(secret)
+    digest = hashlib.sha256(message).digest()
+    z = int.from_bytes(digest, "big")
+    k = _rfc6979_nonce(d, digest)
+    point = _k1_mul(k, _K1_G)
+    assert point is not None
+    r = point[0] % _K1_N
+    s = pow(k, _K1_N - 2, _K1_N) * (z + r * d) % _K1_N
+    if s > _K1_N // 2:
+        s = _K1_N - s
+    return r.to_bytes(32, "big") + s.to_bytes(32, "big")
+
+
+def secp256k1_verify(public: bytes, message: bytes, signature: bytes) -> bool:
+    """Verify a 64-byte r||s ECDSA signature. Never raises on malformed input."""
+    q = _k1_decode_point(public)
+    if q is None or len(signature) != 64:
+        return False
+    r, s = int.from_bytes(signature[:32], "big"), int.from_bytes(signature[32:], "big")
+    if not (1 <= r < _K1_N and 1 <= s < _K1_N):
+        return False
+    z = int.from_bytes(hashlib.sha256(message).digest(), "big")
+    w = pow(s, _K1_N - 2, _K1_N)
+    point = _k1_add(_k1_mul(z * w % _K1_N, _K1_G), _k1_mul(r * w % _K1_N, q))
+    return point is not None and point[0] % _K1_N == r
+
+
+# ─── Key encoding ───────────────────────────────────────────────────
+
+
+def strip_key_prefix(key: str) -> str:
+    """Remove a 'jade-pk-<role>-' prefix from an encoded public key."""
+    for prefix in PUBLIC_KEY_PREFIXES:
+        if key.startswith(prefix):
+            return key[len(prefix):]
+    return key
+
+
+def key_role(key: str) -> SignerRole:
+    """The signer role named by a key's 'jade-pk-<role>-' prefix, or UNKNOWN."""
+    for prefix in PUBLIC_KEY_PREFIXES:
+        if key.strip().startswith(prefix):
+            return SignerRole.parse(prefix[len("jade-pk-"):-1])
+    return SignerRole.UNKNOWN
+
+
+def _b64decode(value: str, what: str) -> bytes:
+    try:
+        return base64.b64decode(value, validate=True)
+    except (ValueError, TypeError) as e:
+        raise CryptoError(CryptoError.INVALID_KEY, f"{what} is not valid base64: {e}") from None
+
+
+def parse_public_key(key: str, algorithm: Optional[SignatureAlgorithm] = SignatureAlgorithm.ED25519) -> bytes:
+    """
+    Decode a public key into raw bytes, detecting its encoding.
+
+    After an optional 'jade-pk-<role>-' prefix the key may be:
+    - PEM 'PUBLIC KEY' (SubjectPublicKeyInfo DER); only Ed25519 keys
+    - multibase base58btc ('z...') with an ed25519-pub or secp256k1-pub
+      multicodec prefix, as used by did:key
+    - plain base64 of the raw key
+
+    The length is checked against the algorithm; with algorithm=None any
+    length used by a known algorithm is accepted. Raises CryptoError
+    (INVALID_KEY) for anything else, including PEM or multibase keys of a
+    different algorithm.
+    """
+    text = strip_key_prefix(key.strip())
+    encoded: Optional[SignatureAlgorithm] = None
+    if text.startswith("-----BEGIN"):
+        raw, encoded = _pem_public_key(text), SignatureAlgorithm.ED25519
+    elif text.startswith(MULTIBASE_BASE58BTC) and not _is_base64_key(text):
+        encoded, raw = _multibase_public_key(text)
+    else:
+        raw = _b64decode(text, "Public key")
+    if encoded is not None and algorithm is not None and encoded != algorithm:
+        raise CryptoError(CryptoError.INVALID_KEY, f"Public key is a {encoded.value} key, not {algorithm.value}")
+
+    if algorithm is None:
+        allowed = {n for lengths in PUBLIC_KEY_LENGTHS.values() for n in lengths}
+        if len(raw) not in allowed:
+            raise CryptoError(CryptoError.INVALID_KEY, f"Public key has unsupported length {len(raw)} bytes")
+    elif len(raw) not in PUBLIC_KEY_LENGTHS[algorithm]:
+        expected = " or ".join(str(n) for n in PUBLIC_KEY_LENGTHS[algorithm])
+        raise CryptoError(
+            CryptoError.INVALID_KEY,
+            f"{algorithm.value} public key must be {expected} bytes, got {len(raw)}",
+        )
+    return raw
+
+
+def decode_public_key(key: str, algorithm: Optional[SignatureAlgorithm] = SignatureAlgorithm.ED25519) -> bytes:
+    """Decode a public key into raw bytes; see parse_public_key for the accepted encodings."""
+    return parse_public_key(key, algorithm)
+
+
+def public_key_to_pem(raw: bytes) -> str:
+    """PEM SubjectPublicKeyInfo encoding of a raw 32-byte Ed25519 public key."""
+    if len(raw) != 32:
+        raise CryptoError(CryptoError.INVALID_KEY, f"Ed25519 public key must be 32 bytes, got {len(raw)}")
+    der = bytes.fromhex("302a300506032b6570032100") + raw
+    body = base64.b64encode(der).decode("ascii")
+    return f"-----BEGIN PUBLIC KEY-----\n{body}\n-----END PUBLIC KEY-----\n"
+
+
+def public_key_to_multibase(raw: bytes, algorithm: SignatureAlgorithm = SignatureAlgorithm.ED25519) -> str:
+    """Multibase base58btc encoding ('z...') of a raw public key with its multicodec prefix."""
+    codec = next((c for c, a in MULTICODEC_PUBLIC_KEYS.items() if a == algorithm), None)
+    if codec is None:
+        raise CryptoError(CryptoError.UNSUPPORTED_ALGORITHM, f"No multicodec for {algorithm.value} keys")
+    return MULTIBASE_BASE58BTC + _b58encode(codec + raw)
+
+
+def _is_base64_key(text: str) -> bool:
+    """Whether text is base64 of a key-sized value (base58 is a subset of base64's alphabet)."""
+    try:
+        raw = base64.b64decode(text, validate=True)
+    except (ValueError, TypeError):
+        return False
+    return any(len(raw) in lengths for lengths in PUBLIC_KEY_LENGTHS.values())
+
+
+def _pem_public_key(text: str) -> bytes:
+    match = _PEM_RE.fullmatch(text.strip())
+    if not match or match.group(1) != "PUBLIC KEY":
+        label = match.group(1) if match else "malformed"
+        raise CryptoError(CryptoError.INVALID_KEY, f"Expected a PEM 'PUBLIC KEY' block, got {label!r}")
+    der = _b64decode("".join(match.group(2).split()), "PEM public key")
+    try:
+        spki, end = _der_element(der, 0, 0x30)
+        if end != len(der):
+            raise ValueError("trailing data after SubjectPublicKeyInfo")
+        algorithm_id, pos = _der_element(spki, 0, 0x30)
+        oid, _ = _der_element(algorithm_id, 0, 0x06)
+        bits, _ = _der_element(spki, pos, 0x03)
+        oid_text = _oid_text(oid)
+    except ValueError as e:
+        raise CryptoError(CryptoError.INVALID_KEY, f"PEM public key is not valid SubjectPublicKeyInfo DER: {e}") from None
+    if oid_text != _ED25519_OID:
+        name = _SPKI_ALGORITHM_NAMES.get(oid_text, f"algorithm {oid_text}")
+        raise CryptoError(CryptoError.INVALID_KEY, f"PEM public key is an {name} key; only Ed25519 is supported")
+    if not bits or bits[0] != 0:
+        raise CryptoError(CryptoError.INVALID_KEY, "PEM public key has a malformed BIT STRING")
+    return bits[1:]
+
+
+def _der_element(data: bytes, pos: int, tag: int) -> Tuple[bytes, int]:
+    """Contents and end offset of the DER element at `pos`, which must have `tag`."""
+    if pos + 2 > len(data) or data[pos] != tag:
+        raise ValueError(f"expected tag 0x{tag:02x} at offset {pos}")
+    length, pos = data[pos + 1], pos + 2
+    if length & 0x80:
+        size = length & 0x7F
+        if not 1 <= size <= 4 or pos + size > len(data):
+            raise ValueError("bad length")
+        length, pos = int.from_bytes(data[pos:pos + size], "big"), pos + size
+    if pos + length > len(data):
+        raise ValueError("element runs past the end of the data")
+    return data[pos:pos + length], pos + length
+
+
+def _oid_text(oid: bytes) -> str:
+    if not oid or oid[-1] & 0x80:
+        raise ValueError("malformed OBJECT IDENTIFIER")
+    arcs: List[int] = []
+    value = 0
+    for byte in oid:
+        value = (value << 7) | (byte & 0x7F)
+        if not byte & 0x80:
+            arcs.append(value)
+            value = 0
+    first = min(arcs[0] // 40, 2)
+    return ".".join(str(a) for a in [first, arcs[0] - 40 * first] + arcs[1:])
+
+
+def _multibase_public_key(text: str) -> Tuple[SignatureAlgorithm, bytes]:
+    data = _b58decode(text[len(MULTIBASE_BASE58BTC):])
+    for codec, algorithm in MULTICODEC_PUBLIC_KEYS.items():
+        if data.startswith(codec):
+            return algorithm, data[len(codec):]
+    raise CryptoError(
+        CryptoError.INVALID_KEY, f"Multibase public key has unknown multicodec prefix 0x{data[:2].hex()}",
+    )
+
+
+def _b58encode(data: bytes) -> str:
+    value = int.from_bytes(data, "big")
+    out = ""
+    while value:
+        value, digit = divmod(value, 58)
+        out = _B58_ALPHABET[digit] + out
+    zeros = len(data) - len(data.lstrip(b"\0"))
+    return "1" * zeros + out
+
+
+def _b58decode(text: str) -> bytes:
+    value = 0
+    for char in text:
+        digit = _B58_ALPHABET.find(char)
+        if digit < 0:
+            raise CryptoError(CryptoError.INVALID_KEY, f"Multibase public key has invalid base58 character {char!r}")
+        value = value * 58 + digit
+    zeros = len(text) - len(text.lstrip("1"))
+    body = value.to_bytes((value.bit_length() + 7) // 8, "big")
+    return b"\0" * zeros + body
+
+
+def key_fingerprint(public_key: str) -> str:
+    """Short, stable fingerprint of a public key (first 16 hex chars of SHA-256)."""
+    return hashlib.sha256(decode_public_key(public_key, algorithm=None)).hexdigest()[:16]
+
+
+def verify_signature(
+    content: bytes,
+    signature: str,
+    public_key: str,
+    algorithm: SignatureAlgorithm = SignatureAlgorithm.ED25519,
+) -> bool:
+    """
+    Verify a base64 signature over content with a public key in any
+    encoding parse_public_key accepts (base64, PEM or multibase).
+
+    Malformed keys or signatures return False. Raises UnsupportedAlgorithmError
+    for algorithms outside IMPLEMENTED_ALGORITHMS.
+    """
+    if algorithm not in IMPLEMENTED_ALGORITHMS:
+        raise UnsupportedAlgorithmError(f"Signature algorithm '{algorithm.value}' is not supported")
+    try:
+        raw_key = parse_public_key(public_key, algorithm)
+        raw_sig = base64.b64decode(signature, validate=True)
+    except (ValueError, TypeError):
+        return False
+    if algorithm == SignatureAlgorithm.ECDSA_SECP256K1:
+        return secp256k1_verify(raw_key, content, raw_sig)
+    return ed25519_verify(raw_key, content, raw_sig)
+
+
+def generate_keypair(role: Optional[str] = None) -> Tuple[str, str]:
+    """
+    Generate a fresh Ed25519 keypair as (public_key, private_key) strings.
+
+    The seed comes from os.urandom, the OS CSPRNG. With a role ("root" or
+    "ci") the public key carries the matching 'jade-pk-<role>-' prefix.
+    The private key is always 'jade-sk-' prefixed.
+    """
+    keypair = JadeKeyPair.generate()
+    public_key = keypair.public_key
+    if role is not None:
+        public_key = _role_prefix(role) + public_key
+    return public_key, PRIVATE_KEY_PREFIX + keypair.private_key
+
+
+def _role_prefix(role: str) -> str:
+    prefix = f"jade-pk-{role}-"
+    if prefix not in PUBLIC_KEY_PREFIXES:
+        raise CryptoError(
+            CryptoError.INVALID_KEY,
+            f"Unknown key role '{role}'. Expected one of: "
+            f"{', '.join(p[len('jade-pk-'):-1] for p in PUBLIC_KEY_PREFIXES)}",
+        )
+    return prefix
+
+
+def sign(private_key: str, content: bytes) -> str:
+    """Sign content with a base64 private key, returning a base64 signature."""
+    return JadeKeyPair.from_private_key(private_key).sign(content)
+
+
+def signable_content(data: Union[Dict[str, Any], JadeSkill]) -> str:
+    """
+    Canonical JSON of a skill with signature fields removed.
+
+    This is the signing contract: the root signature and every community
+    signature are over the UTF-8 bytes of this string, and content_hash is
+    its hash. Signers in other languages must reproduce it exactly. A
+    JadeSkill is serialized from the document it was parsed from.
+    """
+    if isinstance(data, JadeSkill):
+        if not data.raw_data:
+            raise ValueError(f"Skill '{data.skill_id}' has no source document to sign")
+        data = data.raw_data
+    return canonical_json({k: v for k, v in data.items() if k not in SIGNATURE_FIELDS})
+
+
+def signable_bytes(data: Union[Dict[str, Any], JadeSkill]) -> bytes:
+    """The exact bytes a signature covers: signable_content encoded as UTF-8."""
+    return signable_content(data).encode("utf-8")
+
+
+def canonical_skill(data: Dict[str, Any]) -> Tuple[str, str]:
+    """
+    Return (full, signable): canonical JSON of a skill dict with and without
+    its signature fields.
+
+    Equal to (canonical_json(data), signable_content(data)), but each
+    top-level member is serialized once and shared by both strings.
+    """
+    members = []
+    for key in sorted(data, key=lambda k: str(k).encode("utf-16-be")):
+        members.append((key, json.dumps(str(key), ensure_ascii=False) + ":" + canonical_json(data[key])))
+    full = "{" + ",".join(m for _, m in members) + "}"
+    signable = "{" + ",".join(m for k, m in members if k not in SIGNATURE_FIELDS) + "}"
+    return full, signable
+
+
+def signable_and_hash(data: Dict[str, Any]) -> Tuple[str, str]:
+    """
+    Return (signable, full_hash): signable_content(data) and the sha256 hex
+    digest of canonical_json(data).
+
+    Like canonical_skill, but the full canonical string is hashed piece by
+    piece as it is serialized rather than built, and the signable string
+    is joined once, which saves two copies of the skill in memory.
+    """
+    digest = hashlib.sha256(b"{")
+    parts: List[str] = ["{"]
+    for i, key in enumerate(sorted(data, key=lambda k: str(k).encode("utf-16-be"))):
+        out = [] if key in SIGNATURE_FIELDS else parts
+        if out is parts and len(parts) > 1:
+            parts.append(",")
+        start = len(out)
+        out.append(json.dumps(str(key), ensure_ascii=False) + ":")
+        _write_canonical(data[key], out)
+        if i:
+            digest.update(b",")
+        for part in out[start:]:
+            _update_text(digest, part)
+    parts.append("}")
+    digest.update(b"}")
+    return "".join(parts), digest.hexdigest()
+
+
+def sign_skill(
+    data: Dict[str, Any],
+    private_key: str,
+    signer: str = "",
+    signed_at: Optional[str] = None,
+    role: Optional[str] = None,
+    hash_algorithm: HashAlgorithm = DEFAULT_HASH_ALGORITHM,
+) -> Dict[str, Any]:
+    """
+    Return a copy of a skill dict sealed with a root jade_signature.
+
+    Existing community_signatures are kept: they cover the same signable
+    content, so re-signing the root does not invalidate them. With a role
+    ("root" or "ci") the embedded public key carries that role's prefix.
+    `hash_algorithm` only selects the declared content_hash; the signature
+    is over the content itself, whatever the hash.
+    """
+    keypair = JadeKeyPair.from_private_key(private_key)
+    public_key = keypair.public_key if role is None else _role_prefix(role) + keypair.public_key
+    content = signable_content(data)
+    signed = dict(data)
+    signed["jade_signature"] = {
+        "signer": signer,
+        "algorithm": SIGNATURE_ALGORITHM,
+        "public_key": public_key,
+        "content_hash": content_hash(content, hash_algorithm),
+        "signature": keypair.sign(content.encode("utf-8")),
+        "signed_at": signed_at or time.strftime("%Y-%m-%dT%H:%M:%SZ", time.gmtime()),
+    }
+    return signed
+
+
+@dataclass
+class SignatureReport:
+    """
+    What Layer 5 verifies for a skill, for debugging a failed signature.
+
+    `signed_content` is the exact canonical text whose UTF-8 bytes the root
+    signature and community signatures cover; diffing it against the
+    signer's copy shows what changed. Fields of an absent or malformed
+    jade_signature are empty, and `signature_valid` is None when there is
+    nothing to check or the algorithm is not implemented.
+    """
+    signed_content: str
+    computed_hash: str
+    stripped_fields: List[str]
+    declared_hash: str = ""
+    algorithm: str = ""
+    public_key: str = ""
+    fingerprint: str = ""
+    signature_valid: Optional[bool] = None
+
+    @property
+    def hash_matches(self) -> bool:
+        return not self.declared_hash or self.declared_hash == self.computed_hash
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "signed_content": self.signed_content,
+            "computed_hash": self.computed_hash,
+            "declared_hash": self.declared_hash,
+            "hash_matches": self.hash_matches,
+            "stripped_fields": list(self.stripped_fields),
+            "algorithm": self.algorithm,
+            "public_key": self.public_key,
+            "fingerprint": self.fingerprint,
+            "signature_valid": self.signature_valid,
+        }
+
+
+def inspect_signature(data: Dict[str, Any]) -> SignatureReport:
+    """
+    Report the content a validator hashes and verifies for a skill dict.
+
+    Uses signable_content, so the same SIGNATURE_FIELDS are stripped as
+    during verification. Raises ValueError if the skill cannot be
+    canonicalized (e.g. a NaN parameter).
+    """
+    content = signable_content(data)
+    report = SignatureReport(
+        signed_content=content,
+        computed_hash=content_hash(content),
+        stripped_fields=[f for f in SIGNATURE_FIELDS if f in data],
+    )
+    sig = data.get("jade_signature")
+    if not isinstance(sig, dict):
+        return report
+
+    report.declared_hash = str(sig.get("content_hash") or "")
+    try:
+        hash_algorithm, _ = parse_content_hash(report.declared_hash)
+    except ValueError:
+        pass  # No declared hash, or a malformed one: keep the default algorithm's
+    else:
+        report.computed_hash = content_hash(content, hash_algorithm)
+    report.algorithm = str(sig.get("algorithm") or SIGNATURE_ALGORITHM)
+    report.public_key = str(sig.get("public_key") or "")
+    try:
+        report.fingerprint = key_fingerprint(report.public_key)
+    except ValueError:
+        pass  # Malformed key: no fingerprint, and the signature cannot verify
+    try:
+        algorithm = SignatureAlgorithm.parse(report.algorithm)
+    except ValueError:
+        return report
+    if algorithm in IMPLEMENTED_ALGORITHMS:
+        report.signature_valid = verify_signature(
+            content.encode("utf-8"), str(sig.get("signature") or ""), report.public_key, algorithm,
+        )
+    return report
+
+
+class JadeKeyPair:
+    """
+    An Ed25519 keypair with JADE string encodings.
+
+    Usage:
+        kp = JadeKeyPair.generate()
+        sig = kp.sign(b"content")
+        assert JadeKeyPair.verify(b"content", sig, kp.public_key)
+    """
+
+    def __init__(self, seed: bytes):
+        if len(seed) != 32:
+            raise CryptoError(CryptoError.INVALID_KEY, "Ed25519 private key must be 32 bytes")
+        self._seed = seed
+        self._public = ed25519_public_key(seed)
+
+    @classmethod
+    def generate(cls) -> JadeKeyPair:
+        """Generate a fresh keypair from the OS CSPRNG."""
+        return cls(os.urandom(32))
+
+    @classmethod
+    def from_private_key(cls, private_key: str) -> JadeKeyPair:
+        """Load a keypair from a base64 private key (optionally 'jade-sk-' prefixed)."""
+        key = private_key.strip()
+        if key.startswith(PRIVATE_KEY_PREFIX):
+            key = key[len(PRIVATE_KEY_PREFIX):]
+        return cls(_b64decode(key, "Private key"))
+
+    @property
+    def private_key(self) -> str:
+        return base64.b64encode(self._seed).decode("ascii")
+
+    @property
+    def public_key(self) -> str:
+        return base64.b64encode(self._public).decode("ascii")
+
+    @property
+    def fingerprint(self) -> str:
+        return key_fingerprint(self.public_key)
+
+    def sign(self, content: bytes) -> str:
+        """Sign content, returning a base64 signature."""
+        return base64.b64encode(ed25519_sign(self._seed, content)).decode("ascii")
+
+    @staticmethod
+    def verify(content: bytes, signature: str, public_key: str) -> bool:
+        return verify_signature(content, signature, public_key)
+
+
+# ─── Trusted keys ───────────────────────────────────────────────────
+
+
+@dataclass
+class TrustedKey:
+    """A signer public key pinned in a KeyRegistry."""
+    fingerprint: str
+    role: str
+    public_key: str
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {"fingerprint": self.fingerprint, "role": self.role, "public_key": self.public_key}
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> TrustedKey:
+        """Parse a registry entry, checking the fingerprint matches the key."""
+        if not isinstance(data, dict) or "public_key" not in data:
+            raise JadeParseError("Key registry entry is missing 'public_key'")
+        public_key = data["public_key"]
+        actual = key_fingerprint(public_key)
+        declared = data.get("fingerprint") or actual
+        if declared != actual:
+            raise CryptoError(
+                CryptoError.FINGERPRINT_MISMATCH,
+                f"Fingerprint '{declared}' does not match public key (actual '{actual}')",
+            )
+        return cls(fingerprint=actual, role=data.get("role", "root"), public_key=public_key)
+
+
+class KeyRegistry:
+    """
+    Signer keys a validator trusts, keyed by fingerprint.
+
+    Usage:
+        registry = KeyRegistry.from_file("trusted_keys.json")
+        validator = JadeValidator.with_key_registry(registry)
+
+    The file holds a JSON list of {"fingerprint", "role", "public_key"}
+    entries (or {"keys": [...]}).
+    """
+
+    def __init__(self, keys: Optional[Iterable[TrustedKey]] = None):
+        self._keys: Dict[str, TrustedKey] = {}
+        for key in keys or []:
+            self._keys[key.fingerprint] = key
+
+    def add(self, public_key: str, role: str = "root") -> TrustedKey:
+        """Trust a public key under the given role."""
+        key = TrustedKey(fingerprint=key_fingerprint(public_key), role=role, public_key=public_key)
+        self._keys[key.fingerprint] = key
+        return key
+
+    def get(self, fingerprint: str) -> Optional[TrustedKey]:
+        return self._keys.get(fingerprint)
+
+    def __contains__(self, fingerprint: object) -> bool:
+        return fingerprint in self._keys
+
+    def __len__(self) -> int:
+        return len(self._keys)
+
+    def to_list(self) -> List[Dict[str, Any]]:
+        return [k.to_dict() for k in self._keys.values()]
+
+    @classmethod
+    def from_list(cls, entries: List[Dict[str, Any]]) -> KeyRegistry:
+        return cls(TrustedKey.from_dict(e) for e in entries)
+
+    @classmethod
+    def from_file(cls, path: str) -> KeyRegistry:
+        """Load a registry from a JSON file. Raises JadeIOError, JadeParseError or CryptoError."""
+        try:
+            with open(path, "r", encoding="utf-8") as f:
+                data = json.load(f)
+        except OSError as e:
+            raise JadeIOError(f"Cannot read key registry {path}: {e}", path) from e
+        except json.JSONDecodeError as e:
+            raise JadeParseError(f"Key registry {path} is not valid JSON: {e}") from e
+        if isinstance(data, dict):
+            data = data.get("keys", [])
+        if not isinstance(data, list):
+            raise JadeParseError("Key registry must be a list of key entries")
+        return cls.from_list(data)
+
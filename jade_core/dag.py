@@ -3,23 +3,59 @@ Project JADE - DAG (Directed Acyclic Graph) Engine
 Validates and analyzes execution DAGs for structural correctness.
 
 This module does NOT execute DAGs - it only validates their structure.
-Execution is the responsibility of the local Agent runtime.
+Execution lives in executor.py and is driven by the local Agent runtime.
 JADE only guarantees the DAG is safe and well-formed.
 """
 
 from __future__ import annotations
 
 from collections import deque
-from typing import Any, Dict, List, Optional, Set, Tuple
-
+from dataclasses import asdict, dataclass
+from typing import Any, Dict, Iterable, List, Optional, Set, Tuple
+
+from .conditions import (
+    ConditionSyntaxError,
+    condition_references,
+    conditions_contradict,
+    conditions_equivalent,
+    parse_condition,
+)
+from .errors import CycleError
 from .models import (
     DAGEdge,
     DAGNode,
     ExecutionDAG,
+    IssueCode,
     JadeSkill,
     ValidationIssue,
     ValidationSeverity,
 )
+from .templates import INPUT_ROOT, RESERVED_ROOTS, template_references
+
+# Node timeout used when neither the node nor its params declare one
+DEFAULT_NODE_TIMEOUT_MS = 30000
+
+
+@dataclass
+class DAGMetrics:
+    """
+    Size and shape of one execution DAG, for catalog dashboards.
+
+    Only edges between declared nodes count towards fan-out, fan-in,
+    depth and connectivity; repeated edges count once. max_depth is the
+    number of edges on the longest path, or -1 if the DAG has a cycle.
+    """
+    node_count: int
+    edge_count: int
+    root_count: int
+    sink_count: int
+    max_fan_out: int
+    max_fan_in: int
+    max_depth: int
+    connected: bool  # All nodes form one weakly connected component
+
+    def to_dict(self) -> Dict[str, Any]:
+        return asdict(self)
 
 
 class DAGAnalyzer:
@@ -29,29 +65,59 @@ class DAGAnalyzer:
     Checks:
     1. Structural integrity (all referenced nodes exist)
     2. Acyclicity (no cycles - it's a DAG after all)
-    3. Reachability (all nodes reachable from entry)
+    3. Reachability (all nodes reachable from entry, and from some root)
     4. Exit reachability (at least one exit node reachable)
     5. No orphan nodes
     6. No duplicate node IDs
-    7. Edge condition validity
+    7. Edge condition syntax (see conditions.py)
     8. Entry/exit node validity
+    9. No self-loop edges
+    10. No empty actions on nodes with outgoing edges
+    11. A single root node (policy; see `multiple_roots`)
+    12. No duplicate edges
+    13. Conditions only read inputs declared in input_schema
     """
 
+    def __init__(self, multiple_roots: Optional[ValidationSeverity] = ValidationSeverity.WARNING):
+        # Severity for DAGs with more than one root; None disables the check
+        self.multiple_roots = multiple_roots
+
     def validate(self, skill: JadeSkill) -> List[ValidationIssue]:
         """Run all DAG validation checks."""
         issues: List[ValidationIssue] = []
         dag = skill.execution_dag
 
+        issues.extend(self._check_self_loops(dag))
         issues.extend(self._check_node_uniqueness(dag))
         issues.extend(self._check_entry_node(dag))
         issues.extend(self._check_exit_nodes(dag))
         issues.extend(self._check_edge_references(dag))
+        issues.extend(self._check_duplicate_edges(dag))
         issues.extend(self._check_acyclicity(dag))
+        issues.extend(self._check_root_reachability(dag))
+        issues.extend(self._check_multiple_roots(dag))
         issues.extend(self._check_reachability(dag))
         issues.extend(self._check_exit_reachability(dag))
         issues.extend(self._check_edge_conditions(dag))
+        issues.extend(self._check_conflicting_conditions(dag))
         issues.extend(self._check_orphan_nodes(dag))
+        issues.extend(self._check_empty_actions(dag))
+        issues.extend(self._check_template_references(dag))
+        issues.extend(self._check_condition_inputs(skill))
+
+        return issues
 
+    def _check_self_loops(self, dag: ExecutionDAG) -> List[ValidationIssue]:
+        """Flag edges whose source and target are the same node."""
+        issues: List[ValidationIssue] = []
+        for i, edge in enumerate(dag.edges):
+            if edge.from_node == edge.to_node:
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.ERROR,
+                    code=IssueCode.DAG_SELF_LOOP,
+                    message=f"Self-loop edge on node: {edge.from_node}",
+                    path=f"execution_dag.edges[{i}]",
+                ))
         return issues
 
     def _check_node_uniqueness(self, dag: ExecutionDAG) -> List[ValidationIssue]:
@@ -62,7 +128,7 @@ class DAGAnalyzer:
             if node.id in seen:
                 issues.append(ValidationIssue(
                     severity=ValidationSeverity.ERROR,
-                    code="DAG_DUPLICATE_NODE",
+                    code=IssueCode.DAG_DUPLICATE_NODE,
                     message=f"Duplicate node ID '{node.id}' at index {i} "
                             f"(first seen at index {seen[node.id]})",
                     path=f"execution_dag.nodes[{i}].id",
@@ -78,7 +144,7 @@ class DAGAnalyzer:
         if dag.entry_node not in node_ids:
             issues.append(ValidationIssue(
                 severity=ValidationSeverity.ERROR,
-                code="DAG_INVALID_ENTRY",
+                code=IssueCode.DAG_INVALID_ENTRY,
                 message=f"Entry node '{dag.entry_node}' does not exist in nodes list",
                 path="execution_dag.entry_node",
             ))
@@ -92,14 +158,14 @@ class DAGAnalyzer:
             if exit_id not in node_ids:
                 issues.append(ValidationIssue(
                     severity=ValidationSeverity.ERROR,
-                    code="DAG_INVALID_EXIT",
+                    code=IssueCode.DAG_INVALID_EXIT,
                     message=f"Exit node '{exit_id}' does not exist in nodes list",
                     path="execution_dag.exit_node",
                 ))
         if not dag.exit_node:
             issues.append(ValidationIssue(
                 severity=ValidationSeverity.ERROR,
-                code="DAG_NO_EXIT",
+                code=IssueCode.DAG_NO_EXIT,
                 message="DAG must have at least one exit node",
                 path="execution_dag.exit_node",
             ))
@@ -113,68 +179,136 @@ class DAGAnalyzer:
             if edge.from_node not in node_ids:
                 issues.append(ValidationIssue(
                     severity=ValidationSeverity.ERROR,
-                    code="DAG_INVALID_EDGE_SRC",
+                    code=IssueCode.DAG_INVALID_EDGE_SRC,
                     message=f"Edge {i} references non-existent source node '{edge.from_node}'",
                     path=f"execution_dag.edges[{i}].from",
                 ))
             if edge.to_node not in node_ids:
                 issues.append(ValidationIssue(
                     severity=ValidationSeverity.ERROR,
-                    code="DAG_INVALID_EDGE_DST",
+                    code=IssueCode.DAG_INVALID_EDGE_DST,
                     message=f"Edge {i} references non-existent target node '{edge.to_node}'",
                     path=f"execution_dag.edges[{i}].to",
                 ))
         return issues
 
+    def _check_duplicate_edges(self, dag: ExecutionDAG) -> List[ValidationIssue]:
+        """
+        Flag repeated edges, usually a copy-paste mistake.
+
+        Edges between the same nodes with different conditions are distinct
+        branches, so the condition is part of an edge's identity.
+        """
+        issues: List[ValidationIssue] = []
+        seen: Set[Tuple[str, str, str]] = set()
+        for i, edge in enumerate(dag.edges):
+            key = (edge.from_node, edge.to_node, edge.condition or "")
+            if key in seen:
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.WARNING,
+                    code=IssueCode.DAG_DUPLICATE_EDGE,
+                    message=f"Duplicate edge: {edge.from_node} -> {edge.to_node}",
+                    path=f"execution_dag.edges[{i}]",
+                ))
+            seen.add(key)
+        return issues
+
     def _check_acyclicity(self, dag: ExecutionDAG) -> List[ValidationIssue]:
-        """Detect cycles using DFS-based topological sort."""
+        """Report every strongly connected component that forms a cycle."""
         issues: List[ValidationIssue] = []
+        for component, cycle in self._find_cycle_components(dag):
+            message = f"Cycle detected: {' -> '.join(cycle)}"
+            if len(component) > len(cycle) - 1:
+                message += (f" ({len(component)} nodes in strongly connected component: "
+                            f"{', '.join(component)})")
+            issues.append(ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.DAG_CYCLE_DETECTED,
+                message=message,
+                path="execution_dag.edges",
+            ))
+        return issues
 
-        # Build adjacency list
+    def _reachable_from_roots(self, dag: ExecutionDAG) -> Tuple[List[str], Set[str]]:
+        """Return (roots, reached): zero in-degree nodes and everything they reach."""
+        node_ids = {n.id for n in dag.nodes}
+        roots = self.get_roots(dag)
         adj: Dict[str, List[str]] = {n.id: [] for n in dag.nodes}
         for edge in dag.edges:
-            if edge.from_node in adj:
+            if edge.from_node in adj and edge.to_node in node_ids:
                 adj[edge.from_node].append(edge.to_node)
 
-        # DFS cycle detection
-        WHITE, GRAY, BLACK = 0, 1, 2
-        color: Dict[str, int] = {n.id: WHITE for n in dag.nodes}
-        path: List[str] = []
-
-        def dfs(node_id: str) -> bool:
-            color[node_id] = GRAY
-            path.append(node_id)
-            for neighbor in adj.get(node_id, []):
-                if color.get(neighbor) == GRAY:
-                    cycle_start = path.index(neighbor)
-                    cycle = path[cycle_start:] + [neighbor]
-                    issues.append(ValidationIssue(
-                        severity=ValidationSeverity.ERROR,
-                        code="DAG_CYCLE_DETECTED",
-                        message=f"Cycle detected: {' -> '.join(cycle)}",
-                        path="execution_dag.edges",
-                    ))
-                    return True
-                if color.get(neighbor) == WHITE:
-                    if dfs(neighbor):
-                        return True
-            color[node_id] = BLACK
-            path.pop()
-            return False
+        reached: Set[str] = set()
+        queue = deque(roots)
+        while queue:
+            current = queue.popleft()
+            if current in reached:
+                continue
+            reached.add(current)
+            queue.extend(n for n in adj[current] if n not in reached)
+        return roots, reached
+
+    def _check_root_reachability(self, dag: ExecutionDAG) -> List[ValidationIssue]:
+        """
+        Every node must be reachable from some zero in-degree root.
+
+        A node that no root reaches can only be entered through a cycle, so
+        it can never run. A DAG with edges but no root at all is reported
+        on its own, since every node is then unreachable.
+        """
+        issues: List[ValidationIssue] = []
+        if not dag.nodes:
+            return issues
+        roots, reached = self._reachable_from_roots(dag)
+        if not roots:
+            issues.append(ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.DAG_NO_ROOT,
+                message="DAG has no root node: every node has an incoming edge",
+                path="execution_dag.edges",
+            ))
+            return issues
 
+        seen: Set[str] = set()
         for node in dag.nodes:
-            if color[node.id] == WHITE:
-                dfs(node.id)
-
+            if node.id not in reached and node.id not in seen:
+                seen.add(node.id)
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.ERROR,
+                    code=IssueCode.DAG_UNREACHABLE_FROM_ROOT,
+                    message=f"Unreachable node: {node.id}",
+                    path=f"execution_dag.nodes.{node.id}",
+                ))
         return issues
 
+    def _check_multiple_roots(self, dag: ExecutionDAG) -> List[ValidationIssue]:
+        """Flag DAGs with more than one root, per the `multiple_roots` policy."""
+        if self.multiple_roots is None:
+            return []
+        roots = self.get_roots(dag)
+        if len(roots) <= 1:
+            return []
+        return [ValidationIssue(
+            severity=self.multiple_roots,
+            code=IssueCode.DAG_MULTIPLE_ROOTS,
+            message=f"DAG has {len(roots)} root nodes: {', '.join(roots)}",
+            path="execution_dag.nodes",
+        )]
+
     def _check_reachability(self, dag: ExecutionDAG) -> List[ValidationIssue]:
-        """Ensure all nodes are reachable from the entry node."""
+        """
+        Ensure all nodes are reachable from the entry node.
+
+        Nodes that no root reaches are already errors from
+        _check_root_reachability, so they get no second warning here.
+        """
         issues: List[ValidationIssue] = []
         node_ids = {n.id for n in dag.nodes}
 
         if dag.entry_node not in node_ids:
             return issues  # Entry node check will catch this
+        roots, from_roots = self._reachable_from_roots(dag)
+        reported = node_ids - from_roots if roots else set()
 
         # BFS from entry
         reachable: Set[str] = set()
@@ -193,13 +327,14 @@ class DAGAnalyzer:
                 if neighbor not in reachable:
                     queue.append(neighbor)
 
-        unreachable = node_ids - reachable
-        for node_id in unreachable:
+        # Declaration order, so issue order does not depend on set hashing
+        reachable |= reported
+        for node_id in _unique(n.id for n in dag.nodes if n.id not in reachable):
             issues.append(ValidationIssue(
                 severity=ValidationSeverity.WARNING,
-                code="DAG_UNREACHABLE_NODE",
+                code=IssueCode.DAG_UNREACHABLE_NODE,
                 message=f"Node '{node_id}' is not reachable from entry node '{dag.entry_node}'",
-                path=f"execution_dag.nodes",
+                path=f"execution_dag.nodes.{node_id}",
             ))
 
         return issues
@@ -233,27 +368,41 @@ class DAGAnalyzer:
         if not reachable_exits:
             issues.append(ValidationIssue(
                 severity=ValidationSeverity.ERROR,
-                code="DAG_NO_REACHABLE_EXIT",
+                code=IssueCode.DAG_NO_REACHABLE_EXIT,
                 message="No exit node is reachable from the entry node",
                 path="execution_dag",
             ))
 
         return issues
 
+    def validate_conditions(self, dag: ExecutionDAG) -> List[Tuple[int, str]]:
+        """Parse every non-empty edge condition, returning (edge index, error) pairs."""
+        errors: List[Tuple[int, str]] = []
+        for i, edge in enumerate(dag.edges):
+            if edge.condition is None or edge.condition == "":
+                continue
+            if not isinstance(edge.condition, str):
+                errors.append((i, f"condition must be a string, got {type(edge.condition).__name__}"))
+                continue
+            try:
+                parse_condition(edge.condition)
+            except ConditionSyntaxError as e:
+                errors.append((i, str(e)))
+        return errors
+
     def _check_edge_conditions(self, dag: ExecutionDAG) -> List[ValidationIssue]:
         """Validate edge conditions are well-formed."""
         issues: List[ValidationIssue] = []
-        valid_conditions = {"success", "failure", None}
 
-        for i, edge in enumerate(dag.edges):
-            if edge.condition not in valid_conditions:
-                issues.append(ValidationIssue(
-                    severity=ValidationSeverity.WARNING,
-                    code="DAG_INVALID_CONDITION",
-                    message=f"Edge {i} has unknown condition '{edge.condition}'. "
-                            f"Expected 'success', 'failure', or none.",
-                    path=f"execution_dag.edges[{i}].condition",
-                ))
+        for i, error in self.validate_conditions(dag):
+            edge = dag.edges[i]
+            issues.append(ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.DAG_INVALID_CONDITION,
+                message=f"Edge {i} ({edge.from_node} -> {edge.to_node}) has invalid "
+                        f"condition '{edge.condition}': {error}",
+                path=f"execution_dag.edges[{i}].condition",
+            ))
 
         # Check that conditional edges come in pairs (success + failure)
         conditional_sources: Dict[str, List[str]] = {}
@@ -268,7 +417,7 @@ class DAGAnalyzer:
             if cond_set == {"success"} or cond_set == {"failure"}:
                 issues.append(ValidationIssue(
                     severity=ValidationSeverity.WARNING,
-                    code="DAG_INCOMPLETE_BRANCH",
+                    code=IssueCode.DAG_INCOMPLETE_BRANCH,
                     message=f"Node '{node_id}' has conditional edges but only "
                             f"covers {cond_set}. Consider adding the missing branch.",
                     path="execution_dag.edges",
@@ -276,36 +425,275 @@ class DAGAnalyzer:
 
         return issues
 
+    def _check_conflicting_conditions(self, dag: ExecutionDAG) -> List[ValidationIssue]:
+        """
+        Compare the conditions of each node's incoming conditional edges.
+
+        Best effort (see conditions_contradict): a pair that can never both
+        hold, like `x == "a"` and `x == "b"`, or the same condition twice
+        is a warning. Exact duplicate edges are DAG_DUPLICATE_EDGE instead.
+        """
+        issues: List[ValidationIssue] = []
+        incoming: Dict[str, List[Tuple[int, Any]]] = {}
+        for i, edge in enumerate(dag.edges):
+            if not edge.condition or not isinstance(edge.condition, str):
+                continue
+            try:
+                incoming.setdefault(edge.to_node, []).append((i, parse_condition(edge.condition)))
+            except ConditionSyntaxError:
+                continue  # Reported by _check_edge_conditions
+
+        for target, parsed in incoming.items():
+            for n, (i, first) in enumerate(parsed):
+                for j, second in parsed[n + 1:]:
+                    a, b = dag.edges[i], dag.edges[j]
+                    same_source = a.from_node == b.from_node
+                    if same_source and a.condition == b.condition:
+                        continue
+                    pair = f"Edges {i} ({a.from_node} -> {target}) and {j} ({b.from_node} -> {target})"
+                    if conditions_equivalent(first, second, same_source):
+                        issues.append(ValidationIssue(
+                            severity=ValidationSeverity.WARNING,
+                            code=IssueCode.DAG_DUPLICATE_CONDITION,
+                            message=f"{pair} both have condition '{b.condition}'; the conditions are "
+                                    f"redundant, since '{target}' runs once whichever edge is taken",
+                            path=f"execution_dag.edges[{j}].condition",
+                            node_id=target,
+                        ))
+                    elif conditions_contradict(first, second, same_source):
+                        issues.append(ValidationIssue(
+                            severity=ValidationSeverity.WARNING,
+                            code=IssueCode.DAG_CONTRADICTORY_CONDITIONS,
+                            message=f"{pair} have contradictory conditions "
+                                    f"('{a.condition}' vs '{b.condition}'); they can never both be taken",
+                            path=f"execution_dag.edges[{j}].condition",
+                            node_id=target,
+                        ))
+        return issues
+
     def _check_orphan_nodes(self, dag: ExecutionDAG) -> List[ValidationIssue]:
-        """Check for nodes with no incoming or outgoing edges (except entry/exit)."""
+        """
+        Check for nodes with no incoming or outgoing edges (except entry/exit).
+
+        Only edges between declared nodes count: an edge from an undeclared
+        node is reported by _check_edge_references, and does not connect its
+        declared end to anything.
+        """
         issues: List[ValidationIssue] = []
         node_ids = {n.id for n in dag.nodes}
-        has_incoming = {e.to_node for e in dag.edges}
-        has_outgoing = {e.from_node for e in dag.edges}
+        has_incoming = {e.to_node for e in dag.edges if e.from_node in node_ids}
+        has_outgoing = {e.from_node for e in dag.edges if e.to_node in node_ids}
         exit_set = set(dag.exit_node)
 
-        for node_id in node_ids:
+        for node_id in _unique(n.id for n in dag.nodes):
             if node_id == dag.entry_node:
                 continue
             if node_id not in has_incoming:
                 issues.append(ValidationIssue(
                     severity=ValidationSeverity.WARNING,
-                    code="DAG_NO_INCOMING",
-                    message=f"Non-entry node '{node_id}' has no incoming edges",
-                    path="execution_dag.edges",
+                    code=IssueCode.DAG_NO_INCOMING,
+                    message=f"Non-entry node '{node_id}' has no incoming edges"
+                            + _undeclared_note(node_id, dag, incoming=True),
+                    path=f"execution_dag.nodes.{node_id}",
                 ))
             if node_id not in has_outgoing and node_id not in exit_set:
                 issues.append(ValidationIssue(
                     severity=ValidationSeverity.WARNING,
-                    code="DAG_DEAD_END",
-                    message=f"Non-exit node '{node_id}' has no outgoing edges (dead end)",
-                    path="execution_dag.edges",
+                    code=IssueCode.DAG_DEAD_END,
+                    message=f"Non-exit node '{node_id}' has no outgoing edges (dead end)"
+                            + _undeclared_note(node_id, dag, incoming=False),
+                    path=f"execution_dag.nodes.{node_id}",
                 ))
 
         return issues
 
+    def _check_empty_actions(self, dag: ExecutionDAG) -> List[ValidationIssue]:
+        """A node with no action that feeds other nodes most likely lost its action."""
+        issues: List[ValidationIssue] = []
+        has_outgoing = {e.from_node for e in dag.edges}
+        for node in dag.nodes:
+            if not node.action and node.id in has_outgoing:
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.WARNING,
+                    code=IssueCode.DAG_EMPTY_ACTION,
+                    message=f"Node '{node.id}' has no action but has outgoing edges "
+                            f"(forgotten action?)",
+                    path=f"execution_dag.nodes.{node.id}.action",
+                ))
+        return issues
+
+    def _check_template_references(self, dag: ExecutionDAG) -> List[ValidationIssue]:
+        """A template can only read outputs of nodes that always run before its own node."""
+        issues: List[ValidationIssue] = []
+        node_ids = {n.id for n in dag.nodes}
+        ancestors = self.get_ancestors(dag)
+        for node in dag.nodes:
+            for text, segments in template_references(node.params):
+                source = segments[0]
+                if source in RESERVED_ROOTS:
+                    continue  # Never a node, whatever the ids (RESERVED_NAME)
+                if source in node_ids and source not in ancestors.get(node.id, set()):
+                    issues.append(ValidationIssue(
+                        severity=ValidationSeverity.ERROR,
+                        code=IssueCode.DAG_TEMPLATE_NOT_ANCESTOR,
+                        message=f"Node '{node.id}' references '{{{{{text}}}}}', but '{source}' "
+                                f"is not upstream of it and can never have run",
+                        path=f"execution_dag.nodes.{node.id}.params",
+                    ))
+        return issues
+
+    def _check_condition_inputs(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """An 'input.x' reference in a condition must name an input the skill declares."""
+        declared = declared_inputs(skill.input_schema)
+        if declared is None:
+            return []  # Malformed input_schema; reported by Layer 1
+        issues: List[ValidationIssue] = []
+        for i, edge in enumerate(skill.execution_dag.edges):
+            if not edge.condition or not isinstance(edge.condition, str):
+                continue
+            try:
+                references = condition_references(parse_condition(edge.condition))
+            except ConditionSyntaxError:
+                continue  # Reported by _check_edge_conditions
+            names = _unique(
+                ref[1] for ref in references
+                if ref[0] == INPUT_ROOT and len(ref) > 1 and isinstance(ref[1], str)
+            )
+            for name in names:
+                if name not in declared:
+                    issues.append(ValidationIssue(
+                        severity=ValidationSeverity.ERROR,
+                        code=IssueCode.DAG_UNDECLARED_INPUT,
+                        message=f"Edge {i} ({edge.from_node} -> {edge.to_node}) condition reads "
+                                f"'input.{name}', which input_schema does not declare",
+                        path=f"execution_dag.edges[{i}].condition",
+                    ))
+        return issues
+
+    def get_ancestors(self, dag: ExecutionDAG) -> Dict[str, Set[str]]:
+        """Map each node to every node with a path to it (excluding itself unless on a cycle)."""
+        incoming = self._incoming(dag)
+        ancestors: Dict[str, Set[str]] = {}
+        for node_id in incoming:
+            seen: Set[str] = set()
+            queue = deque(incoming[node_id])
+            while queue:
+                current = queue.popleft()
+                if current not in seen:
+                    seen.add(current)
+                    queue.extend(incoming.get(current, []))
+            ancestors[node_id] = seen
+        return ancestors
+
+    def find_cycles(self, dag: ExecutionDAG) -> List[List[str]]:
+        """
+        Return one concrete cycle path per strongly connected component,
+        e.g. ["a", "b", "c", "a"]. Self-loops are not included.
+        """
+        return [cycle for _, cycle in self._find_cycle_components(dag)]
+
+    def _find_cycle_components(self, dag: ExecutionDAG) -> List[Tuple[List[str], List[str]]]:
+        """Pair each cyclic SCC (in node declaration order) with a cycle through it."""
+        order = list(dict.fromkeys(n.id for n in dag.nodes))
+        position = {node_id: i for i, node_id in enumerate(order)}
+        adj: Dict[str, List[str]] = {node_id: [] for node_id in order}
+        for edge in dag.edges:
+            if edge.from_node in adj and edge.to_node in adj and edge.from_node != edge.to_node:
+                adj[edge.from_node].append(edge.to_node)
+
+        components = [
+            sorted(c, key=position.__getitem__)
+            for c in self._strongly_connected_components(order, adj)
+            if len(c) > 1
+        ]
+        components.sort(key=lambda c: position[c[0]])
+        return [(c, self._cycle_through(c[0], adj, set(c))) for c in components]
+
+    def _strongly_connected_components(
+        self, order: List[str], adj: Dict[str, List[str]],
+    ) -> List[List[str]]:
+        """Tarjan's algorithm, iterative so deep graphs cannot exhaust the stack."""
+        index: Dict[str, int] = {}
+        low: Dict[str, int] = {}
+        stack: List[str] = []
+        on_stack: Set[str] = set()
+        components: List[List[str]] = []
+
+        for root in order:
+            if root in index:
+                continue
+            index[root] = low[root] = len(index)
+            stack.append(root)
+            on_stack.add(root)
+            work = [(root, iter(adj[root]))]
+            while work:
+                node, neighbors = work[-1]
+                descended = False
+                for neighbor in neighbors:
+                    if neighbor not in index:
+                        index[neighbor] = low[neighbor] = len(index)
+                        stack.append(neighbor)
+                        on_stack.add(neighbor)
+                        work.append((neighbor, iter(adj[neighbor])))
+                        descended = True
+                        break
+                    if neighbor in on_stack:
+                        low[node] = min(low[node], index[neighbor])
+                if descended:
+                    continue
+                work.pop()
+                if work:
+                    parent = work[-1][0]
+                    low[parent] = min(low[parent], low[node])
+                if low[node] == index[node]:
+                    component: List[str] = []
+                    while True:
+                        member = stack.pop()
+                        on_stack.discard(member)
+                        component.append(member)
+                        if member == node:
+                            break
+                    components.append(component)
+        return components
+
+    def _cycle_through(self, start: str, adj: Dict[str, List[str]], members: Set[str]) -> List[str]:
+        """Shortest path from start back to itself, staying inside the component."""
+        parent: Dict[str, str] = {}
+        queue = deque([start])
+        while queue:
+            current = queue.popleft()
+            for neighbor in adj[current]:
+                if neighbor not in members:
+                    continue
+                if neighbor == start:
+                    path = [current]
+                    while path[-1] != start:
+                        path.append(parent[path[-1]])
+                    return list(reversed(path)) + [start]
+                if neighbor not in parent:
+                    parent[neighbor] = current
+                    queue.append(neighbor)
+        return [start, start]
+
+    def get_roots(self, dag: ExecutionDAG) -> List[str]:
+        """Return nodes with no incoming edges, in declaration order."""
+        node_ids = {n.id for n in dag.nodes}
+        has_incoming = {e.to_node for e in dag.edges if e.from_node in node_ids}
+        return _unique(n.id for n in dag.nodes if n.id not in has_incoming)
+
+    def get_sinks(self, dag: ExecutionDAG) -> List[str]:
+        """Return nodes with no outgoing edges, in declaration order."""
+        node_ids = {n.id for n in dag.nodes}
+        has_outgoing = {e.from_node for e in dag.edges if e.to_node in node_ids}
+        return _unique(n.id for n in dag.nodes if n.id not in has_outgoing)
+
     def get_topological_order(self, dag: ExecutionDAG) -> List[str]:
-        """Return nodes in topological order. Returns empty list if cycle exists."""
+        """
+        Return nodes in topological order. Returns empty list if cycle exists.
+
+        Edge conditions are ignored, so the order lists every node that
+        could run; see get_static_topological_order.
+        """
         in_degree: Dict[str, int] = {n.id: 0 for n in dag.nodes}
         adj: Dict[str, List[str]] = {n.id: [] for n in dag.nodes}
 
@@ -330,6 +718,78 @@ class DAGAnalyzer:
             return []  # Cycle exists
         return order
 
+    def get_static_topological_order(self, dag: ExecutionDAG) -> List[str]:
+        """
+        The "all possible nodes" order: every node, conditions ignored.
+
+        Which branches actually run is decided at runtime by the executor
+        as it evaluates edge conditions; nodes on branches not taken are
+        skipped. get_possibly_unreachable_nodes lists the nodes that depend
+        on such a branch. Returns an empty list if a cycle exists.
+        """
+        return self.get_topological_order(dag)
+
+    def get_possibly_unreachable_nodes(self, dag: ExecutionDAG) -> List[str]:
+        """
+        Nodes reachable from a root only through conditional edges.
+
+        These run only if some edge condition holds at runtime, so a test
+        or coverage tool needs inputs that take those branches. Nodes that
+        are not reachable at all are not listed (see DAG_UNREACHABLE_*).
+        Declaration order.
+        """
+        _, reached = self._reachable_from_roots(dag)
+        always: Set[str] = set()
+        queue = deque(self.get_roots(dag))
+        while queue:
+            current = queue.popleft()
+            if current in always:
+                continue
+            always.add(current)
+            queue.extend(
+                e.to_node for e in dag.get_outgoing_edges(current)
+                if not e.condition and e.to_node in reached
+            )
+        return _unique(n.id for n in dag.nodes if n.id in reached and n.id not in always)
+
+    def get_execution_levels(self, dag: ExecutionDAG) -> List[List[str]]:
+        """
+        Group nodes into topological waves.
+
+        Every node in a wave depends only on nodes in earlier waves, so the
+        nodes of one wave can run concurrently. Within a wave, nodes keep
+        their declaration order. Raises CycleError if the DAG has a cycle.
+        """
+        in_degree: Dict[str, int] = {n.id: 0 for n in dag.nodes}
+        adj: Dict[str, List[str]] = {n.id: [] for n in dag.nodes}
+
+        for edge in dag.edges:
+            if edge.to_node in in_degree:
+                in_degree[edge.to_node] += 1
+            if edge.from_node in adj:
+                adj[edge.from_node].append(edge.to_node)
+
+        position = {n.id: i for i, n in enumerate(dag.nodes)}
+        wave = [n for n, d in in_degree.items() if d == 0]
+        levels: List[List[str]] = []
+        placed = 0
+
+        while wave:
+            levels.append(wave)
+            placed += len(wave)
+            next_wave: Set[str] = set()
+            for current in wave:
+                for neighbor in adj.get(current, []):
+                    in_degree[neighbor] -= 1
+                    if in_degree[neighbor] == 0:
+                        next_wave.add(neighbor)
+            wave = sorted(next_wave, key=position.__getitem__)
+
+        if placed != len(in_degree):
+            stuck = sorted((n for n, d in in_degree.items() if d > 0), key=position.__getitem__)
+            raise CycleError(f"Cycle detected; cannot order nodes: {', '.join(stuck)}", stuck)
+        return levels
+
     def get_execution_paths(self, dag: ExecutionDAG) -> List[List[str]]:
         """Enumerate all possible execution paths from entry to exit nodes."""
         paths: List[List[str]] = []
@@ -355,21 +815,157 @@ class DAGAnalyzer:
         dfs(dag.entry_node, [dag.entry_node], set())
         return paths
 
-    def compute_max_depth(self, dag: ExecutionDAG) -> int:
-        """Compute the maximum depth (longest path) of the DAG."""
-        topo = self.get_topological_order(dag)
-        if not topo:
-            return -1  # Cycle
+    def get_critical_path(
+        self,
+        dag: ExecutionDAG,
+        default_timeout_ms: int = DEFAULT_NODE_TIMEOUT_MS,
+    ) -> Tuple[List[str], float]:
+        """
+        Return the longest path weighted by node timeout, and its total.
+
+        Each node weighs what the executor enforces (see node_timeout_ms);
+        nodes with no timeout at all weigh `default_timeout_ms`. The total is
+        a worst-case latency estimate for the skill. Raises CycleError if the
+        DAG has a cycle.
+        """
+        weight = {n.id: node_timeout_ms(n, default_timeout_ms) for n in dag.nodes}
+        best: Dict[str, int] = {}  # Heaviest path weight ending at each node
+        prev: Dict[str, Optional[str]] = {}
+        incoming = self._incoming(dag)
+        for node_id in self._ordered(dag):
+            best[node_id] = weight[node_id]
+            prev[node_id] = None
+            for source in incoming[node_id]:
+                if best[source] + weight[node_id] > best[node_id]:
+                    best[node_id] = best[source] + weight[node_id]
+                    prev[node_id] = source
+
+        if not best:
+            return [], 0
+        end = max(best, key=best.__getitem__)
+        path = [end]
+        while prev[path[-1]] is not None:
+            path.append(prev[path[-1]])  # type: ignore[arg-type]
+        path.reverse()
+        return path, best[end]
+
+    def get_max_depth(self, dag: ExecutionDAG) -> int:
+        """Return the number of edges on the longest path. Raises CycleError on a cycle."""
+        depth: Dict[str, int] = {}
+        incoming = self._incoming(dag)
+        for node_id in self._ordered(dag):
+            depth[node_id] = max((depth[s] + 1 for s in incoming[node_id]), default=0)
+        return max(depth.values(), default=0)
 
-        adj: Dict[str, List[str]] = {n.id: [] for n in dag.nodes}
+    def compute_max_depth(self, dag: ExecutionDAG) -> int:
+        """Compute the maximum depth (longest path) of the DAG; -1 on a cycle."""
+        try:
+            return self.get_max_depth(dag)
+        except CycleError:
+            return -1
+
+    def get_metrics(self, dag: ExecutionDAG) -> DAGMetrics:
+        """Node and edge counts, roots, sinks, fan-out/in, depth and connectivity."""
+        incoming = self._incoming(dag)
+        outgoing: Dict[str, Set[str]] = {node_id: set() for node_id in incoming}
+        for target, sources in incoming.items():
+            for source in sources:
+                outgoing[source].add(target)
+        return DAGMetrics(
+            node_count=len(dag.nodes),
+            edge_count=len(dag.edges),
+            root_count=len(self.get_roots(dag)),
+            sink_count=len(self.get_sinks(dag)),
+            max_fan_out=max((len(t) for t in outgoing.values()), default=0),
+            max_fan_in=max((len(s) for s in incoming.values()), default=0),
+            max_depth=self.compute_max_depth(dag),
+            connected=_component_count(incoming, outgoing) == 1,
+        )
+
+    def _ordered(self, dag: ExecutionDAG) -> List[str]:
+        """Node IDs in topological order; raises CycleError."""
+        return [node_id for level in self.get_execution_levels(dag) for node_id in level]
+
+    def _incoming(self, dag: ExecutionDAG) -> Dict[str, List[str]]:
+        """Map each node to the distinct known sources of its incoming edges."""
+        incoming: Dict[str, List[str]] = {n.id: [] for n in dag.nodes}
         for edge in dag.edges:
-            if edge.from_node in adj:
-                adj[edge.from_node].append(edge.to_node)
+            sources = incoming.get(edge.to_node)
+            if sources is not None and edge.from_node in incoming and edge.from_node not in sources:
+                sources.append(edge.from_node)
+        return incoming
+
+
+def node_timeout_ms(node: DAGNode, default: float = DEFAULT_NODE_TIMEOUT_MS) -> float:
+    """
+    The timeout the executor enforces on `node`: its own timeout_ms, else
+    its action's timeout_ms param, else `default`.
+    """
+    if node.timeout_ms is not None:
+        return node.timeout_ms
+    param = node.params.get("timeout_ms")
+    if isinstance(param, (int, float)) and not isinstance(param, bool):
+        return param
+    return default
 
-        dist: Dict[str, int] = {n: 0 for n in topo}
-        for node_id in topo:
-            for neighbor in adj.get(node_id, []):
-                if dist[neighbor] < dist[node_id] + 1:
-                    dist[neighbor] = dist[node_id] + 1
 
-        return max(dist.values()) if dist else 0
+def declared_inputs(schema: Any) -> Optional[Set[str]]:
+    """
+    Input names an input_schema declares: JSON Schema properties, or the
+    names in required_params / optional_params. Empty without a schema;
+    None if the schema is in neither form.
+    """
+    if schema is None:
+        return set()
+    if not isinstance(schema, dict):
+        return None
+    if "type" in schema or "properties" in schema:
+        properties = schema.get("properties", {})
+        return set(properties) if isinstance(properties, dict) else None
+    lists = [schema[key] for key in ("required_params", "optional_params") if key in schema]
+    if not lists:
+        return None
+    return {
+        p["name"] for params in lists if isinstance(params, list)
+        for p in params if isinstance(p, dict) and isinstance(p.get("name"), str)
+    }
+
+
+def _unique(ids: Iterable[str]) -> List[str]:
+    """Drop repeated IDs, keeping first-seen order."""
+    seen: Set[str] = set()
+    result: List[str] = []
+    for node_id in ids:
+        if node_id not in seen:
+            seen.add(node_id)
+            result.append(node_id)
+    return result
+
+
+def _component_count(incoming: Dict[str, List[str]], outgoing: Dict[str, Set[str]]) -> int:
+    """Number of weakly connected components in the declared-node graph."""
+    seen: Set[str] = set()
+    components = 0
+    for start in incoming:
+        if start in seen:
+            continue
+        components += 1
+        seen.add(start)
+        stack = [start]
+        while stack:
+            current = stack.pop()
+            for neighbor in list(incoming[current]) + list(outgoing[current]):
+                if neighbor not in seen:
+                    seen.add(neighbor)
+                    stack.append(neighbor)
+    return components
+
+
+def _undeclared_note(node_id: str, dag: ExecutionDAG, incoming: bool) -> str:
+    """' (only from undeclared 'x')' when the node's only edges involve missing nodes."""
+    node_ids = {n.id for n in dag.nodes}
+    if incoming:
+        others = _unique(e.from_node for e in dag.edges if e.to_node == node_id and e.from_node not in node_ids)
+        return f" (only from undeclared {', '.join(repr(o) for o in others)})" if others else ""
+    others = _unique(e.to_node for e in dag.edges if e.from_node == node_id and e.to_node not in node_ids)
+    return f" (only to undeclared {', '.join(repr(o) for o in others)})" if others else ""
@@ -0,0 +1,283 @@
+"""
+Project JADE - Skill Diff
+Semantic comparison of two versions of a skill, for registry review.
+
+    change = diff_skills(JadeSkill.from_dict(old), JadeSkill.from_dict(new))
+    if change.privilege_escalating:
+        print(change.summary())
+
+Nodes are matched by ID and edges by (from, to), so reordering the JSON is
+not a change. Security-policy changes that grant the skill more than it had
+are listed in `escalations`: network, env or file access that the old
+whitelists did not cover, a more permissive sandbox, or a shell action the
+old version did not use. A reviewer can approve everything else on sight.
+"""
+
+from __future__ import annotations
+
+from dataclasses import dataclass, field
+from typing import Any, Dict, List, Optional, Tuple
+
+from .crypto import key_fingerprint
+from .models import SHELL_ACTIONS, DAGEdge, DAGNode, JadeSkill, SandboxLevel
+from .security import domain_matches_whitelist, whitelist_network
+
+# Sandbox levels from most to least restrictive
+SANDBOX_ORDER = [SandboxLevel.STRICT, SandboxLevel.STANDARD, SandboxLevel.PERMISSIVE]
+
+_NODE_FIELDS = ("action", "params", "timeout_ms", "retry")
+
+
+@dataclass
+class FieldChange:
+    """One field whose value differs between the versions."""
+    path: str  # e.g. 'security.sandbox_level' or 'execution_dag.nodes.fetch.params'
+    old: Any
+    new: Any
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {"path": self.path, "old": self.old, "new": self.new}
+
+
+@dataclass
+class Escalation:
+    """One way the new version is more privileged than the old."""
+    path: str    # The field that grants it, e.g. 'security.env_whitelist[0]'
+    reason: str  # e.g. "env_whitelist now grants 'GITHUB_TOKEN'"
+    node_id: Optional[str] = None  # Set when one node causes it
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {"path": self.path, "reason": self.reason, "node_id": self.node_id}
+
+
+@dataclass
+class SkillDiff:
+    """What changed from one version of a skill to the next."""
+    skill_id: str
+    old_version: str
+    new_version: str
+    nodes_added: List[str] = field(default_factory=list)
+    nodes_removed: List[str] = field(default_factory=list)
+    node_changes: List[FieldChange] = field(default_factory=list)
+    edges_added: List[Tuple[str, str]] = field(default_factory=list)
+    edges_removed: List[Tuple[str, str]] = field(default_factory=list)
+    edge_changes: List[FieldChange] = field(default_factory=list)  # Conditions
+    security_changes: List[FieldChange] = field(default_factory=list)
+    metadata_changes: List[FieldChange] = field(default_factory=list)
+    escalations: List[Escalation] = field(default_factory=list)
+    signature_changed: bool = False
+    signing_key_changed: bool = False
+
+    @property
+    def privilege_escalating(self) -> bool:
+        return bool(self.escalations)
+
+    @property
+    def dag_changed(self) -> bool:
+        return bool(
+            self.nodes_added or self.nodes_removed or self.node_changes
+            or self.edges_added or self.edges_removed or self.edge_changes
+        )
+
+    @property
+    def empty(self) -> bool:
+        """No change apart from (possibly) the signature."""
+        return not (self.dag_changed or self.security_changes or self.metadata_changes)
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "skill_id": self.skill_id,
+            "old_version": self.old_version,
+            "new_version": self.new_version,
+            "privilege_escalating": self.privilege_escalating,
+            "escalations": [e.to_dict() for e in self.escalations],
+            "nodes_added": list(self.nodes_added),
+            "nodes_removed": list(self.nodes_removed),
+            "node_changes": [c.to_dict() for c in self.node_changes],
+            "edges_added": [list(e) for e in self.edges_added],
+            "edges_removed": [list(e) for e in self.edges_removed],
+            "edge_changes": [c.to_dict() for c in self.edge_changes],
+            "security_changes": [c.to_dict() for c in self.security_changes],
+            "metadata_changes": [c.to_dict() for c in self.metadata_changes],
+            "signature_changed": self.signature_changed,
+            "signing_key_changed": self.signing_key_changed,
+        }
+
+    def summary(self) -> str:
+        """A few lines for a human reviewer, escalations first."""
+        lines = [f"{self.skill_id}: {self.old_version} -> {self.new_version}"]
+        if self.escalations:
+            lines.append(f"PRIVILEGE ESCALATION ({len(self.escalations)}):")
+            lines.extend(f"  ! {e.reason}" for e in self.escalations)
+        if self.empty:
+            lines.append("No changes to the DAG, security policy or metadata")
+        for label, items in (("Nodes added", self.nodes_added), ("Nodes removed", self.nodes_removed)):
+            if items:
+                lines.append(f"{label}: {', '.join(items)}")
+        for label, edges in (("Edges added", self.edges_added), ("Edges removed", self.edges_removed)):
+            if edges:
+                lines.append(f"{label}: {', '.join(f'{a} -> {b}' for a, b in edges)}")
+        for change in self.node_changes + self.edge_changes + self.security_changes + self.metadata_changes:
+            lines.append(f"Changed {change.path}: {_describe(change)}")
+        if self.signing_key_changed:
+            lines.append("Signed with a different key")
+        elif self.signature_changed:
+            lines.append("Signature changed")
+        return "\n".join(lines)
+
+
+def diff_skills(old: JadeSkill, new: JadeSkill) -> SkillDiff:
+    """Compare two versions of a skill."""
+    result = SkillDiff(
+        skill_id=new.skill_id,
+        old_version=old.metadata.version,
+        new_version=new.metadata.version,
+    )
+    _diff_nodes(old.execution_dag.nodes, new.execution_dag.nodes, result)
+    _diff_edges(old.execution_dag.edges, new.execution_dag.edges, result)
+    _diff_security(old, new, result)
+    _diff_metadata(old, new, result)
+    _diff_signature(old, new, result)
+    return result
+
+
+def _diff_nodes(old: List[DAGNode], new: List[DAGNode], result: SkillDiff) -> None:
+    old_by_id = {n.id: n for n in old}
+    new_by_id = {n.id: n for n in new}
+    result.nodes_added = [n.id for n in new if n.id not in old_by_id]
+    result.nodes_removed = [n.id for n in old if n.id not in new_by_id]
+    for node in new:
+        before = old_by_id.get(node.id)
+        if before is None:
+            continue
+        for name in _NODE_FIELDS:
+            old_value, new_value = _plain(getattr(before, name)), _plain(getattr(node, name))
+            if old_value != new_value:
+                result.node_changes.append(
+                    FieldChange(f"execution_dag.nodes.{node.id}.{name}", old_value, new_value)
+                )
+
+    old_actions = {n.action for n in old}
+    for node in new:
+        if node.action in SHELL_ACTIONS and node.action not in old_actions:
+            result.escalations.append(Escalation(
+                f"execution_dag.nodes.{node.id}.action",
+                f"Node '{node.id}' adds shell action '{node.action}'",
+                node_id=node.id,
+            ))
+            old_actions.add(node.action)
+
+
+def _diff_edges(old: List[DAGEdge], new: List[DAGEdge], result: SkillDiff) -> None:
+    old_by_key = {(e.from_node, e.to_node): e for e in old}
+    new_by_key = {(e.from_node, e.to_node): e for e in new}
+    result.edges_added = [k for k in new_by_key if k not in old_by_key]
+    result.edges_removed = [k for k in old_by_key if k not in new_by_key]
+    for key, edge in new_by_key.items():
+        before = old_by_key.get(key)
+        if before is not None and before.condition != edge.condition:
+            result.edge_changes.append(FieldChange(
+                f"execution_dag.edges.{key[0]}->{key[1]}.condition", before.condition, edge.condition,
+            ))
+
+
+def _diff_security(old: JadeSkill, new: JadeSkill, result: SkillDiff) -> None:
+    before, after = old.security, new.security
+    for name in ("network_whitelist", "env_whitelist", "file_read_paths", "file_write_paths",
+                 "dangerous_patterns", "max_execution_time_ms", "max_retries"):
+        old_value, new_value = getattr(before, name), getattr(after, name)
+        if old_value != new_value:
+            result.security_changes.append(FieldChange(f"security.{name}", old_value, new_value))
+    if before.sandbox_level != after.sandbox_level:
+        result.security_changes.append(FieldChange(
+            "security.sandbox_level", before.sandbox_level.value, after.sandbox_level.value,
+        ))
+
+    for i, entry in enumerate(after.network_whitelist):
+        if entry not in before.network_whitelist and not _covered_host(entry, before.network_whitelist):
+            result.escalations.append(Escalation(
+                f"security.network_whitelist[{i}]", f"network_whitelist now allows '{entry}'",
+            ))
+    for i, name in enumerate(after.env_whitelist):
+        if name not in before.env_whitelist:
+            result.escalations.append(Escalation(
+                f"security.env_whitelist[{i}]", f"env_whitelist now grants '{name}'",
+            ))
+    for label, old_paths, new_paths in (
+        ("read", before.file_read_paths, after.file_read_paths),
+        ("write", before.file_write_paths, after.file_write_paths),
+    ):
+        for i, path in enumerate(new_paths):
+            if path not in old_paths:
+                result.escalations.append(Escalation(
+                    f"security.file_permissions.{label}[{i}]", f"File {label} access added for '{path}'",
+                ))
+    if SANDBOX_ORDER.index(after.sandbox_level) > SANDBOX_ORDER.index(before.sandbox_level):
+        result.escalations.append(Escalation(
+            "security.sandbox_level",
+            f"Sandbox relaxed from {before.sandbox_level.value} to {after.sandbox_level.value}",
+        ))
+    for name in ("max_execution_time_ms", "max_retries"):
+        old_value, new_value = getattr(before, name), getattr(after, name)
+        if _is_number(old_value) and _is_number(new_value) and new_value > old_value:
+            result.escalations.append(Escalation(
+                f"security.{name}", f"{name} raised from {old_value} to {new_value}",
+            ))
+
+
+def _covered_host(entry: str, whitelist: List[str]) -> bool:
+    """A plain host already allowed by an old entry (e.g. '*.example.com') adds nothing."""
+    if entry == "*" or entry.startswith("*.") or whitelist_network(entry) is not None:
+        return False
+    return domain_matches_whitelist(entry, whitelist)
+
+
+def _diff_metadata(old: JadeSkill, new: JadeSkill, result: SkillDiff) -> None:
+    if old.skill_id != new.skill_id:
+        result.metadata_changes.append(FieldChange("skill_id", old.skill_id, new.skill_id))
+    if old.jade_version != new.jade_version:
+        result.metadata_changes.append(FieldChange("jade_version", old.jade_version, new.jade_version))
+    for name in ("name", "version", "description", "author", "tags", "license"):
+        old_value, new_value = getattr(old.metadata, name), getattr(new.metadata, name)
+        if old_value != new_value:
+            result.metadata_changes.append(FieldChange(f"metadata.{name}", old_value, new_value))
+
+
+def _diff_signature(old: JadeSkill, new: JadeSkill, result: SkillDiff) -> None:
+    before, after = old.jade_signature, new.jade_signature
+    result.signature_changed = (before.signature if before else None) != (after.signature if after else None)
+    result.signing_key_changed = (
+        before is not None and after is not None
+        and _fingerprint(before.public_key) != _fingerprint(after.public_key)
+    )
+
+
+def _fingerprint(public_key: str) -> Optional[str]:
+    try:
+        return key_fingerprint(public_key)
+    except ValueError:
+        return public_key
+
+
+def _is_number(value: Any) -> bool:
+    return isinstance(value, (int, float)) and not isinstance(value, bool)
+
+
+def _plain(value: Any) -> Any:
+    """Dataclass-valued fields (RetryPolicy) as dicts, so changes serialize."""
+    return value.to_dict() if hasattr(value, "to_dict") else value
+
+
+def _describe(change: FieldChange) -> str:
+    """'old -> new', or the entries added and removed for list fields."""
+    if isinstance(change.old, list) and isinstance(change.new, list):
+        added = [v for v in change.new if v not in change.old]
+        removed = [v for v in change.old if v not in change.new]
+        parts = [f"+{_short(v)}" for v in added] + [f"-{_short(v)}" for v in removed]
+        return ", ".join(parts) or "reordered"
+    return f"{_short(change.old)} -> {_short(change.new)}"
+
+
+def _short(value: Any, limit: int = 60) -> str:
+    text = repr(value)
+    return text if len(text) <= limit else text[:limit - 3] + "..."
@@ -0,0 +1,60 @@
+"""
+Project JADE - Errors
+Structured exception types raised by the jade_core public API.
+
+Failures callers need to tell apart (I/O, parsing, cycles, crypto, missing
+resources) raise subclasses of JadeError, so callers can catch the whole
+family or branch on the class instead of matching message strings. Each
+class also derives from the built-in exception the API raised before
+(ValueError, OSError), so existing `except ValueError:` handlers still work.
+
+Validation problems are not exceptions: they are reported as
+ValidationIssue entries in a ValidationResult.
+"""
+
+from __future__ import annotations
+
+from typing import Iterable, List
+
+
+class JadeError(Exception):
+    """Base class for all jade_core errors."""
+
+
+class JadeIOError(JadeError, OSError):
+    """A file could not be read or written."""
+
+    def __init__(self, message: str, path: str = ""):
+        super().__init__(message)
+        self.path = path
+
+    def __str__(self) -> str:
+        return str(self.args[0]) if self.args else ""
+
+
+class JadeParseError(JadeError, ValueError):
+    """Input is not valid JSON, or not a well-formed JADE document or expression."""
+
+
+class CycleError(JadeError, ValueError):
+    """The execution DAG contains a cycle, so it has no execution order."""
+
+    def __init__(self, message: str, nodes: Iterable[str] = ()):
+        super().__init__(message)
+        self.nodes: List[str] = list(nodes)  # Nodes that could not be ordered
+
+
+class CryptoError(JadeError, ValueError):
+    """A key, signature or algorithm could not be used. See `kind`."""
+
+    INVALID_KEY = "invalid_key"
+    UNSUPPORTED_ALGORITHM = "unsupported_algorithm"
+    FINGERPRINT_MISMATCH = "fingerprint_mismatch"
+
+    def __init__(self, kind: str, message: str):
+        super().__init__(message)
+        self.kind = kind
+
+
+class NotFoundError(JadeError, ValueError):
+    """A skill or other named resource does not exist."""
@@ -0,0 +1,41 @@
+"""
+Project JADE - Structured Log Events
+What validation and execution do, reported through the logging module.
+
+Library code never prints. Records go to the 'jade_core' logger tree,
+which has only a NullHandler until the application configures logging:
+
+    logging.basicConfig(level=logging.DEBUG)
+
+Each record has an `event` attribute and the event's fields as further
+record attributes, so a JSON formatter or log shipper can index them
+without parsing the message:
+
+    event                 level  fields
+    validation_started    DEBUG  skill_id, source_path
+    layer_started         DEBUG  skill_id, layer
+    issue_found           DEBUG  skill_id, layer, code, severity, path, node_id
+    layer_finished        DEBUG  skill_id, layer, duration_ms, issue_count
+    signature_verified    INFO   skill_id, kind, signer, fingerprint, verified
+    validation_finished   INFO   skill_id, valid, error_count, warning_count
+    execution_started     INFO   skill_id, node_count
+    node_skipped          DEBUG  skill_id, node_id
+    node_executed         DEBUG  skill_id, node_id, action, attempts, duration_ms
+    execution_finished    INFO   skill_id, duration_ms, skipped_count
+
+A layer's or node's started/finished (or executed) pair brackets its
+work, the way a span would.
+"""
+
+from __future__ import annotations
+
+import logging
+from typing import Any
+
+LOGGER_NAME = "jade_core"
+
+
+def emit(logger: logging.Logger, level: int, event: str, message: str, **fields: Any) -> None:
+    """Log `message` with `event` and `fields` attached as record attributes."""
+    if logger.isEnabledFor(level):
+        logger.log(level, message, extra={"event": event, **fields})
@@ -0,0 +1,570 @@
+"""
+Project JADE - Executor
+Runs a validated JADE skill's execution DAG.
+
+The executor is deliberately small: nodes are visited in topological waves,
+each node's output is recorded and made available to downstream nodes, and
+every network call is checked against the skill's network_whitelist before
+it is made. Only actions with a registered handler can run; HTTP actions are
+built in, other actions can be supplied via register_action().
+
+Branching: after a node runs, each outgoing edge's condition is evaluated
+against the outputs collected so far and the run's inputs (see
+conditions.py). An edge without a condition is always taken. A node runs
+only if it has no incoming edges or at least one incoming edge was taken;
+otherwise it is skipped, and its own outgoing edges are not taken either.
+Nodes that ran succeeded (a failing node aborts the run), so 'failure'
+edges are never taken here.
+
+Templates: `{{node.output.field}}` and `{{input.name}}` in a node's params
+are resolved against the outputs collected so far and the run's inputs
+just before the node runs (see templates.py); handlers only ever see the
+resolved params. A reference without a value fails the run with
+TEMPLATE_UNRESOLVED. `{{env.NAME}}` reads the process environment (or the
+executor's `env` mapping), limited to the skill's security.env_whitelist.
+Template functions (`| upper`) are the built-ins plus any added with
+register_template_function(); validation rejects those not allowlisted.
+
+Sandbox: the skill's sandbox_level decides what may run, via
+SandboxLevel.capabilities. Under strict, a '*' whitelist entry matches
+nothing, http:// URLs are refused (INSECURE_SCHEME) and shell actions such
+as shell_exec never run (SANDBOX_VIOLATION), even with a registered handler.
+
+Retries: a node with a `retry` policy is re-run after a handler failure,
+a transport error or a response whose status is in retry_on_status, with
+an exponential backoff between attempts. The node's timeout_ms bounds all
+attempts and delays together, and the skill's max_execution_time_ms bounds
+the run as usual. Policy violations (whitelist, scheme, params) are never
+retried.
+
+Redirects: the transport never follows a 3xx response itself. The executor
+follows up to MAX_REDIRECTS of them, checking each Location against the
+scheme and network_whitelist rules like the original URL, so a whitelisted
+host cannot send a request on to one that is not.
+
+Parallelism: with JadeExecutor(parallel=True) the nodes of one wave run
+concurrently on a thread pool. Results are still recorded in wave order, so
+result.outputs is ordered the same way whichever node finishes first.
+
+Always validate a skill with JadeValidator before executing it.
+"""
+
+from __future__ import annotations
+
+import json
+import logging
+import os
+import threading
+import time
+from concurrent.futures import ThreadPoolExecutor
+from dataclasses import dataclass, field, replace
+from typing import Any, Callable, Dict, List, Mapping, Optional, Set, Tuple
+from urllib.error import HTTPError, URLError
+from urllib.parse import urljoin, urlparse
+from urllib.request import HTTPRedirectHandler, Request, build_opener
+
+from .conditions import ConditionSyntaxError, evaluate_condition
+from .dag import DAGAnalyzer, node_timeout_ms
+from .errors import CycleError, JadeError
+from .events import emit
+from .models import SHELL_ACTIONS, DAGNode, ExecutionDAG, JadeSkill, RetryPolicy
+from .security import domain_matches_whitelist
+from .templates import TemplateError, TemplateFunction, interpolate, template_references
+
+logger = logging.getLogger("jade_core.executor")
+
+HTTP_METHODS = {"GET", "POST", "PUT", "PATCH", "DELETE", "HEAD"}
+
+# 3xx statuses the executor follows, and how many in a row
+REDIRECT_STATUSES = {301, 302, 303, 307, 308}
+MAX_REDIRECTS = 5
+
+# Request headers dropped when a redirect leaves the original host
+_CREDENTIAL_HEADERS = {"authorization", "cookie", "proxy-authorization"}
+
+# ExecutionError codes a node's retry policy applies to
+RETRYABLE_CODES = {"NODE_FAILED", "HTTP_ERROR"}
+
+
+class ExecutionError(JadeError):
+    """Raised when a skill cannot be executed to completion."""
+
+    def __init__(self, code: str, message: str, node_id: Optional[str] = None):
+        super().__init__(message)
+        self.code = code
+        self.message = message
+        self.node_id = node_id
+
+    def __str__(self) -> str:
+        where = f" (node '{self.node_id}')" if self.node_id else ""
+        return f"[{self.code}] {self.message}{where}"
+
+
+@dataclass
+class ExecutionContext:
+    """State visible to an action handler while a node runs."""
+    skill: JadeSkill
+    inputs: Dict[str, Any]
+    outputs: Dict[str, Any]  # Outputs of nodes that already ran, by node id
+    timeout_s: float  # Time budget for the current node
+
+
+@dataclass
+class NodePlan:
+    """What a dry run knows about one node before anything executes."""
+    node_id: str
+    action: str
+    level: int  # Wave the node runs in, from 0
+    depends_on: List[str]  # Direct upstream nodes, in execution order
+    references: List[str]  # Template references in params, e.g. 'fetch.output.body'
+    gated_by: List[Dict[str, str]]  # Incoming edges with a condition: {"from", "condition"}
+    conditional: bool  # Runs only if some edge condition holds
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "node_id": self.node_id,
+            "action": self.action,
+            "level": self.level,
+            "depends_on": list(self.depends_on),
+            "references": list(self.references),
+            "gated_by": [dict(g) for g in self.gated_by],
+            "conditional": self.conditional,
+        }
+
+
+@dataclass
+class ExecutionResult:
+    """Outcome of executing (or dry-running) a skill."""
+    skill_id: str
+    order: List[str]
+    outputs: Dict[str, Any] = field(default_factory=dict)
+    skipped: List[str] = field(default_factory=list)  # Nodes on branches not taken
+    attempts: Dict[str, int] = field(default_factory=dict)  # Runs per node that ran
+    dry_run: bool = False
+    duration_ms: float = 0.0
+    plan: List[NodePlan] = field(default_factory=list)  # Per node, in order; dry runs only
+
+    @property
+    def node_count(self) -> int:
+        return len(self.order)
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "skill_id": self.skill_id,
+            "dry_run": self.dry_run,
+            "order": list(self.order),
+            "node_count": self.node_count,
+            "outputs": dict(self.outputs),
+            "skipped": list(self.skipped),
+            "attempts": dict(self.attempts),
+            "duration_ms": self.duration_ms,
+            "plan": [p.to_dict() for p in self.plan],
+        }
+
+
+# (node, context) -> node output (any JSON-compatible value)
+ActionHandler = Callable[[DAGNode, ExecutionContext], Any]
+
+# (method, url, headers, body, timeout_s) -> {"status_code", "body", "headers"}
+HttpTransport = Callable[[str, str, Dict[str, str], Optional[bytes], float], Dict[str, Any]]
+
+
+class _NoRedirectHandler(HTTPRedirectHandler):
+    """Surface 3xx responses instead of following them (the executor checks each hop)."""
+
+    def redirect_request(self, req, fp, code, msg, headers, newurl):
+        return None
+
+
+_opener = build_opener(_NoRedirectHandler)
+
+
+def urllib_transport(
+    method: str,
+    url: str,
+    headers: Dict[str, str],
+    body: Optional[bytes],
+    timeout_s: float,
+) -> Dict[str, Any]:
+    """
+    Default HTTP transport built on urllib. Non-2xx responses, redirects
+    included, are returned, not raised or followed.
+    """
+    req = Request(url, data=body, headers=headers, method=method)
+    try:
+        with _opener.open(req, timeout=timeout_s) as resp:
+            return {
+                "status_code": resp.status,
+                "body": resp.read().decode("utf-8", errors="replace"),
+                "headers": dict(resp.headers.items()),
+            }
+    except HTTPError as e:
+        return {
+            "status_code": e.code,
+            "body": e.read().decode("utf-8", errors="replace"),
+            "headers": dict(e.headers.items()) if e.headers else {},
+        }
+
+
+class JadeExecutor:
+    """
+    Executes a JADE skill's DAG.
+
+    Usage:
+        executor = JadeExecutor()
+        result = executor.execute(skill, inputs={"city": "Paris"})
+        print(result.outputs["fetch"]["status_code"])
+
+        # Run independent nodes of each wave concurrently
+        executor = JadeExecutor(parallel=True, max_workers=4)
+    """
+
+    def __init__(
+        self,
+        transport: Optional[HttpTransport] = None,
+        parallel: bool = False,
+        max_workers: Optional[int] = None,
+        sleep: Callable[[float], None] = time.sleep,
+        env: Optional[Mapping[str, str]] = None,
+    ):
+        self._transport = transport or urllib_transport
+        self._parallel = parallel
+        self._max_workers = max_workers
+        self._sleep = sleep  # Injectable backoff delay
+        self._env = env if env is not None else os.environ  # Filtered by each skill's env_whitelist
+        self._dag_analyzer = DAGAnalyzer()
+        self._handlers: Dict[str, ActionHandler] = {
+            "http_request": self._http_request,
+            "http_get": self._http_request,
+            "http_post": self._http_request,
+        }
+        self._template_functions: Dict[str, TemplateFunction] = {}
+
+    def register_action(self, action: str, handler: ActionHandler) -> None:
+        """Register (or replace) the handler for an atomic action."""
+        self._handlers[action] = handler
+
+    def register_template_function(self, name: str, function: TemplateFunction) -> None:
+        """
+        Register (or replace) a template function. Skills can only call it
+        once it is also in the validator's SecurityRules.template_functions.
+        """
+        self._template_functions[name] = function
+
+    def plan(self, skill: JadeSkill, levels: Optional[List[List[str]]] = None) -> List[NodePlan]:
+        """
+        Per-node data flow of the skill, in execution order, without running
+        anything. Raises CycleError if the DAG has a cycle.
+        """
+        dag = skill.execution_dag
+        levels = levels if levels is not None else self._dag_analyzer.get_execution_levels(dag)
+        position = {node_id: i for i, node_id in enumerate(n for level in levels for n in level)}
+        conditional = set(self._dag_analyzer.get_possibly_unreachable_nodes(dag))
+        plan: List[NodePlan] = []
+        for index, level in enumerate(levels):
+            for node_id in level:
+                node = dag.get_node(node_id)
+                assert node is not None  # levels only contain declared nodes
+                incoming = dag.get_incoming_edges(node_id)
+                plan.append(NodePlan(
+                    node_id=node_id,
+                    action=node.action,
+                    level=index,
+                    depends_on=sorted({e.from_node for e in incoming}, key=lambda n: position.get(n, len(position))),
+                    references=list(dict.fromkeys(text for text, _ in template_references(node.params))),
+                    gated_by=[{"from": e.from_node, "condition": e.condition} for e in incoming if e.condition],
+                    conditional=node_id in conditional,
+                ))
+        return plan
+
+    def execute(
+        self,
+        skill: JadeSkill,
+        inputs: Optional[Dict[str, Any]] = None,
+        dry_run: bool = False,
+    ) -> ExecutionResult:
+        """
+        Execute the skill's DAG wave by wave, following only edges whose
+        condition holds.
+
+        The whole run is bounded by security.max_execution_time_ms and each
+        node by its own timeout_ms, whichever is smaller. In dry-run mode
+        nothing is executed; the result carries the order nodes would run
+        in and a plan of each node's dependencies, template references and
+        gating conditions. Raises ExecutionError on the first failure (in wave order
+        when a wave runs in parallel).
+        """
+        start = time.monotonic()
+        dag = skill.execution_dag
+        try:
+            levels = self._dag_analyzer.get_execution_levels(dag)
+        except CycleError as e:
+            raise ExecutionError("DAG_CYCLE_DETECTED", f"Execution DAG contains a cycle: {e}") from e
+
+        order = [node_id for level in levels for node_id in level]
+        result = ExecutionResult(skill_id=skill.skill_id, order=order, dry_run=dry_run)
+        if dry_run:
+            result.plan = self.plan(skill, levels)
+            return result
+
+        # Wall-clock budget for the whole DAG; each node gets the smaller of
+        # its own timeout and whatever is left of the budget
+        budget_ms = skill.security.max_execution_time_ms
+        deadline = start + budget_ms / 1000.0
+        emit(logger, logging.INFO, "execution_started", f"Executing {skill.skill_id} ({len(order)} nodes)",
+             skill_id=skill.skill_id, node_count=len(order))
+        run_inputs = dict(inputs or {})
+        capabilities = skill.security.sandbox_level.capabilities
+
+        taken: Set[Tuple[str, str]] = set()  # Edges whose condition held
+        for level in levels:
+            runnable: List[Tuple[DAGNode, ActionHandler]] = []
+            for node_id in level:
+                node = dag.get_node(node_id)
+                assert node is not None  # levels only contain declared nodes
+                incoming = dag.get_incoming_edges(node_id)
+                if incoming and not any((e.from_node, node_id) in taken for e in incoming):
+                    result.skipped.append(node_id)
+                    emit(logger, logging.DEBUG, "node_skipped", f"Node '{node_id}' skipped: no edge taken",
+                         skill_id=skill.skill_id, node_id=node_id)
+                    continue
+                if node.action in SHELL_ACTIONS and not capabilities.shell_actions:
+                    raise ExecutionError(
+                        "SANDBOX_VIOLATION",
+                        f"Shell action '{node.action}' is not allowed with "
+                        f"{skill.security.sandbox_level.value} sandbox",
+                        node_id,
+                    )
+                handler = self._handlers.get(node.action)
+                if handler is None:
+                    raise ExecutionError(
+                        "UNSUPPORTED_ACTION",
+                        f"No executor handler for action '{node.action}'",
+                        node_id,
+                    )
+                runnable.append((node, handler))
+
+            if self._parallel and len(runnable) > 1:
+                # Handlers in a wave see a snapshot of earlier waves' outputs
+                snapshot = dict(result.outputs)
+                with ThreadPoolExecutor(max_workers=self._max_workers) as pool:
+                    futures = [
+                        pool.submit(self._run_node, skill, run_inputs, node, handler,
+                                    snapshot, deadline, budget_ms, result)
+                        for node, handler in runnable
+                    ]
+                    values = [f.result() for f in futures]
+                for (node, _), value in zip(runnable, values):
+                    result.outputs[node.id] = value
+            else:
+                for node, handler in runnable:
+                    result.outputs[node.id] = self._run_node(
+                        skill, run_inputs, node, handler, result.outputs, deadline, budget_ms, result
+                    )
+
+            for node, _ in runnable:
+                taken.update(self._taken_edges(dag, node.id, result.outputs, run_inputs))
+
+        result.duration_ms = (time.monotonic() - start) * 1000
+        emit(logger, logging.INFO, "execution_finished",
+             f"Executed {skill.skill_id} in {result.duration_ms:.1f}ms ({len(result.skipped)} nodes skipped)",
+             skill_id=skill.skill_id, duration_ms=result.duration_ms, skipped_count=len(result.skipped))
+        return result
+
+    # ---- Internal helpers ----
+
+    def _budget_exceeded(self, budget_ms: int, node_id: str, result: ExecutionResult) -> ExecutionError:
+        done = ", ".join(result.outputs) or "none"
+        return ExecutionError(
+            "EXECUTION_TIMEOUT",
+            f"Skill exceeded max_execution_time_ms ({budget_ms}ms) while running node "
+            f"'{node_id}'. Completed nodes: {done}",
+            node_id,
+        )
+
+    def _run_node(
+        self,
+        skill: JadeSkill,
+        inputs: Dict[str, Any],
+        node: DAGNode,
+        handler: ActionHandler,
+        outputs: Dict[str, Any],
+        deadline: float,
+        budget_ms: int,
+        result: ExecutionResult,
+    ) -> Any:
+        """Run one node, with retries, within its own timeout and the remaining budget."""
+        try:
+            env = {name: self._env[name] for name in skill.security.env_whitelist if name in self._env}
+            node = replace(node, params=interpolate(node.params, inputs, outputs, env, self._template_functions))
+        except TemplateError as e:
+            raise ExecutionError(e.code, str(e), node.id) from e
+        retry = node.retry or RetryPolicy()
+        node_start = time.monotonic()
+        if deadline <= node_start:
+            raise self._budget_exceeded(budget_ms, node.id, result)
+        node_deadline = node_start + node_timeout_ms(node) / 1000.0
+        budget_bound = deadline < node_deadline
+        node_deadline = min(node_deadline, deadline)
+
+        attempt = 1
+        attempt_start = node_start
+        while True:
+            context = ExecutionContext(
+                skill=skill,
+                inputs=dict(inputs),
+                outputs=outputs,
+                timeout_s=max(node_deadline - attempt_start, 0.0),
+            )
+            error: Optional[ExecutionError] = None
+            value: Any = None
+            try:
+                value = self._run_with_timeout(handler, node, context)
+            except ExecutionError as e:
+                if e.code == "NODE_TIMEOUT" and budget_bound:
+                    raise self._budget_exceeded(budget_ms, node.id, result) from e
+                if e.code not in RETRYABLE_CODES:
+                    raise
+                error = e
+            result.attempts[node.id] = attempt
+
+            failed = error is not None or _retryable_status(value, retry)
+            delay_s = retry.backoff_ms * 2 ** (attempt - 1) / 1000.0
+            # Give up when attempts run out or the backoff would outlast the node's time
+            if not failed or attempt >= retry.max_attempts or time.monotonic() + delay_s >= node_deadline:
+                if error is not None:
+                    raise error
+                duration_ms = (time.monotonic() - node_start) * 1000
+                emit(logger, logging.DEBUG, "node_executed",
+                     f"Node '{node.id}' ({node.action}) ran in {duration_ms:.1f}ms, attempt {attempt}",
+                     skill_id=skill.skill_id, node_id=node.id, action=node.action,
+                     attempts=attempt, duration_ms=duration_ms)
+                return value
+            if delay_s > 0:
+                self._sleep(delay_s)
+            attempt += 1
+            attempt_start = time.monotonic()
+
+    def _taken_edges(
+        self, dag: ExecutionDAG, node_id: str, outputs: Dict[str, Any], inputs: Dict[str, Any],
+    ) -> List[Tuple[str, str]]:
+        """Outgoing edges of a node that just ran whose condition holds."""
+        taken = []
+        for edge in dag.get_outgoing_edges(node_id):
+            try:
+                holds = evaluate_condition(edge.condition, outputs, inputs=inputs)
+            except ConditionSyntaxError as e:
+                raise ExecutionError(
+                    "INVALID_CONDITION",
+                    f"Edge {node_id} -> {edge.to_node} has invalid condition: {e}",
+                    node_id,
+                ) from e
+            if holds:
+                taken.append((node_id, edge.to_node))
+        return taken
+
+    def _run_with_timeout(self, handler: ActionHandler, node: DAGNode, context: ExecutionContext) -> Any:
+        """Run a handler on a worker thread, abandoning it once the node times out."""
+        outcome: Dict[str, Any] = {}
+
+        def target() -> None:
+            try:
+                outcome["value"] = handler(node, context)
+            except BaseException as e:  # Re-raised on the calling thread
+                outcome["error"] = e
+
+        worker = threading.Thread(target=target, name=f"jade-node-{node.id}", daemon=True)
+        worker.start()
+        worker.join(context.timeout_s)
+        if worker.is_alive():
+            raise ExecutionError(
+                "NODE_TIMEOUT",
+                f"Node exceeded its {context.timeout_s * 1000:.0f}ms timeout",
+                node.id,
+            )
+        if "error" in outcome:
+            error = outcome["error"]
+            if isinstance(error, ExecutionError):
+                raise error
+            raise ExecutionError("NODE_FAILED", f"{type(error).__name__}: {error}", node.id) from error
+        return outcome.get("value")
+
+    def _http_request(self, node: DAGNode, context: ExecutionContext) -> Dict[str, Any]:
+        """Built-in handler for http_request, http_get and http_post."""
+        params = node.params
+        default_method = "POST" if node.action == "http_post" else "GET"
+        method = str(params.get("method", default_method)).upper()
+        if method not in HTTP_METHODS:
+            raise ExecutionError("INVALID_PARAMS", f"Unsupported HTTP method '{method}'", node.id)
+
+        url = params.get("url")
+        if not isinstance(url, str) or not url:
+            raise ExecutionError("INVALID_PARAMS", "Parameter 'url' must be a non-empty string", node.id)
+        self._check_url(url, node, context)
+
+        headers = {str(k): str(v) for k, v in (params.get("headers") or {}).items()}
+        body = params.get("body")
+        payload: Optional[bytes] = None
+        if isinstance(body, (dict, list)):
+            payload = json.dumps(body).encode("utf-8")
+            headers.setdefault("Content-Type", "application/json")
+        elif body is not None:
+            payload = str(body).encode("utf-8")
+
+        origin = urlparse(url).hostname
+        for _ in range(MAX_REDIRECTS + 1):
+            try:
+                response = self._transport(method, url, headers, payload, context.timeout_s)
+            except (URLError, OSError) as e:
+                raise ExecutionError("HTTP_ERROR", f"{method} {url} failed: {e}", node.id) from e
+            location = _header(response, "Location")
+            if response.get("status_code") not in REDIRECT_STATUSES or not location:
+                return response
+            url = urljoin(url, location)
+            self._check_url(url, node, context)
+            if response["status_code"] == 303 or (response["status_code"] in (301, 302) and method == "POST"):
+                method, payload = "GET", None
+                headers = {k: v for k, v in headers.items() if k.lower() != "content-type"}
+            if urlparse(url).hostname != origin:
+                headers = {k: v for k, v in headers.items() if k.lower() not in _CREDENTIAL_HEADERS}
+        raise ExecutionError("HTTP_ERROR", f"{method} {url} failed: more than {MAX_REDIRECTS} redirects", node.id)
+
+    def _check_url(self, url: str, node: DAGNode, context: ExecutionContext) -> None:
+        """Refuse a request (or redirect) target the skill's security policy does not allow."""
+        parsed = urlparse(url)
+        host = parsed.hostname or ""
+        if parsed.scheme not in ("http", "https") or not host:
+            raise ExecutionError("INVALID_PARAMS", f"Not an absolute http(s) URL: '{url}'", node.id)
+
+        security = context.skill.security
+        capabilities = security.sandbox_level.capabilities
+        if parsed.scheme == "http" and not capabilities.plain_http:
+            raise ExecutionError(
+                "INSECURE_SCHEME",
+                f"Refusing plain http:// request to '{host}' with {security.sandbox_level.value} sandbox",
+                node.id,
+            )
+        whitelist = security.network_whitelist
+        if not capabilities.wildcard_network:
+            whitelist = [entry for entry in whitelist if entry != "*"]
+        if not domain_matches_whitelist(url, whitelist):
+            ignored = len(whitelist) < len(security.network_whitelist)
+            wildcard = " ('*' is ignored in this sandbox)" if ignored else ""
+            raise ExecutionError(
+                "DOMAIN_NOT_WHITELISTED",
+                f"Refusing request to '{host}': not in network_whitelist{wildcard}",
+                node.id,
+            )
+
+
+def _header(response: Dict[str, Any], name: str) -> Optional[str]:
+    """A response header, matched case-insensitively."""
+    for key, value in (response.get("headers") or {}).items():
+        if str(key).lower() == name.lower():
+            return str(value)
+    return None
+
+
+def _retryable_status(value: Any, retry: RetryPolicy) -> bool:
+    """Whether a node's output is an HTTP response the retry policy wants re-sent."""
+    return isinstance(value, dict) and value.get("status_code") in retry.retry_on_status
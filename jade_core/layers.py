@@ -0,0 +1,57 @@
+"""
+Project JADE - Custom Validation Layers
+Organization-specific checks run after the five built-in layers.
+
+    class CmdbDomains(CustomLayer):
+        name = "cmdb-domains"
+
+        def validate(self, skill, raw):
+            return [
+                ValidationIssue(ValidationSeverity.ERROR, "ORG_UNKNOWN_DOMAIN",
+                                f"{d} is not in the CMDB", "security.network_whitelist")
+                for d in skill.security.network_whitelist if d not in CMDB
+            ]
+
+    validator = JadeValidator(custom_layers=[CmdbDomains()])
+
+Custom layers are numbered from FIRST_CUSTOM_LAYER (6) in the order they
+are given, and take part in layer selection, layers_passed/layers_skipped,
+timings and suppressions like the built-in ones. Their issues are tagged
+with that number, so `layer` is the only field a layer need not set.
+
+A validator with a cache only caches while every custom layer returns a
+cache_token(): the layer's verdicts depend on its configuration, and the
+token is how that configuration enters the cache key.
+"""
+
+from __future__ import annotations
+
+from abc import ABC, abstractmethod
+from typing import Any, Dict, List, Optional
+
+from .models import JadeSkill, ValidationIssue
+
+# Number of the first custom layer; the built-in ValidationLayer values are 1-5
+FIRST_CUSTOM_LAYER = 6
+
+
+class CustomLayer(ABC):
+    """A validation layer supplied by the caller rather than built in."""
+
+    # Short identifier shown in reports, e.g. 'cmdb-domains'
+    name: str = ""
+
+    @abstractmethod
+    def validate(self, skill: JadeSkill, raw: Dict[str, Any]) -> List[ValidationIssue]:
+        """
+        Check one skill. `skill` is the parsed model; `raw` is the document
+        as given, for fields the model does not keep. Neither may be modified.
+        """
+
+    def cache_token(self) -> Optional[str]:
+        """
+        A string naming everything that decides this layer's verdicts, e.g.
+        its configuration, or None (the default) if its results must not be
+        cached. Two layers with the same token must accept the same skills.
+        """
+        return None
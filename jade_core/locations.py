@@ -0,0 +1,126 @@
+"""
+Project JADE - Source Locations
+Map issue paths back to line and column positions in the skill's JSON text.
+
+Issue paths ('execution_dag.edges[3].to', 'security') name a place in the
+parsed document. value_offsets() scans the original text once and records
+where each path starts: the key of an object member, or the first character
+of an array element. When a key appears twice the last one wins, as it does
+when the JSON is parsed.
+
+Lines and columns are 1-based; columns count characters, not bytes.
+"""
+
+from __future__ import annotations
+
+import bisect
+import json
+import re
+from typing import Dict, List, Optional, Sequence, Tuple
+
+Location = Tuple[int, int]
+
+_TOKEN_RE = re.compile(r'"(?:[^"\\]|\\.)*"|[{}\[\],:]|[^\s{}\[\],:"]+')
+
+
+def value_offsets(text: str) -> Dict[str, int]:
+    """Character offset of every path in a well-formed JSON document."""
+    offsets: Dict[str, int] = {}
+    # Frames: [is_object, path, array index, current key, expecting a key]
+    stack: List[list] = []
+    for match in _TOKEN_RE.finditer(text):
+        token, start = match.group(), match.start()
+        top = stack[-1] if stack else None
+        if top is not None and top[0] and top[4] and token.startswith('"'):
+            top[3] = json.loads(token)
+            top[4] = False
+            offsets[_join(top[1], top[3])] = start
+            continue
+        if token in ":":
+            continue
+        if token == ",":
+            if top is not None:
+                if top[0]:
+                    top[4] = True
+                else:
+                    top[2] += 1
+            continue
+        if token in "}]":
+            stack.pop()
+            continue
+
+        # A value: record array elements (object members were recorded at the key)
+        if top is None:
+            path = ""
+        elif top[0]:
+            path = _join(top[1], top[3])
+        else:
+            path = f"{top[1]}[{top[2]}]"
+            offsets[path] = start
+        if token in "{[":
+            stack.append([token == "{", path, 0, None, token == "{"])
+    return offsets
+
+
+def _join(parent: str, key: str) -> str:
+    return f"{parent}.{key}" if parent else key
+
+
+def line_column(text: str, offset: int, line_starts: Optional[Sequence[int]] = None) -> Location:
+    """1-based (line, column) of a character offset."""
+    starts = line_starts if line_starts is not None else _line_starts(text)
+    line = bisect.bisect_right(starts, offset)
+    return line, offset - starts[line - 1] + 1
+
+
+def _line_starts(text: str) -> List[int]:
+    return [0] + [m.end() for m in re.finditer("\n", text)]
+
+
+class SourceMap:
+    """Resolves issue paths against one JSON text, scanning it on first use."""
+
+    def __init__(self, text: str, node_ids: Sequence[Optional[str]] = ()):
+        """`node_ids` are the declared node IDs in order, None where a node has none."""
+        self._text = text
+        self._node_ids = list(node_ids)
+        self._offsets: Optional[Dict[str, int]] = None
+        self._line_starts: List[int] = []
+
+    def locate(self, path: str) -> Optional[Location]:
+        """
+        Location of `path`, or of its nearest enclosing path that exists
+        (a missing 'metadata.name' points at 'metadata'). None for ''.
+        """
+        if self._offsets is None:
+            self._offsets = value_offsets(self._text)
+            self._line_starts = _line_starts(self._text)
+        path = self._index_node_path(path)
+        while path:
+            if path in self._offsets:
+                return line_column(self._text, self._offsets[path], self._line_starts)
+            path = _parent(path)
+        return None
+
+    def _index_node_path(self, path: str) -> str:
+        """'execution_dag.nodes.fetch.x' -> 'execution_dag.nodes[i].x'."""
+        prefix = "execution_dag.nodes."
+        if not path.startswith(prefix):
+            return path
+        rest = path[len(prefix):]
+        best: Optional[Tuple[int, str]] = None
+        for i, node_id in enumerate(self._node_ids):
+            if not isinstance(node_id, str) or not node_id:
+                continue
+            if rest == node_id or rest.startswith(node_id + ".") or rest.startswith(node_id + "["):
+                # IDs may contain dots; the longest matching ID is the node
+                if best is None or len(node_id) > len(best[1]):
+                    best = (i, node_id)
+        if best is None:
+            return path
+        return f"execution_dag.nodes[{best[0]}]{rest[len(best[1]):]}"
+
+
+def _parent(path: str) -> str:
+    cut = max(path.rfind("."), path.rfind("["))
+    return path[:cut] if cut > 0 else ""
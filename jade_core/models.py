@@ -10,9 +10,9 @@ from __future__ import annotations
 import hashlib
 import json
 import time
-from dataclasses import dataclass, field
-from enum import Enum
-from typing import Any, Dict, List, Optional
+from dataclasses import dataclass, field, fields, is_dataclass, replace
+from enum import Enum, IntEnum
+from typing import Any, Dict, Iterable, List, Optional, Tuple
 
 
 class TriggerType(str, Enum):
@@ -35,10 +35,44 @@ class ConditionOperator(str, Enum):
 
 
 class SandboxLevel(str, Enum):
+    """
+    How much a skill may do at runtime. See SandboxCapabilities:
+
+                            strict   standard / permissive
+        '*' whitelist       no       yes
+        plain http://       no       yes
+        shell actions       no       yes
+
+    Strict also caps max_execution_time_ms at 120s and max_retries at 10.
+    Standard and permissive currently grant the same capabilities.
+    """
     STRICT = "strict"
     STANDARD = "standard"
     PERMISSIVE = "permissive"
 
+    @property
+    def capabilities(self) -> SandboxCapabilities:
+        return _SANDBOX_CAPABILITIES[self]
+
+
+# Actions that run arbitrary commands on the host
+SHELL_ACTIONS = frozenset({"shell_exec"})
+
+
+@dataclass(frozen=True)
+class SandboxCapabilities:
+    """What a sandbox level lets a skill do at runtime."""
+    wildcard_network: bool  # A '*' network_whitelist entry allows any host
+    plain_http: bool  # Requests may use http:// rather than https://
+    shell_actions: bool  # Actions in SHELL_ACTIONS may run
+
+
+_SANDBOX_CAPABILITIES = {
+    SandboxLevel.STRICT: SandboxCapabilities(wildcard_network=False, plain_http=False, shell_actions=False),
+    SandboxLevel.STANDARD: SandboxCapabilities(wildcard_network=True, plain_http=True, shell_actions=True),
+    SandboxLevel.PERMISSIVE: SandboxCapabilities(wildcard_network=True, plain_http=True, shell_actions=True),
+}
+
 
 class RiskLevel(str, Enum):
     NONE = "none"
@@ -53,36 +87,414 @@ class AttestationType(str, Enum):
     FAILURE = "failure"
 
 
+class TrustLevel(IntEnum):
+    """Ordered trust levels for community co-signers."""
+    UNTRUSTED = 0
+    COMMUNITY = 1
+    VERIFIED = 2
+    OFFICIAL = 3
+
+    @classmethod
+    def parse(cls, value: str) -> TrustLevel:
+        try:
+            return cls[value.strip().upper()]
+        except KeyError:
+            raise ValueError(f"Unknown trust level '{value}'") from None
+
+
+class SignerRole(IntEnum):
+    """
+    Ordered roles of root signers.
+
+    A validator takes a signer's role from its KeyRegistry entry; the
+    'jade-pk-<role>-' key prefix is chosen by the signer and only has to
+    agree with it. Without a registry every signer is UNKNOWN, the lowest
+    role.
+    """
+    UNKNOWN = 0
+    CI = 1
+    ROOT = 2
+
+    @classmethod
+    def parse(cls, value: str) -> SignerRole:
+        try:
+            return cls[value.strip().upper()]
+        except KeyError:
+            raise ValueError(f"Unknown signer role '{value}'") from None
+
+
 class ValidationSeverity(str, Enum):
+    """
+    How serious an issue is, ordered INFO < WARNING < ERROR.
+
+    Comparisons use that order rather than the string values, so
+    `issue.severity >= ValidationSeverity.WARNING` and sorting by severity
+    work as expected. `.value` is the lowercase name used in reports.
+    """
     ERROR = "error"
     WARNING = "warning"
     INFO = "info"
 
+    @property
+    def rank(self) -> int:
+        return _SEVERITY_RANK[self]
+
+    # All four are defined: str's own would order by value, and would also
+    # accept plain strings, which raise TypeError here instead
+    def __lt__(self, other: Any) -> bool:  # type: ignore[override]
+        return self.rank < _severity_rank(other)
+
+    def __le__(self, other: Any) -> bool:  # type: ignore[override]
+        return self.rank <= _severity_rank(other)
+
+    def __gt__(self, other: Any) -> bool:  # type: ignore[override]
+        return self.rank > _severity_rank(other)
+
+    def __ge__(self, other: Any) -> bool:  # type: ignore[override]
+        return self.rank >= _severity_rank(other)
+
+    @classmethod
+    def parse(cls, value: str) -> ValidationSeverity:
+        try:
+            return cls(value.strip().lower())
+        except ValueError:
+            raise ValueError(f"Unknown severity '{value}'") from None
+
+
+_SEVERITY_RANK = {ValidationSeverity.INFO: 0, ValidationSeverity.WARNING: 1, ValidationSeverity.ERROR: 2}
+
+
+def _severity_rank(other: Any) -> int:
+    if not isinstance(other, ValidationSeverity):
+        raise TypeError(f"Cannot compare ValidationSeverity with {type(other).__name__}")
+    return other.rank
+
+
+class ValidationLayer(IntEnum):
+    """
+    The built-in validation layers, in the order JadeValidator runs them.
+    Custom layers (layers.CustomLayer) follow, numbered from 6.
+    """
+    SCHEMA = 1  # Structure, metadata, versions, input/output schemas
+    DAG = 2  # Execution graph integrity
+    SECURITY = 3  # Injection, network, file and sandbox checks
+    SEMANTIC = 4  # Cross-field consistency
+    SIGNATURE = 5  # Root and community signatures, trust policy
+
+
+class IssueCode(str, Enum):
+    """
+    Stable codes for every built-in validation finding.
+
+    The string values are part of the public API: they appear in JSON
+    output and can be passed to `jadegate verify --ignore`. A released code
+    is never renamed or reused; a new kind of finding gets a new code.
+    Custom InjectionRules may use codes outside this set.
+    """
+    # Input (reported under Layer 1)
+    FILE_NOT_FOUND = "FILE_NOT_FOUND"
+    INVALID_JSON = "INVALID_JSON"
+    DUPLICATE_KEY = "DUPLICATE_KEY"
+    RESOURCE_LIMIT = "RESOURCE_LIMIT"
+    MISSING_FIELD = "MISSING_FIELD"
+    PARSE_ERROR = "PARSE_ERROR"
+    SERIALIZE_ERROR = "SERIALIZE_ERROR"
+    FETCH_FAILED = "FETCH_FAILED"
+    SKILL_NOT_FOUND = "SKILL_NOT_FOUND"
+
+    # Layer 1: schema
+    UNSUPPORTED_VERSION = "UNSUPPORTED_VERSION"
+    JADE_VERSION_TOO_NEW = "JADE_VERSION_TOO_NEW"
+    INVALID_SKILL_ID = "INVALID_SKILL_ID"
+    INVALID_IDENTIFIER = "INVALID_IDENTIFIER"
+    SKILL_ID_FILENAME_MISMATCH = "SKILL_ID_FILENAME_MISMATCH"
+    INVALID_METADATA_NAME = "INVALID_METADATA_NAME"
+    INVALID_METADATA_VERSION = "INVALID_METADATA_VERSION"
+    INVALID_METADATA_DESC = "INVALID_METADATA_DESC"
+    INVALID_METADATA_TAGS = "INVALID_METADATA_TAGS"
+    DUPLICATE_TAGS = "DUPLICATE_TAGS"
+    EMPTY_TAG = "EMPTY_TAG"
+    TAG_TOO_LONG = "TAG_TOO_LONG"
+    TAG_WHITESPACE = "TAG_WHITESPACE"
+    NO_TRIGGER_CONDITIONS = "NO_TRIGGER_CONDITIONS"
+    INVALID_IO_SCHEMA = "INVALID_IO_SCHEMA"
+    UNKNOWN_ACTION = "UNKNOWN_ACTION"
+    INVALID_NODE_PARAMS = "INVALID_NODE_PARAMS"
+    PARAMS_WITHOUT_ACTION = "PARAMS_WITHOUT_ACTION"
+    OUTPUT_SCHEMA_MISMATCH = "OUTPUT_SCHEMA_MISMATCH"
+    INVALID_RETRY = "INVALID_RETRY"
+    RESERVED_NAME = "RESERVED_NAME"
+    UNKNOWN_FIELD = "UNKNOWN_FIELD"
+    SANDBOX_MISSING = "SANDBOX_MISSING"
+    INVALID_SANDBOX_LEVEL = "INVALID_SANDBOX_LEVEL"
+
+    # Layer 2: DAG
+    DAG_SELF_LOOP = "DAG_SELF_LOOP"
+    DAG_DUPLICATE_NODE = "DAG_DUPLICATE_NODE"
+    DAG_DUPLICATE_EDGE = "DAG_DUPLICATE_EDGE"
+    DAG_INVALID_ENTRY = "DAG_INVALID_ENTRY"
+    DAG_INVALID_EXIT = "DAG_INVALID_EXIT"
+    DAG_NO_EXIT = "DAG_NO_EXIT"
+    DAG_INVALID_EDGE_SRC = "DAG_INVALID_EDGE_SRC"
+    DAG_INVALID_EDGE_DST = "DAG_INVALID_EDGE_DST"
+    DAG_CYCLE_DETECTED = "DAG_CYCLE_DETECTED"
+    DAG_NO_ROOT = "DAG_NO_ROOT"
+    DAG_MULTIPLE_ROOTS = "DAG_MULTIPLE_ROOTS"
+    DAG_UNREACHABLE_FROM_ROOT = "DAG_UNREACHABLE_FROM_ROOT"
+    DAG_UNREACHABLE_NODE = "DAG_UNREACHABLE_NODE"
+    DAG_NO_REACHABLE_EXIT = "DAG_NO_REACHABLE_EXIT"
+    DAG_DEAD_END = "DAG_DEAD_END"
+    DAG_NO_INCOMING = "DAG_NO_INCOMING"
+    DAG_INVALID_CONDITION = "DAG_INVALID_CONDITION"
+    DAG_INCOMPLETE_BRANCH = "DAG_INCOMPLETE_BRANCH"
+    DAG_CONTRADICTORY_CONDITIONS = "DAG_CONTRADICTORY_CONDITIONS"
+    DAG_DUPLICATE_CONDITION = "DAG_DUPLICATE_CONDITION"
+    DAG_EMPTY_ACTION = "DAG_EMPTY_ACTION"
+    DAG_TEMPLATE_NOT_ANCESTOR = "DAG_TEMPLATE_NOT_ANCESTOR"
+    DAG_UNDECLARED_INPUT = "DAG_UNDECLARED_INPUT"
+
+    # Layer 3: security
+    SEC_EXEC_CODE = "SEC_EXEC_CODE"
+    SEC_DANGEROUS_CMD = "SEC_DANGEROUS_CMD"
+    SEC_CODE_LIKE_TEXT = "SEC_CODE_LIKE_TEXT"
+    SEC_ENCODED_INJECTION = "SEC_ENCODED_INJECTION"
+    SEC_WILDCARD_NETWORK = "SEC_WILDCARD_NETWORK"
+    SEC_SUSPICIOUS_NETWORK = "SEC_SUSPICIOUS_NETWORK"
+    SEC_MALFORMED_WHITELIST = "SEC_MALFORMED_WHITELIST"
+    SEC_CLEARTEXT_WHITELIST = "SEC_CLEARTEXT_WHITELIST"
+    SEC_NOT_HSTS_PRELOADED = "SEC_NOT_HSTS_PRELOADED"
+    SEC_UNLISTED_DOMAIN = "SEC_UNLISTED_DOMAIN"
+    SEC_INSECURE_SCHEME = "SEC_INSECURE_SCHEME"
+    SEC_ROOT_FILE_ACCESS = "SEC_ROOT_FILE_ACCESS"
+    SEC_SENSITIVE_PATH = "SEC_SENSITIVE_PATH"
+    SEC_UNKNOWN_ACTION = "SEC_UNKNOWN_ACTION"
+    SEC_SANDBOX_ACTION = "SEC_SANDBOX_ACTION"
+    SEC_TIMEOUT_EXCEEDED = "SEC_TIMEOUT_EXCEEDED"
+    SEC_TIME_BUDGET_EXCEEDED = "SEC_TIME_BUDGET_EXCEEDED"
+    SEC_RETRIES_EXCEEDED = "SEC_RETRIES_EXCEEDED"
+    SEC_DATA_EXFIL_RISK = "SEC_DATA_EXFIL_RISK"
+    SEC_ENV_EXFIL_RISK = "SEC_ENV_EXFIL_RISK"
+    SEC_ENV_UNUSED = "SEC_ENV_UNUSED"
+    SEC_ENV_NOT_WHITELISTED = "SEC_ENV_NOT_WHITELISTED"
+    SEC_POLICY_RELAXED = "SEC_POLICY_RELAXED"
+    TEMPLATE_FORBIDDEN_FN = "TEMPLATE_FORBIDDEN_FN"
+
+    # Layer 4: semantic
+    NETWORK_ACTION_NO_WHITELIST = "NETWORK_ACTION_NO_WHITELIST"
+    FILE_ACTION_NO_PERMS = "FILE_ACTION_NO_PERMS"
+    RETRY_MISMATCH = "RETRY_MISMATCH"
+
+    # Layer 5: signatures
+    SIGNATURE_REQUIRED = "SIGNATURE_REQUIRED"
+    SIG_INVALID = "SIG_INVALID"
+    HASH_MISMATCH = "HASH_MISMATCH"
+    HASH_FORMAT_INVALID = "HASH_FORMAT_INVALID"
+    UNSUPPORTED_ALGORITHM = "UNSUPPORTED_ALGORITHM"
+    UNKNOWN_SIGNER = "UNKNOWN_SIGNER"
+    SIG_EXPIRED = "SIG_EXPIRED"
+    SIG_FUTURE_DATED = "SIG_FUTURE_DATED"
+    SIG_INVALID_TIMESTAMP = "SIG_INVALID_TIMESTAMP"
+    COMMUNITY_SIG_INVALID = "COMMUNITY_SIG_INVALID"
+    UNKNOWN_TRUST_LEVEL = "UNKNOWN_TRUST_LEVEL"
+    TRUST_POLICY_UNMET = "TRUST_POLICY_UNMET"
+    SIGNER_ROLE_TOO_LOW = "SIGNER_ROLE_TOO_LOW"
+    SIGNER_ROLE_MISMATCH = "SIGNER_ROLE_MISMATCH"
+
 
 @dataclass
 class ValidationIssue:
     """A single validation issue found during skill verification."""
     severity: ValidationSeverity
-    code: str
+    code: str  # An IssueCode value, or a custom rule's code
     message: str
     path: str = ""  # JSON path to the problematic field
+    layer: Optional[int] = None  # ValidationLayer that raised the issue
+    node_id: Optional[str] = None  # DAG node the path points into, if any
+    suppressed: bool = False  # Matched a Suppression; severity was lowered to INFO
+    location: Optional[Tuple[int, int]] = None  # 1-based (line, column), when validated from JSON text
+
+    def __post_init__(self) -> None:
+        # Store IssueCode members as plain strings so output never shows 'IssueCode.X'
+        if isinstance(self.code, IssueCode):
+            self.code = self.code.value
 
-    def to_dict(self) -> Dict[str, str]:
+    def to_dict(self) -> Dict[str, Any]:
         return {
+            "layer": self.layer,
             "severity": self.severity.value,
             "code": self.code,
             "message": self.message,
             "path": self.path,
+            "node_id": self.node_id,
+            "suppressed": self.suppressed,
+            "location": list(self.location) if self.location else None,
         }
 
 
+@dataclass
+class Suppression:
+    """
+    Silences one issue code, optionally only for a single DAG node.
+
+    Matching issues are kept in the result as suppressed INFO issues rather
+    than dropped, so reports still show what was accepted.
+    """
+    code: str
+    node_id: Optional[str] = None  # None matches the code anywhere in the skill
+
+    def __post_init__(self) -> None:
+        if isinstance(self.code, IssueCode):
+            self.code = self.code.value
+
+    def matches(self, issue: ValidationIssue) -> bool:
+        if issue.code != self.code:
+            return False
+        return self.node_id is None or issue.node_id == self.node_id
+
+    @classmethod
+    def parse(cls, text: str) -> Suppression:
+        """Parse 'CODE' or 'CODE@node_id'."""
+        code, _, node_id = text.strip().partition("@")
+        return cls(code=code, node_id=node_id or None)
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> Suppression:
+        return cls(code=data["code"], node_id=data.get("node_id"))
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {"code": self.code, "node_id": self.node_id}
+
+
+@dataclass
+class CommunitySigResult:
+    """Verification outcome for a single community co-signature."""
+    signer: str
+    fingerprint: str
+    trust_level: str
+    valid: bool
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "signer": self.signer,
+            "fingerprint": self.fingerprint,
+            "trust_level": self.trust_level,
+            "valid": self.valid,
+        }
+
+
+@dataclass
+class SignatureRecord:
+    """
+    Audit record of one signature Layer 5 checked: which key and algorithm
+    it used and whether it verified, kept whether or not it did.
+    """
+    kind: str  # 'root' or 'community'
+    signer: str
+    algorithm: str  # As declared in the skill
+    fingerprint: str  # key_fingerprint() of the public key; '' if it cannot be decoded
+    verified: bool  # The signature verifies over the skill's signable content
+    signed_at: str = ""
+    path: str = ""  # 'jade_signature' or 'community_signatures[i]'
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "kind": self.kind,
+            "signer": self.signer,
+            "algorithm": self.algorithm,
+            "fingerprint": self.fingerprint,
+            "verified": self.verified,
+            "signed_at": self.signed_at,
+            "path": self.path,
+        }
+
+
+@dataclass
+class TrustPolicy:
+    """
+    Signature requirements a skill must meet to be accepted.
+
+    A min_signer_role above UNKNOWN implies require_root_signature: the
+    root signature must verify and its key must be registered with at
+    least that role, so it needs a validator with a KeyRegistry.
+
+    require_signature_when names capability risk factors (see
+    security.CAPABILITY_RISK_WEIGHTS), e.g. ['wildcard_egress',
+    'sensitive_env', 'relaxed_sandbox']: a skill granted any of them needs
+    a valid root signature, while one granted none may stay unsigned.
+    """
+    min_community_signers: int = 0
+    min_trust_level: TrustLevel = TrustLevel.COMMUNITY
+    require_root_signature: bool = False
+    min_signer_role: SignerRole = SignerRole.UNKNOWN
+    require_signature_when: List[str] = field(default_factory=list)
+
+
+@dataclass
+class SkillIdPolicy:
+    """
+    Accepted shapes of skill_id.
+
+    The default accepts flat ids such as 'weather_api_query'; namespaced()
+    requires 'namespace/name', as registries shared between organisations
+    do. With match_filename, validate_file also requires the id to match
+    the file: 'weather_api' in weather_api.json, 'acme/weather' in
+    acme/weather.json.
+    """
+    pattern: str = r'^[a-z][a-z0-9_]{2,63}$'
+    match_filename: bool = False
+
+    @classmethod
+    def namespaced(cls, match_filename: bool = False) -> SkillIdPolicy:
+        return cls(pattern=r'^[a-z][a-z0-9_-]{1,63}/[a-z][a-z0-9_]{2,63}$', match_filename=match_filename)
+
+
+@dataclass
+class ResourceLimits:
+    """
+    Size limits checked before a skill is validated; None disables a limit.
+
+    The defaults are far above any real skill and stop hostile documents
+    (deep nesting, huge DAGs) from exhausting the stack or memory of the
+    validation layers. Expressions inside strings have fixed limits of
+    their own: edge conditions nest at most conditions.MAX_CONDITION_DEPTH
+    levels (deeper is DAG_INVALID_CONDITION), template literals at most
+    templates.MAX_LITERAL_DEPTH.
+    """
+    max_bytes: Optional[int] = 1024 * 1024  # Encoded JSON text, for validate_file/_bytes/_str
+    max_depth: Optional[int] = 64  # Nesting of objects and arrays
+    max_nodes: Optional[int] = 500
+    max_edges: Optional[int] = 2000
+
+
+@dataclass
+class FreshnessPolicy:
+    """Age limits for signature signed_at timestamps (RFC 3339)."""
+    max_age_s: Optional[float] = None  # None: signatures never expire
+    clock_skew_s: float = 300.0  # How far in the future signed_at may be
+
+
 @dataclass
 class ValidationResult:
-    """Result of validating a JADE skill."""
+    """
+    Result of validating a JADE skill.
+
+    The order of `issues` is stable: by layer, then by check within the
+    layer, then by position in the document (nodes and edges in
+    declaration order). The same input always yields the same list, so
+    reports can be snapshot-tested and diffed.
+    """
     valid: bool
     issues: List[ValidationIssue] = field(default_factory=list)
     skill_hash: str = ""
     checked_at: float = field(default_factory=time.time)
+    content_hash: Optional[str] = None  # Hash of the signable content
+    community_results: List[CommunitySigResult] = field(default_factory=list)
+    layers_passed: List[int] = field(default_factory=list)
+    layers_skipped: List[int] = field(default_factory=list)  # Not run, as opposed to passed
+    timings_ms: Dict[int, float] = field(default_factory=dict)  # Wall time per layer that ran
+    signer_role: Optional[SignerRole] = None  # Role of a root signature that verified
+    signatures: List[SignatureRecord] = field(default_factory=list)  # Root first, then co-signatures
+    hidden: Dict[str, int] = field(default_factory=dict)  # Severity -> issues dropped by at_least
 
     @property
     def errors(self) -> List[ValidationIssue]:
@@ -92,14 +504,65 @@ class ValidationResult:
     def warnings(self) -> List[ValidationIssue]:
         return [i for i in self.issues if i.severity == ValidationSeverity.WARNING]
 
+    @property
+    def valid_community_signers(self) -> int:
+        return sum(1 for r in self.community_results if r.valid)
+
+    def ignoring(self, codes: Iterable[str]) -> ValidationResult:
+        """
+        Copy of this result without issues whose code is in `codes`.
+
+        `valid` and `layers_passed` are recomputed, so a layer whose only
+        errors were ignored counts as passed. A run that stopped early stays
+        invalid, since the layers after the stop never ran.
+        """
+        ignored = {c.value if isinstance(c, IssueCode) else c for c in codes}
+        kept = [i for i in self.issues if i.code not in ignored]
+        failing = {i.layer for i in kept if i.severity == ValidationSeverity.ERROR}
+        ran = set(self.layers_passed) | {i.layer for i in self.errors if i.layer is not None}
+        complete = ran | set(self.layers_skipped) >= set(ValidationLayer)
+        return replace(
+            self,
+            valid=not failing and (self.valid or complete),
+            issues=kept,
+            layers_passed=sorted(ran - failing),
+        )
+
+    def at_least(self, severity: ValidationSeverity) -> ValidationResult:
+        """
+        Copy of this result without issues below `severity`.
+
+        Errors are never dropped, so `valid` and the layers are unchanged.
+        The dropped issues are counted by severity in `hidden`, so a report
+        can say how much it leaves out.
+        """
+        hidden = dict(self.hidden)
+        kept = []
+        for issue in self.issues:
+            if issue.severity >= severity:
+                kept.append(issue)
+            else:
+                hidden[issue.severity.value] = hidden.get(issue.severity.value, 0) + 1
+        ordered = {s.value: hidden[s.value] for s in ValidationSeverity if s.value in hidden}
+        return replace(self, issues=kept, hidden=ordered)
+
     def to_dict(self) -> Dict[str, Any]:
         return {
             "valid": self.valid,
+            "layers_passed": list(self.layers_passed),
+            "layers_skipped": list(self.layers_skipped),
             "issues": [i.to_dict() for i in self.issues],
             "skill_hash": self.skill_hash,
+            "content_hash": self.content_hash,
+            "community_results": [r.to_dict() for r in self.community_results],
+            "valid_community_signers": self.valid_community_signers,
+            "signer_role": self.signer_role.name.lower() if self.signer_role is not None else None,
+            "signatures": [s.to_dict() for s in self.signatures],
             "error_count": len(self.errors),
             "warning_count": len(self.warnings),
+            "hidden_counts": dict(self.hidden),
             "checked_at": self.checked_at,
+            "timings_ms": {str(layer): round(ms, 3) for layer, ms in sorted(self.timings_ms.items())},
         }
 
 
@@ -133,16 +596,73 @@ class Trigger:
         )
 
 
+def _frozen(value: Any) -> Any:
+    """
+    A hashable stand-in for a JSON-like value or model dataclass: two values
+    that compare equal always freeze to equal (so equally hashed) results.
+    """
+    if isinstance(value, dict):
+        return frozenset((k, _frozen(v)) for k, v in value.items())
+    if isinstance(value, (list, tuple)):
+        return tuple(_frozen(v) for v in value)
+    if is_dataclass(value):
+        return (type(value).__name__,) + tuple(_frozen(getattr(value, f.name)) for f in fields(value) if f.compare)
+    return value
+
+
+@dataclass
+class RetryPolicy:
+    """
+    How often a failing node is re-run.
+
+    max_attempts counts the first run. The delay before attempt n+1 is
+    backoff_ms * 2**(n-1). HTTP responses whose status is listed in
+    retry_on_status are retried like failures. Values are kept as given
+    so the validator can report malformed ones.
+    """
+    max_attempts: int = 1
+    backoff_ms: int = 0
+    retry_on_status: List[int] = field(default_factory=list)
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> RetryPolicy:
+        if not isinstance(data, dict):
+            raise ValueError(f"retry must be an object, got {type(data).__name__}")
+        return cls(
+            max_attempts=data.get("max_attempts", 1),
+            backoff_ms=data.get("backoff_ms", 0),
+            retry_on_status=data.get("retry_on_status", []),
+        )
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "max_attempts": self.max_attempts,
+            "backoff_ms": self.backoff_ms,
+            "retry_on_status": list(self.retry_on_status),
+        }
+
+
 @dataclass
 class DAGNode:
-    """A node in the execution DAG."""
+    """A node in the execution DAG. Equality and hashing: see JadeSkill."""
     id: str
     action: str
     params: Dict[str, Any]
+    timeout_ms: Optional[int] = None  # Per-node execution cap, across all attempts
+    retry: Optional[RetryPolicy] = None  # None runs the node once
+
+    def __hash__(self) -> int:
+        return hash(_frozen(self))
 
     @classmethod
     def from_dict(cls, data: Dict[str, Any]) -> DAGNode:
-        return cls(id=data["id"], action=data["action"], params=data["params"])
+        return cls(
+            id=data["id"],
+            action=data["action"],
+            params=data["params"],
+            timeout_ms=data.get("timeout_ms"),
+            retry=RetryPolicy.from_dict(data["retry"]) if "retry" in data else None,
+        )
 
 
 @dataclass
@@ -196,7 +716,7 @@ class ExecutionDAG:
 
 @dataclass
 class SecurityPolicy:
-    """Security constraints for a skill."""
+    """Security constraints for a skill. Equality and hashing: see JadeSkill."""
     network_whitelist: List[str]
     file_read_paths: List[str]
     file_write_paths: List[str]
@@ -204,6 +724,10 @@ class SecurityPolicy:
     max_retries: int
     sandbox_level: SandboxLevel
     dangerous_patterns: List[str]
+    env_whitelist: List[str] = field(default_factory=list)  # Env vars the skill may read
+
+    def __hash__(self) -> int:
+        return hash(_frozen(self))
 
     @classmethod
     def from_dict(cls, data: Dict[str, Any]) -> SecurityPolicy:
@@ -214,8 +738,9 @@ class SecurityPolicy:
             file_write_paths=file_perms.get("write", []),
             max_execution_time_ms=data.get("max_execution_time_ms", 30000),
             max_retries=data.get("max_retries", 3),
-            sandbox_level=SandboxLevel(data.get("sandbox_level", "strict")),
+            sandbox_level=SandboxLevel(data.get("sandbox_level") or "strict"),  # Absent or empty: strict
             dangerous_patterns=data.get("dangerous_patterns", []),
+            env_whitelist=data.get("env_whitelist", []),
         )
 
 
@@ -245,9 +770,82 @@ class SkillMetadata:
         )
 
 
+@dataclass
+class JadeSignature:
+    """Root signature sealing a skill's content."""
+    public_key: str
+    signature: str
+    signer: str = ""
+    algorithm: str = "ed25519"
+    content_hash: str = ""
+    signed_at: str = ""
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "signer": self.signer,
+            "algorithm": self.algorithm,
+            "public_key": self.public_key,
+            "content_hash": self.content_hash,
+            "signature": self.signature,
+            "signed_at": self.signed_at,
+        }
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> JadeSignature:
+        return cls(
+            public_key=data["public_key"],
+            signature=data["signature"],
+            signer=data.get("signer", ""),
+            algorithm=data.get("algorithm", "ed25519"),
+            content_hash=data.get("content_hash", ""),
+            signed_at=data.get("signed_at", ""),
+        )
+
+
+@dataclass
+class CommunitySignature:
+    """A co-signature from a community member vouching for a skill."""
+    public_key: str
+    signature: str
+    signer: str = ""
+    trust_level: str = "community"
+    signed_at: str = ""
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "signer": self.signer,
+            "public_key": self.public_key,
+            "signature": self.signature,
+            "trust_level": self.trust_level,
+            "signed_at": self.signed_at,
+        }
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> CommunitySignature:
+        return cls(
+            public_key=data["public_key"],
+            signature=data["signature"],
+            signer=data.get("signer", ""),
+            trust_level=data.get("trust_level", "community"),
+            signed_at=data.get("signed_at", ""),
+        )
+
+
 @dataclass
 class JadeSkill:
-    """A complete JADE skill definition."""
+    """
+    A complete JADE skill definition.
+
+    Equality is structural over the parsed fields, before any
+    normalization: list order matters (whitelists, nodes, edges), values
+    compare as Python values (1 == 1.0, key order is irrelevant), and
+    strings such as versions compare as written. raw_data, the source
+    document, is not compared, so two documents that parse to the same
+    fields are equal. DAGNode and SecurityPolicy compare the same way.
+
+    All three hash consistently with equality, for sets and dedup. They
+    are mutable: don't change one while it is in a set or a dict key.
+    """
     jade_version: str
     skill_id: str
     metadata: SkillMetadata
@@ -258,8 +856,13 @@ class JadeSkill:
     output_schema: Optional[Dict[str, Any]] = None
     mcp_compatible: bool = False
     required_mcp_capabilities: List[str] = field(default_factory=list)
+    jade_signature: Optional[JadeSignature] = None
+    community_signatures: List[CommunitySignature] = field(default_factory=list)
+
+    raw_data: Dict[str, Any] = field(default_factory=dict, compare=False)
 
-    raw_data: Dict[str, Any] = field(default_factory=dict)
+    def __hash__(self) -> int:
+        return hash(_frozen(self))
 
     @classmethod
     def from_dict(cls, data: Dict[str, Any]) -> JadeSkill:
@@ -274,6 +877,13 @@ class JadeSkill:
             output_schema=data.get("output_schema"),
             mcp_compatible=data.get("mcp_compatible", False),
             required_mcp_capabilities=data.get("required_mcp_capabilities", []),
+            jade_signature=(
+                JadeSignature.from_dict(data["jade_signature"])
+                if data.get("jade_signature") else None
+            ),
+            community_signatures=[
+                CommunitySignature.from_dict(c) for c in data.get("community_signatures", [])
+            ],
             raw_data=data,
         )
 
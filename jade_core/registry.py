@@ -24,6 +24,7 @@ import time
 from pathlib import Path
 from typing import Any, Dict, List, Optional, Tuple
 
+from .errors import JadeIOError, JadeParseError, NotFoundError
 from .models import (
     Attestation,
     AttestationType,
@@ -128,7 +129,7 @@ class JadeRegistry:
         entry = self._entries.get(skill_id)
 
         if not entry:
-            raise ValueError(f"Skill '{skill_id}' not found in registry")
+            raise NotFoundError(f"Skill '{skill_id}' not found in registry")
 
         # Verify hash matches
         if attestation.skill_hash and attestation.skill_hash != entry.skill_hash:
@@ -336,9 +337,12 @@ class JadeRegistry:
             },
         }
 
-        os.makedirs(os.path.dirname(save_path) or ".", exist_ok=True)
-        with open(save_path, "w", encoding="utf-8") as f:
-            json.dump(data, f, indent=2, ensure_ascii=False)
+        try:
+            os.makedirs(os.path.dirname(save_path) or ".", exist_ok=True)
+            with open(save_path, "w", encoding="utf-8") as f:
+                json.dump(data, f, indent=2, ensure_ascii=False)
+        except OSError as e:
+            raise JadeIOError(f"Cannot write registry {save_path}: {e}", save_path) from e
 
         return save_path
 
@@ -351,8 +355,13 @@ class JadeRegistry:
         if not load_path or not os.path.exists(load_path):
             return 0
 
-        with open(load_path, "r", encoding="utf-8") as f:
-            data = json.load(f)
+        try:
+            with open(load_path, "r", encoding="utf-8") as f:
+                data = json.load(f)
+        except OSError as e:
+            raise JadeIOError(f"Cannot read registry {load_path}: {e}", load_path) from e
+        except json.JSONDecodeError as e:
+            raise JadeParseError(f"Registry {load_path} is not valid JSON: {e}") from e
 
         entries = data.get("entries", {})
         for sid, entry_data in entries.items():
@@ -0,0 +1,160 @@
+"""
+Project JADE - Validation Reports
+Render a ValidationResult for people or tools, independent of the CLI.
+
+    print(render(result, ReportFormat.HUMAN, name="weather.json"))
+
+HUMAN is the `jadegate verify` layout: a PASS/FAIL line, then each error
+with its code and source position. COMPACT is one line per error or
+warning in the 'file:line:column: severity: [CODE] message' form editors
+and CI annotators parse. JSON is ValidationResult.to_dict(). SARIF is a
+SARIF 2.1.0 log for code-scanning UIs (GitHub, GitLab); sarif_log()
+builds one log covering several files.
+
+Color applies to HUMAN only. By default it is on when stdout is a
+terminal, so piped or redirected output carries no escape codes.
+"""
+
+from __future__ import annotations
+
+import json
+import sys
+from enum import Enum
+from typing import Any, Dict, Iterable, List, Optional, Tuple
+
+from .models import ValidationIssue, ValidationResult
+
+_GREEN = "\033[32m"
+_RED = "\033[31m"
+_DIM = "\033[2m"
+_RESET = "\033[0m"
+
+
+class ReportFormat(str, Enum):
+    HUMAN = "human"
+    JSON = "json"
+    COMPACT = "compact"
+    SARIF = "sarif"
+
+
+SARIF_VERSION = "2.1.0"
+SARIF_SCHEMA = "https://json.schemastore.org/sarif-2.1.0.json"
+
+_SARIF_LEVELS = {"error": "error", "warning": "warning", "info": "note"}
+
+
+def render(
+    result: ValidationResult,
+    format: ReportFormat = ReportFormat.HUMAN,
+    name: str = "<memory>",
+    color: Optional[bool] = None,
+    timings: bool = False,
+) -> str:
+    """
+    Render one result. `name` labels it (usually the file path); `color`
+    None means auto-detect; `timings` adds per-layer times to HUMAN output.
+    """
+    format = ReportFormat(format)
+    if format == ReportFormat.JSON:
+        return json.dumps(result.to_dict(), indent=2, ensure_ascii=False)
+    if format == ReportFormat.COMPACT:
+        return _render_compact(result, name)
+    if format == ReportFormat.SARIF:
+        return json.dumps(sarif_log([(name, result)]), indent=2, ensure_ascii=False)
+    if color is None:
+        color = sys.stdout.isatty()
+    return _render_human(result, name, color, timings)
+
+
+def format_timings(timings_ms: Dict[int, float]) -> str:
+    """One line of per-layer wall time, e.g. 'timings: L1 0.41ms  L2 0.08ms'."""
+    if not timings_ms:
+        return "timings: no layers ran"
+    parts = [f"L{layer} {ms:.2f}ms" for layer, ms in sorted(timings_ms.items())]
+    return "timings: " + "  ".join(parts)
+
+
+def _render_human(result: ValidationResult, name: str, color: bool, timings: bool) -> str:
+    def paint(code: str, text: str) -> str:
+        return f"{code}{text}{_RESET}" if color else text
+
+    suppressed = sum(1 for i in result.issues if i.suppressed)
+    hidden = ", ".join(f"{count} {severity}" for severity, count in result.hidden.items())
+    hidden_note = "  " + paint(_DIM, f"({hidden} hidden)") if hidden else ""
+    if result.valid:
+        note = "  " + paint(_DIM, f"({suppressed} suppressed)") if suppressed else ""
+        lines = [f"  {paint(_GREEN, '✅ PASS')} {name}{note}{hidden_note}"]
+    else:
+        lines = [f"  {paint(_RED, '❌ FAIL')} {name}{hidden_note}"]
+        for issue in result.errors:
+            where = "  " + paint(_DIM, f"(line {issue.location[0]}, column {issue.location[1]})") if issue.location else ""
+            lines.append(f"    [{issue.code}] {issue.message}{where}")
+    if timings:
+        lines.append("    " + paint(_DIM, format_timings(result.timings_ms)))
+    return "\n".join(lines)
+
+
+def _render_compact(result: ValidationResult, name: str) -> str:
+    lines = [_compact_line(issue, name) for issue in result.errors + result.warnings]
+    return "\n".join(lines) if lines else f"{name}: ok"
+
+
+def _compact_line(issue: ValidationIssue, name: str) -> str:
+    line, column = issue.location or (0, 0)
+    return f"{name}:{line}:{column}: {issue.severity.value}: [{issue.code}] {issue.message}"
+
+
+def sarif_log(results: Iterable[Tuple[str, ValidationResult]]) -> Dict[str, Any]:
+    """
+    One SARIF 2.1.0 log for (file, result) pairs, as a JSON-ready dict.
+
+    Each issue becomes a result whose ruleId is its stable code, with the
+    file and, when known, line and column as its location. The issue path
+    is kept as a logical location. Suppressed issues are included with an
+    external suppression, so the UI shows them as dismissed.
+    """
+    from . import __version__
+
+    rules: List[Dict[str, Any]] = []
+    rule_index: Dict[str, int] = {}
+    sarif_results: List[Dict[str, Any]] = []
+    for name, result in results:
+        for issue in result.issues:
+            if issue.code not in rule_index:
+                rule_index[issue.code] = len(rules)
+                rules.append({"id": issue.code, "shortDescription": {"text": issue.code}})
+            sarif_results.append(_sarif_result(issue, name, rule_index[issue.code]))
+    return {
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {"driver": {
+                "name": "JadeGate",
+                "informationUri": "https://github.com/JadeGate/jadegate",
+                "version": __version__,
+                "rules": rules,
+            }},
+            "results": sarif_results,
+        }],
+    }
+
+
+def _sarif_result(issue: ValidationIssue, name: str, rule_index: int) -> Dict[str, Any]:
+    physical: Dict[str, Any] = {"artifactLocation": {"uri": name}}
+    if issue.location:
+        physical["region"] = {"startLine": issue.location[0], "startColumn": issue.location[1]}
+    location: Dict[str, Any] = {"physicalLocation": physical}
+    if issue.path:
+        location["logicalLocations"] = [{"fullyQualifiedName": issue.path}]
+    entry: Dict[str, Any] = {
+        "ruleId": issue.code,
+        "ruleIndex": rule_index,
+        "level": _SARIF_LEVELS[issue.severity.value],
+        "message": {"text": issue.message},
+        "locations": [location],
+    }
+    if issue.suppressed:
+        entry["suppressions"] = [{"kind": "external"}]
+    if issue.layer is not None:
+        entry["properties"] = {"layer": int(issue.layer)}
+    return entry
@@ -4,25 +4,52 @@ Zero-Trust security verification for JADE skills.
 
 This module enforces:
 1. No executable code (non-Turing-complete check)
-2. Network whitelist enforcement
+2. Network whitelist enforcement (including SSRF-prone entries)
 3. Dangerous pattern detection (rm -rf, mkfs, etc.)
 4. File permission boundary checks
 5. Sandbox level enforcement
+
+Pattern scans only target fields that feed execution (node actions and
+params). Metadata text is scanned too, but only yields INFO notes.
 """
 
 from __future__ import annotations
 
+import base64
+import difflib
+import ipaddress
 import re
-from typing import Any, Dict, List, Set
+import unicodedata
+from dataclasses import dataclass, field
+from typing import Any, Dict, Iterable, List, Optional, Pattern, Set, Tuple, Union
 from urllib.parse import urlparse
 
 from .models import (
+    SHELL_ACTIONS,
+    DAGNode,
+    IssueCode,
     JadeSkill,
     SecurityPolicy,
     SandboxLevel,
     ValidationIssue,
     ValidationSeverity,
 )
+from .dag import DAGAnalyzer, node_timeout_ms
+from .errors import CycleError
+from .templates import ENV_ROOT, TEMPLATE_FUNCTIONS, TEMPLATE_PATTERN, template_functions, template_references
+
+# All pattern lists below are regular expressions, compiled once per
+# SecurityEngine and matched case-insensitively. Keywords are anchored with
+# \b so incidental substrings ("evaluate(", "systematic") don't match.
+# Path and punctuation patterns can't use \b on their leading side, so they
+# use explicit lookarounds instead: /bin/sh, /bin/bash, the fork bomb and
+# localhost.
+#
+# Injection rules match against normalize_for_scan() of each field, so
+# fullwidth forms, zero-width characters and common Cyrillic/Greek
+# look-alikes can't smuggle 'eval(' or '<script' past them. None of the
+# built-in rules are case-sensitive; InjectionRule.case_sensitive opts a
+# custom rule out of case folding.
 
 # Patterns that indicate executable code injection
 EXECUTABLE_CODE_PATTERNS = [
@@ -46,8 +73,10 @@ EXECUTABLE_CODE_PATTERNS = [
     r'\bWScript\b',
     r'\bpowershell\b',
     r'\bcmd\.exe\b',
-    r'\b/bin/sh\b',
-    r'\b/bin/bash\b',
+    r'(?<![\w/])/bin/sh\b',
+    r'(?<![\w/])/bin/bash\b',
+    r'\bUNION\s+(?:ALL\s+)?SELECT\b',
+    r'\bDROP\s+TABLE\b',
 ]
 
 # Dangerous system commands
@@ -60,7 +89,7 @@ DANGEROUS_COMMANDS = [
     r'\bfdisk\b',
     r'\bchmod\s+777\b',
     r'\bchmod\s+-R\s+777\b',
-    r'\b:(){ :\|:& };:',  # fork bomb
+    r':\(\)\s*\{\s*:\s*\|\s*:\s*&\s*\}\s*;\s*:',  # fork bomb
     r'\bshutdown\b',
     r'\breboot\b',
     r'\binit\s+0\b',
@@ -81,10 +110,11 @@ DANGEROUS_COMMANDS = [
     r'\brsync\s+.*@',
 ]
 
-# Suspicious network patterns
+# Suspicious network patterns (kept for callers that scan free text;
+# whitelist entries are classified with classify_whitelist_entry)
 SUSPICIOUS_NETWORK_PATTERNS = [
     r'\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}',  # Raw IP addresses
-    r'localhost',
+    r'\blocalhost\b',
     r'127\.0\.0\.1',
     r'0\.0\.0\.0',
     r'10\.\d{1,3}\.\d{1,3}\.\d{1,3}',  # Private IP ranges
@@ -94,6 +124,155 @@ SUSPICIOUS_NETWORK_PATTERNS = [
     r'\.i2p$',
 ]
 
+# Whitelist entry classes returned by classify_whitelist_entry
+NET_CLOUD_METADATA = "cloud_metadata"
+NET_LOOPBACK = "loopback"
+NET_PRIVATE = "private"
+NET_LINK_LOCAL = "link_local"
+NET_UNIQUE_LOCAL = "unique_local"
+NET_UNSPECIFIED = "unspecified"
+NET_RESERVED = "reserved"
+NET_DOCUMENTATION = "documentation"
+NET_SHARED = "shared_address_space"
+NET_RAW_IP = "raw_ip"
+NET_INTERNAL_NAME = "internal_name"
+NET_ANONYMITY = "anonymity_network"
+
+CLOUD_METADATA_HOSTS = {
+    "169.254.169.254",
+    "fd00:ec2::254",
+    "metadata.google.internal",
+    "metadata.azure.com",
+}
+INTERNAL_SUFFIXES = (".internal", ".local", ".localdomain", ".lan", ".home.arpa", ".corp")
+ANONYMITY_SUFFIXES = (".onion", ".i2p")
+_SHARED_ADDRESS_SPACE = ipaddress.ip_network("100.64.0.0/10")  # carrier-grade NAT
+_RFC1918 = tuple(ipaddress.ip_network(n) for n in ("10.0.0.0/8", "172.16.0.0/12", "192.168.0.0/16"))
+_DOCUMENTATION = tuple(ipaddress.ip_network(n) for n in (
+    "192.0.2.0/24", "198.51.100.0/24", "203.0.113.0/24", "2001:db8::/32",  # RFC 5737, RFC 3849
+))
+
+_NET_DESCRIPTIONS = {
+    NET_CLOUD_METADATA: "cloud metadata endpoint",
+    NET_LOOPBACK: "loopback address",
+    NET_PRIVATE: "private (RFC 1918) range",
+    NET_SHARED: "shared address space (RFC 6598)",
+    NET_DOCUMENTATION: "documentation range (RFC 5737 / RFC 3849)",
+    NET_LINK_LOCAL: "link-local range",
+    NET_UNIQUE_LOCAL: "unique local IPv6 range",
+    NET_UNSPECIFIED: "unspecified address",
+    NET_RESERVED: "reserved or special-purpose range",
+    NET_RAW_IP: "public IP address or range",
+    NET_INTERNAL_NAME: "internal hostname",
+    NET_ANONYMITY: "anonymity network",
+}
+
+
+# 'host:port' (not IPv6, which needs brackets to carry a port)
+_HOST_PORT_RE = re.compile(r"^[^:/\[\]]+:\d+$")
+# `{{...}}` templates in URLs, replaced by a placeholder host part before parsing
+_URL_TEMPLATE_RE = re.compile(r"\{\{[^}]+\}\}")
+
+
+def classify_whitelist_entry(entry: str) -> Optional[str]:
+    """
+    Classify a network_whitelist entry (hostname, IP or CIDR).
+
+    Returns one of the NET_* classes for SSRF-prone entries, or None for an
+    ordinary public hostname. Public IP addresses and CIDR ranges are
+    NET_RAW_IP. Wildcards ('*.example.com') are classified by their suffix;
+    a scheme or port on the entry is ignored.
+    """
+    host = entry.strip().lower()
+    if "://" in host:
+        host = split_host_port(host, default_port=False)[0]
+    elif _HOST_PORT_RE.match(host):
+        host = host.rsplit(":", 1)[0]
+    host = host.rstrip(".")
+    if host.startswith("*."):
+        host = host[2:]
+    if host.startswith("[") and "]" in host:
+        host = host[1:host.index("]")]
+
+    if host in CLOUD_METADATA_HOSTS:
+        return NET_CLOUD_METADATA
+
+    try:
+        network = ipaddress.ip_network(host, strict=False)
+    except ValueError:
+        network = None
+
+    if network is None:
+        dotted = "." + host
+        if dotted.endswith(".localhost"):
+            return NET_LOOPBACK
+        if dotted.endswith(ANONYMITY_SUFFIXES):
+            return NET_ANONYMITY
+        if dotted.endswith(INTERNAL_SUFFIXES):
+            return NET_INTERNAL_NAME
+        return None
+
+    metadata = [ipaddress.ip_address(h) for h in CLOUD_METADATA_HOSTS if _is_ip(h)]
+    if any(ip.version == network.version and ip in network for ip in metadata):
+        return NET_CLOUD_METADATA
+    address = network.network_address
+    if address.is_unspecified:
+        return NET_UNSPECIFIED
+    if address.is_loopback:
+        return NET_LOOPBACK
+    if address.is_link_local:
+        return NET_LINK_LOCAL
+    if network.version == 6 and network.subnet_of(ipaddress.ip_network("fc00::/7")):  # type: ignore[arg-type]
+        return NET_UNIQUE_LOCAL
+    if _within(network, _RFC1918):
+        return NET_PRIVATE
+    if _within(network, (_SHARED_ADDRESS_SPACE,)):
+        return NET_SHARED
+    if _within(network, _DOCUMENTATION):
+        return NET_DOCUMENTATION
+    if address.is_private or address.is_reserved or address.is_multicast:
+        return NET_RESERVED
+    return NET_RAW_IP
+
+
+def _within(network: Any, ranges: Tuple[Any, ...]) -> bool:
+    """Whether `network` lies inside one of `ranges` of the same IP version."""
+    return any(network.version == r.version and network.subnet_of(r) for r in ranges)
+
+
+# Confusable characters folded to the Latin letter they imitate. Covers the
+# Cyrillic and Greek look-alikes typically used to disguise keywords.
+_HOMOGLYPHS = str.maketrans({
+    # Cyrillic
+    "а": "a", "в": "b", "е": "e", "к": "k", "м": "m", "н": "h", "о": "o", "р": "p",
+    "с": "c", "т": "t", "у": "y", "х": "x", "і": "i", "ј": "j", "ѕ": "s", "ԁ": "d",
+    "ԛ": "q", "ԝ": "w", "ѵ": "v", "һ": "h", "ӏ": "l",
+    "А": "A", "В": "B", "Е": "E", "К": "K", "М": "M", "Н": "H", "О": "O", "Р": "P",
+    "С": "C", "Т": "T", "Х": "X", "Ѕ": "S", "І": "I", "Ј": "J",
+    # Greek
+    "α": "a", "ε": "e", "ι": "i", "κ": "k", "ν": "v", "ο": "o", "ρ": "p", "τ": "t", "υ": "u",
+    "Α": "A", "Β": "B", "Ε": "E", "Ζ": "Z", "Η": "H", "Ι": "I", "Κ": "K", "Μ": "M",
+    "Ν": "N", "Ο": "O", "Ρ": "P", "Τ": "T", "Χ": "X", "Υ": "Y",
+    # Latin
+    "ı": "i",
+    # Invisible characters
+    "\u200b": None, "\u200c": None, "\u200d": None, "\u2060": None, "\ufeff": None,
+})
+
+
+def normalize_for_scan(text: str) -> str:
+    """NFKC-normalize text and fold homoglyphs and invisible characters away."""
+    return unicodedata.normalize("NFKC", text).translate(_HOMOGLYPHS)
+
+
+def _is_ip(value: str) -> bool:
+    try:
+        ipaddress.ip_address(value)
+        return True
+    except ValueError:
+        return False
+
+
 # Data exfiltration patterns
 DATA_EXFIL_PATTERNS = [
     r'api[_-]?key',
@@ -111,6 +290,231 @@ DATA_EXFIL_PATTERNS = [
 ]
 
 
+# Encoded runs long enough to hide a payload. Hex is tried first since a
+# hex run is also valid base64.
+_ENCODED_RUNS = [
+    ("hex", re.compile(r"\b(?:[0-9a-fA-F]{2}){8,}\b")),
+    ("base64", re.compile(r"(?<![A-Za-z0-9+/_-])[A-Za-z0-9+/_-]{16,}={0,2}")),
+]
+
+
+def _decode_run(encoding: str, run: str) -> Optional[str]:
+    """Decode a hex or base64 run to text; None unless it is mostly printable."""
+    try:
+        if encoding == "hex":
+            raw = bytes.fromhex(run)
+        else:
+            if re.fullmatch(r"[0-9a-fA-F]+", run):
+                return None  # Already tried as hex
+            core = run.rstrip("=").replace("-", "+").replace("_", "/")
+            raw = base64.b64decode(core + "=" * (-len(core) % 4), validate=True)
+        text = raw.decode("utf-8")
+    except (ValueError, UnicodeDecodeError):
+        return None
+    printable = sum(1 for ch in text if ch.isprintable() or ch in "\t\r\n")
+    return text if text and printable >= 0.9 * len(text) else None
+
+# Environment variable names that look like secrets
+SENSITIVE_ENV_PATTERNS = [
+    r'secret',
+    r'token',
+    r'passw(?:or)?d',
+    r'api[_-]?key',
+    r'private[_-]?key',
+    r'access[_-]?key',
+    r'credential',
+    r'^aws_',
+]
+
+# Action that reads the environment variable named by its `name` param
+ENV_READ_ACTION = "env_read"
+
+
+# Field scopes an injection rule can apply to
+FIELD_ACTION = "action"
+FIELD_PARAMS = "params"
+FIELD_METADATA = "metadata"
+EXECUTABLE_FIELDS = (FIELD_ACTION, FIELD_PARAMS)
+
+
+@dataclass
+class InjectionRule:
+    """A single pattern scanned for in skill fields."""
+    pattern: str
+    severity: ValidationSeverity = ValidationSeverity.ERROR
+    code: str = "SEC_EXEC_CODE"
+    message: str = "Executable code pattern detected"
+    applies_to_fields: List[str] = field(default_factory=lambda: list(EXECUTABLE_FIELDS))
+    case_sensitive: bool = False
+
+    def compile(self) -> Pattern[str]:
+        return re.compile(self.pattern, 0 if self.case_sensitive else re.IGNORECASE)
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "pattern": self.pattern,
+            "severity": self.severity.value,
+            "code": self.code,
+            "message": self.message,
+            "applies_to_fields": list(self.applies_to_fields),
+            "case_sensitive": self.case_sensitive,
+        }
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> InjectionRule:
+        return cls(
+            pattern=data["pattern"],
+            severity=ValidationSeverity.parse(data.get("severity", "error")),
+            code=data.get("code", "SEC_EXEC_CODE"),
+            message=data.get("message", "Executable code pattern detected"),
+            applies_to_fields=list(data.get("applies_to_fields", EXECUTABLE_FIELDS)),
+            case_sensitive=bool(data.get("case_sensitive", False)),
+        )
+
+
+# Domains whose hosts are all on the browsers' HSTS preload list: whole
+# TLDs preloaded by their registry. Extend SecurityRules.hsts_preloaded
+# with the preloaded domains a deployment relies on.
+HSTS_PRELOADED_DOMAINS = ("app", "dev", "page")
+
+
+@dataclass
+class SecurityRules:
+    r"""
+    The injection ruleset consumed by SecurityEngine.
+
+    Platform teams can start from SecurityRules.default() and downgrade,
+    scope or add rules:
+
+        rules = SecurityRules.default()
+        rules.set_severity(r'\bsudo\s+', ValidationSeverity.WARNING)
+        rules.add(InjectionRule(r'\bcurl\b.*\|\s*bash', code="SEC_PIPE_TO_SHELL"))
+
+    Setting decode_encoded turns on the (more expensive) deep scan: long
+    base64 or hex runs in node params are decoded and rescanned, up to
+    max_decode_depth nested encodings and max_decoded_bytes per run.
+
+    template_functions is the allowlist of functions templates may call;
+    add a name here after registering it with the executor.
+
+    With require_hsts_preload set, a strict-sandbox skill may only
+    whitelist hosts under a domain in hsts_preloaded, so no request can
+    be downgraded to cleartext even on first contact.
+    """
+    injection_rules: List[InjectionRule] = field(default_factory=list)
+    trusted_egress: List[str] = field(default_factory=list)  # Whitelist entries not counted as egress
+    decode_encoded: bool = False
+    max_decode_depth: int = 2
+    max_decoded_bytes: int = 64 * 1024
+    template_functions: List[str] = field(default_factory=lambda: list(TEMPLATE_FUNCTIONS))
+    require_hsts_preload: bool = False
+    hsts_preloaded: List[str] = field(default_factory=lambda: list(HSTS_PRELOADED_DOMAINS))
+
+    @classmethod
+    def default(cls) -> SecurityRules:
+        """The built-in ruleset: executable code and dangerous commands, all errors."""
+        rules = [
+            InjectionRule(pattern=p, code=IssueCode.SEC_EXEC_CODE,
+                          message="Executable code pattern detected")
+            for p in EXECUTABLE_CODE_PATTERNS
+        ]
+        rules.extend(
+            InjectionRule(pattern=p, code=IssueCode.SEC_DANGEROUS_CMD,
+                          message="Dangerous system command detected")
+            for p in DANGEROUS_COMMANDS
+        )
+        return cls(injection_rules=rules)
+
+    def add(self, rule: InjectionRule) -> None:
+        self.injection_rules.append(rule)
+
+    def set_severity(self, pattern: str, severity: ValidationSeverity) -> None:
+        """Change the severity of every rule with the given pattern."""
+        for rule in self.injection_rules:
+            if rule.pattern == pattern:
+                rule.severity = severity
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "injection_rules": [r.to_dict() for r in self.injection_rules],
+            "trusted_egress": list(self.trusted_egress),
+            "decode_encoded": self.decode_encoded,
+            "max_decode_depth": self.max_decode_depth,
+            "max_decoded_bytes": self.max_decoded_bytes,
+            "template_functions": list(self.template_functions),
+            "require_hsts_preload": self.require_hsts_preload,
+            "hsts_preloaded": list(self.hsts_preloaded),
+        }
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> SecurityRules:
+        return cls(
+            injection_rules=[InjectionRule.from_dict(r) for r in data.get("injection_rules", [])],
+            trusted_egress=list(data.get("trusted_egress", [])),
+            decode_encoded=bool(data.get("decode_encoded", False)),
+            max_decode_depth=int(data.get("max_decode_depth", 2)),
+            max_decoded_bytes=int(data.get("max_decoded_bytes", 64 * 1024)),
+            template_functions=list(data.get("template_functions", TEMPLATE_FUNCTIONS)),
+            require_hsts_preload=bool(data.get("require_hsts_preload", False)),
+            hsts_preloaded=list(data.get("hsts_preloaded", HSTS_PRELOADED_DOMAINS)),
+        )
+
+
+# Risk weight of each dangerous capability; a skill's risk score is the
+# sum of the weights it triggers, capped at 100
+CAPABILITY_RISK_WEIGHTS = {
+    "wildcard_egress": 40,
+    "shell_actions": 30,
+    "sensitive_env": 25,
+    "internal_network": 25,
+    "no_timeout": 15,
+    "file_write": 10,
+    "relaxed_sandbox": 10,
+}
+
+
+@dataclass
+class RiskFactor:
+    """One dangerous capability a skill is granted."""
+    name: str  # A CAPABILITY_RISK_WEIGHTS key
+    detail: str
+    weight: int
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {"name": self.name, "detail": self.detail, "weight": self.weight}
+
+
+@dataclass
+class CapabilitySummary:
+    """What a skill may do at runtime, for review before approving it."""
+    skill_id: str
+    network_hosts: List[str]
+    env_vars: List[str]
+    file_read: List[str]
+    file_write: List[str]
+    sandbox_level: str
+    max_execution_time_ms: Optional[int]  # None when the skill sets no limit
+    risks: List[RiskFactor] = field(default_factory=list)
+
+    @property
+    def risk_score(self) -> int:
+        """0 (locked down) to 100."""
+        return min(100, sum(r.weight for r in self.risks))
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "skill_id": self.skill_id,
+            "network_hosts": list(self.network_hosts),
+            "env_vars": list(self.env_vars),
+            "file_read": list(self.file_read),
+            "file_write": list(self.file_write),
+            "sandbox_level": self.sandbox_level,
+            "max_execution_time_ms": self.max_execution_time_ms,
+            "risk_score": self.risk_score,
+            "risks": [r.to_dict() for r in self.risks],
+        }
+
+
 class SecurityEngine:
     """
     JADE Security Engine - The "immune system" of the protocol.
@@ -122,62 +526,119 @@ class SecurityEngine:
     4. Free of dangerous patterns
     """
 
-    def __init__(self, allowed_actions: List[str] | None = None):
+    def __init__(
+        self,
+        allowed_actions: List[str] | None = None,
+        rules: Optional[SecurityRules] = None,
+    ):
         self._allowed_actions: Set[str] = set(allowed_actions) if allowed_actions else set()
-        self._compiled_exec_patterns = [re.compile(p, re.IGNORECASE) for p in EXECUTABLE_CODE_PATTERNS]
-        self._compiled_danger_patterns = [re.compile(p, re.IGNORECASE) for p in DANGEROUS_COMMANDS]
-        self._compiled_network_patterns = [re.compile(p, re.IGNORECASE) for p in SUSPICIOUS_NETWORK_PATTERNS]
+        self.set_rules(rules or SecurityRules.default())
         self._compiled_exfil_patterns = [re.compile(p, re.IGNORECASE) for p in DATA_EXFIL_PATTERNS]
+        self._compiled_env_patterns = [re.compile(p, re.IGNORECASE) for p in SENSITIVE_ENV_PATTERNS]
 
-    def set_allowed_actions(self, actions: List[str]) -> None:
+    def set_allowed_actions(self, actions: Iterable[str]) -> None:
         self._allowed_actions = set(actions)
 
+    def set_rules(self, rules: SecurityRules) -> None:
+        """Replace the injection ruleset. Patterns are compiled once here, never per check."""
+        compiled: List[Tuple[InjectionRule, Pattern[str]]] = [
+            (rule, rule.compile()) for rule in rules.injection_rules
+        ]
+        self._rules, self._compiled_rules = rules, compiled
+
+    @property
+    def rules(self) -> SecurityRules:
+        return self._rules
+
     def check_all(self, skill: JadeSkill) -> List[ValidationIssue]:
-        """Run all security checks on a skill."""
+        """
+        Run all security checks on a skill.
+
+        Action names are not checked here: the validator reports them in
+        Layer 1 as UNKNOWN_ACTION. check_allowed_actions remains for callers
+        that use the engine on its own.
+        """
         issues: List[ValidationIssue] = []
-        issues.extend(self.check_no_executable_code(skill))
-        issues.extend(self.check_dangerous_patterns(skill))
+        issues.extend(self.check_injection_rules(skill))
         issues.extend(self.check_network_whitelist(skill))
         issues.extend(self.check_file_permissions(skill))
-        issues.extend(self.check_allowed_actions(skill))
         issues.extend(self.check_sandbox_constraints(skill))
+        issues.extend(self.check_time_budget(skill))
         issues.extend(self.check_data_exfiltration(skill))
+        issues.extend(self.check_env_exfiltration(skill))
+        issues.extend(self.check_env_references(skill))
+        issues.extend(self.check_template_functions(skill))
+        if self._rules.decode_encoded:
+            issues.extend(self.check_encoded_payloads(skill))
         return issues
 
+    def capability_summary(self, skill: JadeSkill) -> CapabilitySummary:
+        """
+        Summarize the skill's SecurityPolicy and flag dangerous grants.
+
+        Uses the same classifications as the checks: SSRF-prone whitelist
+        entries, secret-like env vars and shell actions. The timeout is the
+        policy's max_execution_time_ms, which defaults to 30000 when the
+        skill omits it; only a non-positive value counts as no timeout.
+        """
+        policy = skill.security
+        configured = policy.max_execution_time_ms
+        limit = configured if _is_count(configured) and configured > 0 else None
+        risks: List[RiskFactor] = []
+
+        def flag(name: str, detail: str) -> None:
+            risks.append(RiskFactor(name, detail, CAPABILITY_RISK_WEIGHTS[name]))
+
+        if "*" in policy.network_whitelist:
+            flag("wildcard_egress", "network_whitelist allows any host ('*')")
+        internal = [
+            e for e in policy.network_whitelist
+            if e != "*" and classify_whitelist_entry(e) not in (None, NET_RAW_IP)
+        ]
+        if internal:
+            flag("internal_network", f"network_whitelist reaches {', '.join(repr(e) for e in internal)}")
+        secrets = [
+            name for name in policy.env_whitelist
+            if any(p.search(name) for p in self._compiled_env_patterns)
+        ]
+        if secrets:
+            flag("sensitive_env", f"env_whitelist grants {', '.join(repr(n) for n in secrets)}")
+        shell = _unique_ids(n.id for n in skill.execution_dag.nodes if n.action in SHELL_ACTIONS)
+        if shell:
+            flag("shell_actions", f"nodes {', '.join(repr(n) for n in shell)} run shell commands")
+        if limit is None:
+            flag("no_timeout", f"security.max_execution_time_ms is {configured!r}, which sets no limit")
+        if policy.file_write_paths:
+            flag("file_write", f"may write {', '.join(repr(p) for p in policy.file_write_paths)}")
+        if policy.sandbox_level != SandboxLevel.STRICT:
+            flag("relaxed_sandbox", f"'{policy.sandbox_level.value}' sandbox")
+
+        return CapabilitySummary(
+            skill_id=skill.skill_id,
+            network_hosts=list(policy.network_whitelist),
+            env_vars=list(policy.env_whitelist),
+            file_read=list(policy.file_read_paths),
+            file_write=list(policy.file_write_paths),
+            sandbox_level=policy.sandbox_level.value,
+            max_execution_time_ms=limit,
+            risks=risks,
+        )
+
+    def check_injection_rules(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """Scan skill fields against every rule in the configured ruleset."""
+        return self._scan_rules(skill, self._compiled_rules)
+
     def check_no_executable_code(self, skill: JadeSkill) -> List[ValidationIssue]:
         """Ensure no executable code patterns exist in any string values."""
-        issues: List[ValidationIssue] = []
-        all_strings = self._extract_all_strings(skill)
-
-        for path, value in all_strings:
-            for pattern in self._compiled_exec_patterns:
-                match = pattern.search(value)
-                if match:
-                    issues.append(ValidationIssue(
-                        severity=ValidationSeverity.ERROR,
-                        code="SEC_EXEC_CODE",
-                        message=f"Executable code pattern detected: '{match.group()}'. "
-                                f"JADE skills must be non-Turing-complete.",
-                        path=path,
-                    ))
-        return issues
+        return self._scan_rules(skill, [
+            (r, c) for r, c in self._compiled_rules if r.code == "SEC_EXEC_CODE"
+        ])
 
     def check_dangerous_patterns(self, skill: JadeSkill) -> List[ValidationIssue]:
         """Check for dangerous system commands."""
-        issues: List[ValidationIssue] = []
-        all_strings = self._extract_all_strings(skill)
-
-        for path, value in all_strings:
-            for pattern in self._compiled_danger_patterns:
-                match = pattern.search(value)
-                if match:
-                    issues.append(ValidationIssue(
-                        severity=ValidationSeverity.ERROR,
-                        code="SEC_DANGEROUS_CMD",
-                        message=f"Dangerous system command detected: '{match.group()}'",
-                        path=path,
-                    ))
-        return issues
+        return self._scan_rules(skill, [
+            (r, c) for r, c in self._compiled_rules if r.code == "SEC_DANGEROUS_CMD"
+        ])
 
     def check_network_whitelist(self, skill: JadeSkill) -> List[ValidationIssue]:
         """Verify network access is properly whitelisted."""
@@ -185,50 +646,91 @@ class SecurityEngine:
         policy = skill.security
         whitelist = set(policy.network_whitelist)
 
-        # If strict sandbox, network_whitelist must not be wildcard
-        if policy.sandbox_level == SandboxLevel.STRICT and "*" in whitelist:
+        issues.extend(validate_whitelist_syntax(policy.network_whitelist))
+
+        capabilities = policy.sandbox_level.capabilities
+
+        # A wildcard is refused at runtime unless the sandbox allows it
+        if not capabilities.wildcard_network and "*" in whitelist:
             issues.append(ValidationIssue(
                 severity=ValidationSeverity.WARNING,
-                code="SEC_WILDCARD_NETWORK",
-                message="Wildcard '*' in network_whitelist with strict sandbox. "
-                        "Consider specifying exact domains.",
+                code=IssueCode.SEC_WILDCARD_NETWORK,
+                message="Wildcard '*' in network_whitelist with strict sandbox; the executor "
+                        "refuses it at runtime. Specify exact domains.",
                 path="security.network_whitelist",
             ))
 
-        # Check for suspicious domains in whitelist
-        for domain in whitelist:
+        # Check for SSRF-prone entries in whitelist
+        for domain in sorted(whitelist):
             if domain == "*":
                 continue
-            for pattern in self._compiled_network_patterns:
-                if pattern.search(domain):
-                    # In strict sandbox, suspicious networks are errors
-                    severity = (
-                        ValidationSeverity.ERROR
-                        if policy.sandbox_level == SandboxLevel.STRICT
-                        else ValidationSeverity.WARNING
-                    )
+            net_class = classify_whitelist_entry(domain)
+            if net_class is None:
+                continue
+            # Cloud metadata is never a legitimate target; public addresses
+            # and ranges only warn; other classes are errors only in strict
+            # sandbox
+            if net_class == NET_CLOUD_METADATA:
+                severity = ValidationSeverity.ERROR
+            elif net_class == NET_RAW_IP or policy.sandbox_level != SandboxLevel.STRICT:
+                severity = ValidationSeverity.WARNING
+            else:
+                severity = ValidationSeverity.ERROR
+            issues.append(ValidationIssue(
+                severity=severity,
+                code=IssueCode.SEC_SUSPICIOUS_NETWORK,
+                message=f"Suspicious domain in whitelist: '{domain}' "
+                        f"({_NET_DESCRIPTIONS[net_class]}).",
+                path="security.network_whitelist",
+            ))
+
+        # Strict sandbox is TLS-only; flag entries that say otherwise
+        if not capabilities.plain_http:
+            for i, entry in enumerate(policy.network_whitelist):
+                reason = cleartext_whitelist_reason(entry)
+                if reason:
+                    issues.append(ValidationIssue(
+                        severity=ValidationSeverity.WARNING,
+                        code=IssueCode.SEC_CLEARTEXT_WHITELIST,
+                        message=f"Whitelist entry '{entry}' {reason}; strict sandbox only makes "
+                                f"https:// requests",
+                        path=f"security.network_whitelist[{i}]",
+                    ))
+                elif self._rules.require_hsts_preload and entry != "*" and not is_hsts_preloaded(
+                    entry, self._rules.hsts_preloaded,
+                ):
                     issues.append(ValidationIssue(
-                        severity=severity,
-                        code="SEC_SUSPICIOUS_NETWORK",
-                        message=f"Suspicious domain in whitelist: '{domain}'. "
-                                f"Private IPs and special TLDs are flagged.",
-                        path="security.network_whitelist",
+                        severity=ValidationSeverity.ERROR,
+                        code=IssueCode.SEC_NOT_HSTS_PRELOADED,
+                        message=f"Whitelist entry '{entry}' is not under an HSTS-preloaded domain "
+                                f"({', '.join(self._rules.hsts_preloaded) or 'none configured'})",
+                        path=f"security.network_whitelist[{i}]",
                     ))
 
         # Check that all URLs in DAG nodes match the whitelist
-        if "*" not in whitelist:
-            for node in skill.execution_dag.nodes:
-                urls = self._extract_urls_from_params(node.params)
-                for url in urls:
-                    domain = self._extract_domain(url)
-                    if domain and not self._domain_matches_whitelist(domain, whitelist):
-                        issues.append(ValidationIssue(
-                            severity=ValidationSeverity.ERROR,
-                            code="SEC_UNLISTED_DOMAIN",
-                            message=f"Node '{node.id}' accesses domain '{domain}' "
-                                    f"not in network_whitelist",
-                            path=f"execution_dag.nodes.{node.id}.params",
-                        ))
+        for node in skill.execution_dag.nodes:
+            urls = self._extract_urls_from_params(node.params)
+            for url in urls:
+                # Strict sandbox implies TLS-only
+                if not capabilities.plain_http and url.lower().startswith("http://"):
+                    issues.append(ValidationIssue(
+                        severity=ValidationSeverity.ERROR,
+                        code=IssueCode.SEC_INSECURE_SCHEME,
+                        message=f"Node '{node.id}' uses plain http:// with strict sandbox: '{url}'",
+                        path=f"execution_dag.nodes.{node.id}.params",
+                    ))
+                if "*" in whitelist:
+                    continue
+                domain = self._extract_domain(url)
+                static_url = _URL_TEMPLATE_RE.sub('PLACEHOLDER', url)
+                if domain and not self._domain_matches_whitelist(static_url, whitelist):
+                    issues.append(ValidationIssue(
+                        severity=ValidationSeverity.ERROR,
+                        code=IssueCode.SEC_UNLISTED_DOMAIN,
+                        message=f"Node '{node.id}' accesses domain '{domain}' "
+                                f"not in network_whitelist",
+                        path=f"execution_dag.nodes.{node.id}.params",
+                    ))
 
         return issues
 
@@ -242,7 +744,7 @@ class SecurityEngine:
             if path in ("/", "/*", "C:\\", "C:\\*"):
                 issues.append(ValidationIssue(
                     severity=ValidationSeverity.ERROR,
-                    code="SEC_ROOT_FILE_ACCESS",
+                    code=IssueCode.SEC_ROOT_FILE_ACCESS,
                     message=f"Root-level file access is forbidden: '{path}'",
                     path="security.file_permissions",
                 ))
@@ -257,7 +759,7 @@ class SecurityEngine:
                 if sensitive in perm_path:
                     issues.append(ValidationIssue(
                         severity=ValidationSeverity.ERROR,
-                        code="SEC_SENSITIVE_PATH",
+                        code=IssueCode.SEC_SENSITIVE_PATH,
                         message=f"Access to sensitive path forbidden: '{perm_path}'",
                         path="security.file_permissions",
                     ))
@@ -270,15 +772,13 @@ class SecurityEngine:
         if not self._allowed_actions:
             return issues  # No action list loaded, skip check
 
-        for node in skill.execution_dag.nodes:
-            if node.action not in self._allowed_actions:
-                issues.append(ValidationIssue(
-                    severity=ValidationSeverity.ERROR,
-                    code="SEC_UNKNOWN_ACTION",
-                    message=f"Action '{node.action}' is not in the allowed atomic actions list. "
-                            f"Node: '{node.id}'",
-                    path=f"execution_dag.nodes.{node.id}.action",
-                ))
+        for node_id, message in unknown_actions(skill, self._allowed_actions):
+            issues.append(ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.SEC_UNKNOWN_ACTION,
+                message=message,
+                path=f"execution_dag.nodes.{node_id}.action",
+            ))
 
         return issues
 
@@ -287,12 +787,23 @@ class SecurityEngine:
         issues: List[ValidationIssue] = []
         policy = skill.security
 
+        if not policy.sandbox_level.capabilities.shell_actions:
+            for node in skill.execution_dag.nodes:
+                if node.action in SHELL_ACTIONS:
+                    issues.append(ValidationIssue(
+                        severity=ValidationSeverity.ERROR,
+                        code=IssueCode.SEC_SANDBOX_ACTION,
+                        message=f"Node '{node.id}' uses shell action '{node.action}', which "
+                                f"'{policy.sandbox_level.value}' sandbox does not allow",
+                        path=f"execution_dag.nodes.{node.id}.action",
+                    ))
+
         if policy.sandbox_level == SandboxLevel.STRICT:
             # Strict: max execution time <= 120s
             if policy.max_execution_time_ms > 120000:
                 issues.append(ValidationIssue(
                     severity=ValidationSeverity.ERROR,
-                    code="SEC_TIMEOUT_EXCEEDED",
+                    code=IssueCode.SEC_TIMEOUT_EXCEEDED,
                     message=f"Strict sandbox: max_execution_time_ms ({policy.max_execution_time_ms}) "
                             f"exceeds 120000ms limit",
                     path="security.max_execution_time_ms",
@@ -301,13 +812,51 @@ class SecurityEngine:
             if policy.max_retries > 10:
                 issues.append(ValidationIssue(
                     severity=ValidationSeverity.ERROR,
-                    code="SEC_RETRIES_EXCEEDED",
+                    code=IssueCode.SEC_RETRIES_EXCEEDED,
                     message=f"Strict sandbox: max_retries ({policy.max_retries}) exceeds 10 limit",
                     path="security.max_retries",
                 ))
 
         return issues
 
+    def check_time_budget(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """
+        Flag node timeouts that cannot fit in security.max_execution_time_ms.
+
+        The worst case is the critical path weighted by the timeout the
+        executor enforces on each node (dag.node_timeout_ms: its timeout_ms,
+        else a timeout_ms param); nodes with neither are not counted, so
+        this only fires when the declared timeouts alone exceed the budget.
+        An error in a strict sandbox, a warning otherwise. Skipped for a
+        budget of 0 (unset), and for malformed or negative timeouts, cycles
+        or edges to undeclared nodes, which Layers 1-2 report.
+        """
+        budget = skill.security.max_execution_time_ms
+        dag = skill.execution_dag
+        if not _is_count(budget) or budget == 0:
+            return []
+        if not all(n.timeout_ms is None or _is_count(n.timeout_ms) for n in dag.nodes):
+            return []
+        if any(node_timeout_ms(n, 0) < 0 for n in dag.nodes):
+            return []
+        node_ids = {n.id for n in dag.nodes}
+        if any(e.from_node not in node_ids or e.to_node not in node_ids for e in dag.edges):
+            return []
+        try:
+            path, total = DAGAnalyzer().get_critical_path(dag, default_timeout_ms=0)
+        except CycleError:
+            return []
+        if total <= budget:
+            return []
+        strict = skill.security.sandbox_level == SandboxLevel.STRICT
+        return [ValidationIssue(
+            severity=ValidationSeverity.ERROR if strict else ValidationSeverity.WARNING,
+            code=IssueCode.SEC_TIME_BUDGET_EXCEEDED,
+            message=f"Node timeouts along {' -> '.join(path)} add up to {total}ms, more than "
+                    f"max_execution_time_ms ({budget}); the skill cannot finish within its budget",
+            path="security.max_execution_time_ms",
+        )]
+
     def check_data_exfiltration(self, skill: JadeSkill) -> List[ValidationIssue]:
         """Check for potential data exfiltration patterns in all string values."""
         issues: List[ValidationIssue] = []
@@ -319,7 +868,7 @@ class SecurityEngine:
                 if match:
                     issues.append(ValidationIssue(
                         severity=ValidationSeverity.WARNING,
-                        code="SEC_DATA_EXFIL_RISK",
+                        code=IssueCode.SEC_DATA_EXFIL_RISK,
                         message=f"Potential data exfiltration: "
                                 f"sensitive pattern '{match.group()}' found",
                         path=path,
@@ -327,15 +876,201 @@ class SecurityEngine:
 
         return issues
 
+    def check_env_exfiltration(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """
+        Flag secret-like env vars whitelisted alongside external egress.
+
+        Each is fine on its own; together they are the classic exfiltration
+        setup. Egress is '*' or any public whitelist entry not listed in
+        SecurityRules.trusted_egress. Internal and SSRF-prone entries are
+        reported by check_network_whitelist instead.
+        """
+        policy = skill.security
+        secrets = [
+            name for name in policy.env_whitelist
+            if any(p.search(name) for p in self._compiled_env_patterns)
+        ]
+        if not secrets:
+            return []
+        trusted = set(self._rules.trusted_egress)
+        egress = [
+            entry for entry in policy.network_whitelist
+            if entry == "*" or (classify_whitelist_entry(entry) in (None, NET_RAW_IP)
+                                and not domain_matches_whitelist(entry, trusted))
+        ]
+        if not egress:
+            return []
+        severity = (
+            ValidationSeverity.ERROR if policy.sandbox_level == SandboxLevel.STRICT
+            else ValidationSeverity.WARNING
+        )
+        return [
+            ValidationIssue(
+                severity=severity,
+                code=IssueCode.SEC_ENV_EXFIL_RISK,
+                message=f"Secret-like env var '{name}' is readable while network_whitelist "
+                        f"allows egress to {', '.join(repr(e) for e in egress)}",
+                path="security.env_whitelist",
+            )
+            for name in secrets
+        ]
+
+    def check_env_references(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """
+        Match env_whitelist against the variables nodes actually read.
+
+        A node reads a variable through a `{{env.NAME}}` template in its
+        params or an env_read action's literal `name` param. Reading one
+        that is not whitelisted is an error (it resolves to nothing at run
+        time); whitelisting one nothing reads is a least-privilege warning.
+        """
+        issues: List[ValidationIssue] = []
+        whitelist = set(skill.security.env_whitelist)
+        used: Set[str] = set()
+        for node in skill.execution_dag.nodes:
+            names: List[Optional[str]] = [
+                segments[1] if len(segments) > 1 and isinstance(segments[1], str) else None
+                for _, segments in template_references(node.params) if segments[0] == ENV_ROOT
+            ]
+            if node.action == ENV_READ_ACTION and isinstance(node.params.get("name"), str):
+                if TEMPLATE_PATTERN.search(node.params["name"]) is None:
+                    names.append(node.params["name"])
+            for name in dict.fromkeys(names):
+                if name is not None:
+                    used.add(name)
+                if name is None or name not in whitelist:
+                    what = f"env var '{name}'" if name is not None else "the environment without naming a variable"
+                    issues.append(ValidationIssue(
+                        severity=ValidationSeverity.ERROR,
+                        code=IssueCode.SEC_ENV_NOT_WHITELISTED,
+                        message=f"Node '{node.id}' reads {what}, which is not in security.env_whitelist",
+                        path=f"execution_dag.nodes.{node.id}.params",
+                    ))
+        for i, name in enumerate(skill.security.env_whitelist):
+            if name not in used:
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.WARNING,
+                    code=IssueCode.SEC_ENV_UNUSED,
+                    message=f"env_whitelist grants '{name}', but no node reads it",
+                    path=f"security.env_whitelist[{i}]",
+                ))
+        return issues
+
+    def check_template_functions(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """Reject template function calls outside SecurityRules.template_functions."""
+        issues: List[ValidationIssue] = []
+        allowed = self._rules.template_functions
+        for node in skill.execution_dag.nodes:
+            for template, name in dict.fromkeys(template_functions(node.params)):
+                if name not in allowed:
+                    issues.append(ValidationIssue(
+                        severity=ValidationSeverity.ERROR,
+                        code=IssueCode.TEMPLATE_FORBIDDEN_FN,
+                        message=f"Node '{node.id}' calls template function '{name}' in '{template}'; "
+                                f"allowed: {', '.join(sorted(allowed))}",
+                        path=f"execution_dag.nodes.{node.id}.params",
+                    ))
+        return issues
+
+    def check_encoded_payloads(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """
+        Decode base64/hex runs in node params and rescan them for injection.
+
+        Runs regardless of SecurityRules.decode_encoded when called directly;
+        check_all only calls it when decode_encoded is set.
+        """
+        issues: List[ValidationIssue] = []
+        compiled = [(r, c) for r, c in self._compiled_rules if FIELD_PARAMS in r.applies_to_fields]
+        for i, node in enumerate(skill.execution_dag.nodes):
+            params: List[tuple] = []
+            self._walk_dict(node.params, f"execution_dag.nodes[{i}].params", params)
+            for path, value in params:
+                for encoding, decoded in self._decode_layers(value, self._rules.max_decode_depth):
+                    text = normalize_for_scan(decoded)
+                    for rule, pattern in compiled:
+                        match = pattern.search(text)
+                        if match:
+                            issues.append(ValidationIssue(
+                                severity=rule.severity,
+                                code=IssueCode.SEC_ENCODED_INJECTION,
+                                message=f"Node '{node.id}' has a {encoding}-encoded payload: "
+                                        f"{rule.message.lower()}: '{match.group()}'",
+                                path=path,
+                            ))
+        return issues
+
+    def _decode_layers(self, value: str, depth: int) -> List[Tuple[str, str]]:
+        """(encoding, text) for every decodable run in value, nested up to depth."""
+        found: List[Tuple[str, str]] = []
+        if depth <= 0:
+            return found
+        limit = self._rules.max_decoded_bytes * 2  # Hex is the least dense encoding
+        for encoding, regex in _ENCODED_RUNS:
+            for run in regex.findall(value):
+                if len(run) > limit:
+                    continue
+                text = _decode_run(encoding, run)
+                if text is None or len(text) > self._rules.max_decoded_bytes:
+                    continue
+                found.append((encoding, text))
+                found.extend((f"{encoding}+{inner}", t) for inner, t in self._decode_layers(text, depth - 1))
+        return found
+
     # ---- Internal helpers ----
 
-    def _extract_all_strings(self, skill: JadeSkill) -> List[tuple]:
-        """Recursively extract all string values from the skill with their paths."""
-        results: List[tuple] = []
+    def _scan_rules(
+        self,
+        skill: JadeSkill,
+        compiled: List[Tuple[InjectionRule, Pattern[str]]],
+    ) -> List[ValidationIssue]:
+        issues: List[ValidationIssue] = []
+        fields = [
+            (scope, path, normalize_for_scan(value))
+            for scope, path, value in self._extract_scoped_strings(skill)
+        ]
+
+        for rule, pattern in compiled:
+            for scope, path, value in fields:
+                match = pattern.search(value)
+                if not match:
+                    continue
+                if scope in rule.applies_to_fields:
+                    issues.append(ValidationIssue(
+                        severity=rule.severity,
+                        code=rule.code,
+                        message=f"{rule.message}: '{match.group()}'",
+                        path=path,
+                    ))
+                elif scope == FIELD_METADATA:
+                    # Non-executable text: informational only
+                    issues.append(ValidationIssue(
+                        severity=ValidationSeverity.INFO,
+                        code=IssueCode.SEC_CODE_LIKE_TEXT,
+                        message=f"Code-like text '{match.group()}' in non-executable field",
+                        path=path,
+                    ))
+        return issues
+
+    def _extract_scoped_strings(self, skill: JadeSkill) -> List[Tuple[str, str, str]]:
+        """Extract (scope, path, value) for every string a rule may apply to."""
+        results: List[Tuple[str, str, str]] = []
         for i, node in enumerate(skill.execution_dag.nodes):
-            self._walk_dict(node.params, f"execution_dag.nodes[{i}].params", results)
+            results.append((FIELD_ACTION, f"execution_dag.nodes[{i}].action", node.action))
+            params: List[tuple] = []
+            self._walk_dict(node.params, f"execution_dag.nodes[{i}].params", params)
+            results.extend((FIELD_PARAMS, path, value) for path, value in params)
+        # Human-readable fields that are never executed
+        results.append((FIELD_METADATA, "metadata.name", skill.metadata.name))
+        results.append((FIELD_METADATA, "metadata.description", skill.metadata.description))
         return results
 
+    def _extract_all_strings(self, skill: JadeSkill) -> List[tuple]:
+        """Extract every string that feeds execution (node actions and params) with its path."""
+        return [
+            (path, value) for scope, path, value in self._extract_scoped_strings(skill)
+            if scope in EXECUTABLE_FIELDS
+        ]
+
     def _walk_dict(self, obj: Any, path: str, results: List[tuple]) -> None:
         if isinstance(obj, str):
             results.append((path, obj))
@@ -373,7 +1108,7 @@ class SecurityEngine:
             # Try to extract static domain parts
             # e.g., "https://wttr.in/{{input.location}}" -> "wttr.in"
             try:
-                clean = re.sub(r'\{\{[^}]+\}\}', 'PLACEHOLDER', url)
+                clean = _URL_TEMPLATE_RE.sub('PLACEHOLDER', url)
                 parsed = urlparse(clean)
                 return parsed.hostname or ""
             except Exception:
@@ -384,14 +1119,398 @@ class SecurityEngine:
         except Exception:
             return ""
 
-    def _domain_matches_whitelist(self, domain: str, whitelist: Set[str]) -> bool:
-        """Check if a domain matches any entry in the whitelist."""
-        for allowed in whitelist:
-            if allowed == "*":
-                return True
-            if domain == allowed:
-                return True
-            # Support wildcard subdomains: *.example.com
-            if allowed.startswith("*.") and domain.endswith(allowed[1:]):
+    def _domain_matches_whitelist(self, target: str, whitelist: Set[str]) -> bool:
+        """Check if a host or URL matches any entry in the whitelist."""
+        return domain_matches_whitelist(target, whitelist)
+
+
+# Ports implied by a URL scheme, for whitelist entries that pin a port
+_DEFAULT_PORTS = {"http": 80, "https": 443}
+
+
+def split_host_port(value: str, default_port: bool = True) -> Tuple[str, Optional[int]]:
+    """
+    Normalize a host, host:port or URL to (host, port).
+
+    The host is lowercased without a trailing dot, and an IP address is in
+    its compressed form ('2001:db8::1'). The port is the explicit
+    one, else the scheme's default when `default_port` is set, else None.
+    An unparseable port (e.g. a template) counts as absent.
+    """
+    text = value.strip()
+    if _is_ip(text):  # A bare IPv6 literal's colons would parse as a port
+        return str(ipaddress.ip_address(text)), None
+    parsed = urlparse(text if "://" in text else "//" + text)
+    host = (parsed.hostname or "").rstrip(".")
+    if _is_ip(host):
+        host = str(ipaddress.ip_address(host))  # One spelling per IPv6 address
+    try:
+        port = parsed.port
+    except ValueError:
+        port = None
+    if port is None and default_port:
+        port = _DEFAULT_PORTS.get(parsed.scheme.lower())
+    return host, port
+
+
+def domain_matches_whitelist(target: str, whitelist: Iterable[str]) -> bool:
+    """
+    Check if a host, host:port or URL matches any entry in the whitelist.
+
+    Only the host and port are compared; scheme and path are ignored. An
+    entry such as 'api.example.com:8080' also requires that port (a URL
+    without one uses its scheme's default), and '*.example.com' matches any
+    subdomain of example.com but not example.com itself. A CIDR entry
+    ('10.0.0.0/8', '2001:db8::/32') matches the IP addresses in its range,
+    on any port; IPv4-mapped IPv6 targets ('::ffff:10.1.2.3') count as the
+    IPv4 address.
+    """
+    host, port = split_host_port(target)
+    if not host:
+        return False
+    address = _plain_address(host)
+    for allowed in whitelist:
+        if allowed == "*":
+            return True
+        network = whitelist_network(allowed)
+        if network is not None:
+            if address is not None and address.version == network.version and address in network:
                 return True
+            continue
+        allowed_host, allowed_port = split_host_port(allowed, default_port=False)
+        if allowed_port is not None and allowed_port != port:
+            continue
+        if host == allowed_host or (address is not None and address == _plain_address(allowed_host)):
+            return True
+        # Support wildcard subdomains: *.example.com
+        if allowed_host.startswith("*.") and host.endswith(allowed_host[1:]):
+            return True
+    return False
+
+
+IPAddress = Union[ipaddress.IPv4Address, ipaddress.IPv6Address]
+IPNetwork = Union[ipaddress.IPv4Network, ipaddress.IPv6Network]
+
+
+def whitelist_network(entry: str) -> Optional[IPNetwork]:
+    """The IP range of a CIDR whitelist entry, or None for any other entry."""
+    text = entry.strip()
+    if "/" not in text or "://" in text:
+        return None
+    try:
+        return ipaddress.ip_network(text, strict=False)
+    except ValueError:
+        return None
+
+
+def _plain_address(host: str) -> Optional[IPAddress]:
+    """`host` as an IP address, unwrapping IPv4-mapped IPv6; None for a hostname."""
+    try:
+        address = ipaddress.ip_address(host)
+    except ValueError:
+        return None
+    if address.version == 6 and address.ipv4_mapped is not None:  # type: ignore[union-attr]
+        return address.ipv4_mapped  # type: ignore[union-attr]
+    return address
+
+
+_HOSTNAME_LABEL_RE = re.compile(r"^(?!-)[a-z0-9-]{1,63}(?<!-)$")
+
+
+def whitelist_syntax_error(entry: str) -> Optional[str]:
+    """
+    Explain why a network_whitelist entry is malformed, or None if it is valid.
+
+    Valid entries are '*', a hostname, an IP address or CIDR range, or a
+    wildcard '*.example.com' whose '*' is the whole first label and covers
+    at least two more. Any entry may carry a port, and a scheme or path as
+    domain_matches_whitelist ignores them.
+    """
+    text = entry.strip().lower()
+    if text == "*":
+        return None
+    if not text:
+        return "empty entry"
+    if "://" in text:
+        scheme, _, rest = text.partition("://")
+        if not rest.split("/", 1)[0]:
+            return f"scheme '{scheme}://' without a host"
+        text = rest.split("/", 1)[0]
+    if "/" in text:
+        try:
+            ipaddress.ip_network(text, strict=False)
+            return None
+        except ValueError:
+            return "not a valid CIDR range"
+
+    host, port = text, None
+    if text.startswith("["):
+        end = text.find("]")
+        if end < 0:
+            return "unclosed '[' in IPv6 address"
+        host, rest = text[1:end], text[end + 1:]
+        if rest and not rest.startswith(":"):
+            return f"unexpected text after IPv6 address: '{rest}'"
+        port = rest[1:] if rest else None
+    elif text.count(":") == 1:
+        host, port = text.split(":")
+    if port is not None and not (port.isdigit() and 1 <= int(port) <= 65535):
+        return f"invalid port '{port}'"
+
+    if _is_ip(host):
+        return None
+    if text.startswith("["):
+        return "not a valid IPv6 address"
+    return _hostname_syntax_error(host)
+
+
+def _hostname_syntax_error(host: str) -> Optional[str]:
+    name = host[:-1] if host.endswith(".") else host
+    labels = name.split(".")
+    if labels[0] == "*":
+        labels = labels[1:]
+        if len(labels) < 2:
+            return "wildcard must cover at least a domain and TLD, e.g. '*.example.com'"
+    if "*" in name[1:] or (name.startswith("*") and not name.startswith("*.")):
+        return "'*' is only allowed as the whole first label"
+    if len(name) > 253:
+        return "hostname longer than 253 characters"
+    for label in labels:
+        if not label:
+            return "empty label (consecutive or leading dots)"
+        if not _HOSTNAME_LABEL_RE.match(label):
+            return f"invalid hostname label '{label}'"
+    return None
+
+
+def cleartext_whitelist_reason(entry: str) -> Optional[str]:
+    """
+    Why a network_whitelist entry implies cleartext traffic, or None: it
+    names a scheme other than https:// or wss://, or pins port 80.
+    """
+    text = entry.strip().lower()
+    if "://" in text:
+        scheme = text.split("://", 1)[0]
+        if scheme not in ("https", "wss"):
+            return f"names the cleartext scheme '{scheme}://'"
+        return None
+    if split_host_port(text, default_port=False)[1] == 80:
+        return "pins port 80, which serves plain http"
+    return None
+
+
+def is_hsts_preloaded(entry: str, preloaded: Iterable[str]) -> bool:
+    """
+    Whether every host a whitelist entry matches is under a domain in
+    `preloaded` (the domain itself or a subdomain). IP addresses never are.
+    """
+    host = split_host_port(entry, default_port=False)[0]
+    if host.startswith("*."):
+        host = host[2:]
+    if not host or _is_ip(host.strip("[]")):
         return False
+    domains = {d.strip().lower().strip(".") for d in preloaded}
+    labels = host.split(".")
+    return any(".".join(labels[i:]) in domains for i in range(len(labels)))
+
+
+def validate_whitelist_syntax(whitelist: Iterable[str]) -> List[ValidationIssue]:
+    """Warn about network_whitelist entries that could never match as intended."""
+    issues: List[ValidationIssue] = []
+    for i, entry in enumerate(whitelist):
+        reason = whitelist_syntax_error(entry)
+        if reason:
+            issues.append(ValidationIssue(
+                severity=ValidationSeverity.WARNING,
+                code=IssueCode.SEC_MALFORMED_WHITELIST,
+                message=f"Malformed network_whitelist entry '{entry}': {reason}",
+                path=f"security.network_whitelist[{i}]",
+            ))
+    return issues
+
+
+# ─── Node params vs. per-action param schemas ───────────────────────
+
+# action -> param name -> {"type", "required", "enum", ...}, as in
+# allowed_atomic_actions.json
+ActionSchemas = Dict[str, Dict[str, Dict[str, Any]]]
+
+# A value that is a single template is only typed once it is resolved
+_TEMPLATE_VALUE_RE = re.compile(r"^\s*(?:\{\{[^{}]*\}\}|\$\{[^{}]*\})\s*$")
+
+
+def action_schemas_from_catalog(catalog: Dict[str, Any]) -> ActionSchemas:
+    """Collect each action's param specs from an allowed_atomic_actions.json document."""
+    schemas: ActionSchemas = {}
+    for category in catalog.get("categories", {}).values():
+        for action, spec in category.get("actions", {}).items():
+            params = spec.get("params")
+            if isinstance(params, dict):
+                schemas[action] = {k: v for k, v in params.items() if isinstance(v, dict)}
+    return schemas
+
+
+def _json_type(value: Any) -> str:
+    if value is None:
+        return "null"
+    if isinstance(value, bool):
+        return "boolean"
+    if isinstance(value, (int, float)):
+        return "number"
+    if isinstance(value, str):
+        return "string"
+    if isinstance(value, list):
+        return "array"
+    return "object"
+
+
+def unknown_actions(skill: JadeSkill, allowed: Iterable[str]) -> List[Tuple[str, str]]:
+    """
+    Find nodes whose action is not in the allowed set.
+
+    Returns (node_id, message) pairs, suggesting the closest allowed action
+    for a likely typo. An empty action is not checked: it marks a
+    pass-through node, which the DAG layer warns about when it has outgoing
+    edges and Layer 1 warns about when it has params.
+    """
+    names = sorted(set(allowed))
+    problems: List[Tuple[str, str]] = []
+    for node in skill.execution_dag.nodes:
+        if node.action and node.action not in names:
+            close = difflib.get_close_matches(node.action, names, n=1)
+            hint = f" Did you mean '{close[0]}'?" if close else ""
+            problems.append((node.id, f"Action '{node.action}' is not in the allowed atomic actions list. "
+                                      f"Node: '{node.id}'.{hint}"))
+    return problems
+
+
+def validate_node_params(skill: JadeSkill, action_schemas: ActionSchemas) -> List[Tuple[str, str]]:
+    """
+    Check node params against their action's param schema.
+
+    Returns (node_id, message) pairs for missing required params, type
+    mismatches and values outside an enum. Actions without a schema and
+    params the schema doesn't mention are not checked.
+    """
+    problems: List[Tuple[str, str]] = []
+    for node in skill.execution_dag.nodes:
+        schema = action_schemas.get(node.action)
+        if schema is None:
+            continue
+        for name, spec in schema.items():
+            if spec.get("required") and name not in node.params:
+                problems.append((node.id, f"Node '{node.id}' ({node.action}) is missing "
+                                          f"required param '{name}'"))
+        for name, value in node.params.items():
+            spec = schema.get(name)
+            if spec is None or (isinstance(value, str) and _TEMPLATE_VALUE_RE.match(value)):
+                continue
+            expected = spec.get("type")
+            actual = _json_type(value)
+            if expected and actual != expected:
+                problems.append((node.id, f"Node '{node.id}' ({node.action}) param '{name}' "
+                                          f"must be {expected}, got {actual}"))
+            elif "enum" in spec and value not in spec["enum"]:
+                allowed = ", ".join(str(v) for v in spec["enum"])
+                problems.append((node.id, f"Node '{node.id}' ({node.action}) param '{name}' "
+                                          f"must be one of: {allowed}"))
+    return problems
+
+
+# ─── output_schema vs. sink node outputs ─────────────────────────────
+
+# action -> returned field -> type ("string", "number", ..., or "any"), from
+# the "returns" blocks of allowed_atomic_actions.json
+ActionOutputs = Dict[str, Dict[str, str]]
+
+# JSON Schema types that a returned type satisfies
+_COMPATIBLE_TYPES = {"number": {"number", "integer"}}
+
+
+def action_outputs_from_catalog(catalog: Dict[str, Any]) -> ActionOutputs:
+    """
+    Collect each action's output shape from an allowed_atomic_actions.json
+    document. Actions with an empty "returns" (return_result, whose output
+    is whatever its params say) have no known shape and are left out.
+    """
+    outputs: ActionOutputs = {}
+    for category in catalog.get("categories", {}).values():
+        for action, spec in category.get("actions", {}).items():
+            returns = spec.get("returns")
+            if isinstance(returns, dict) and returns:
+                outputs[action] = {k: v for k, v in returns.items() if isinstance(v, str)}
+    return outputs
+
+
+def validate_output_consistency(skill: JadeSkill, action_outputs: ActionOutputs) -> List[Tuple[str, str]]:
+    """
+    Check output_schema against what the DAG's exit nodes return.
+
+    Every action output is an object of named fields, so an output_schema
+    whose type is not 'object' cannot match an exit node with a known
+    output, and a declared field that the node returns with another type is
+    a mismatch. Returns (path, message) pairs; exit nodes whose action has
+    no known output shape, return_error exits, and exit ids shared by
+    several nodes (a DAG error) are not checked.
+    """
+    declared = _declared_output(skill.output_schema)
+    if declared is None:
+        return []
+    schema_type, fields = declared
+    problems: List[Tuple[str, str]] = []
+    nodes: Dict[str, Optional[DAGNode]] = {}
+    for n in skill.execution_dag.nodes:
+        nodes[n.id] = None if n.id in nodes else n
+    for exit_id in _unique_ids(skill.execution_dag.exit_node):
+        node = nodes.get(exit_id)
+        returns = action_outputs.get(node.action) if node is not None else None
+        if not returns or node.action == "return_error":
+            continue
+        source = f"exit node '{node.id}' ({node.action})"
+        if schema_type != "object":
+            problems.append(("output_schema.type", f"output_schema declares type '{schema_type}' but "
+                                                   f"{source} returns an object ({', '.join(returns)})"))
+            continue
+        for name, (path, field_type) in fields.items():
+            returned = returns.get(name)
+            if returned is None or returned == "any" or not isinstance(field_type, str):
+                continue
+            if field_type != returned and field_type not in _COMPATIBLE_TYPES.get(returned, ()):
+                problems.append((path, f"output_schema field '{name}' is '{field_type}' but "
+                                       f"{source} returns '{returned}'"))
+    return problems
+
+
+def _declared_output(schema: Any) -> Optional[Tuple[str, Dict[str, Tuple[str, Any]]]]:
+    """(type, {field: (path, type)}) of an output_schema in either accepted form, or None."""
+    if not isinstance(schema, dict):
+        return None
+    if "type" in schema or "properties" in schema:
+        properties = schema.get("properties")
+        fields = {
+            name: (f"output_schema.properties.{name}.type", spec.get("type"))
+            for name, spec in (properties.items() if isinstance(properties, dict) else ())
+            if isinstance(spec, dict)
+        }
+        schema_type = schema.get("type", "object")
+        return (schema_type, fields) if isinstance(schema_type, str) else None
+    entries = schema.get("fields")
+    if isinstance(entries, list):
+        return "object", {
+            entry["name"]: (f"output_schema.fields[{i}].type", entry.get("type"))
+            for i, entry in enumerate(entries)
+            if isinstance(entry, dict) and isinstance(entry.get("name"), str)
+        }
+    return None
+
+
+def _unique_ids(ids: Iterable[Any]) -> List[str]:
+    seen: List[str] = []
+    for node_id in ids:
+        if isinstance(node_id, str) and node_id not in seen:
+            seen.append(node_id)
+    return seen
+
+
+def _is_count(value: Any) -> bool:
+    return isinstance(value, int) and not isinstance(value, bool) and value >= 0
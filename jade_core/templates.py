@@ -0,0 +1,300 @@
+"""
+Project JADE - Param Templates
+Interpolation of `{{ reference }}` templates in node params.
+
+A reference names either a skill input or an upstream node's output:
+
+    {{input.city}}                 the 'city' input
+    {{fetch.output.body}}          the 'body' field of node 'fetch'
+    {{parse.data.items[0]}}        '.output' is optional, as in conditions
+    {{env.API_BASE}}               environment variable API_BASE
+
+Environment variables are only visible if listed in security.env_whitelist;
+the security layer rejects references to any other variable and warns
+about whitelisted variables nothing references.
+
+A string that is exactly one template is replaced by the referenced value
+itself, keeping its type; templates embedded in longer text are replaced
+by the value's text (JSON for objects and lists).
+
+A reference may be followed by a chain of functions, applied left to
+right: `{{input.city | default:'Paris' | upper}}`. Arguments follow the
+name, separated by colons: `{{input.text | slice:0:5}}`. Only the pure
+functions in TEMPLATE_FUNCTIONS exist; `default:<literal>` supplies a
+value when the reference cannot be resolved. The security layer rejects
+any other function (TEMPLATE_FORBIDDEN_FN), so params cannot smuggle in
+logic.
+
+Templates are resolved by the executor just before a node runs. The DAG
+layer checks statically that every node a template references is an
+ancestor of the node using it, since anything else can never have run.
+"""
+
+from __future__ import annotations
+
+import json
+import re
+from typing import Any, Callable, Dict, List, Optional, Tuple
+from urllib.parse import urlparse
+
+from .errors import JadeError
+
+# Roots of references to skill inputs and environment variables rather than node outputs
+INPUT_ROOT = "input"
+ENV_ROOT = "env"
+# Reference roots no node id may take: '{{input.x}}' and '{{env.X}}' would
+# never read such a node's output. Param keys are not a namespace and may
+# use any name.
+RESERVED_ROOTS = (INPUT_ROOT, ENV_ROOT)
+
+TEMPLATE_PATTERN = re.compile(r"\{\{\s*([^{}|]*?)\s*(?:\|\s*([^{}]*?)\s*)?\}\}")
+_REFERENCE_RE = re.compile(r"^[A-Za-z_][A-Za-z0-9_]*(?:\.[A-Za-z_][A-Za-z0-9_]*|\[\d+\])*$")
+_SEGMENT_RE = re.compile(r"[A-Za-z_][A-Za-z0-9_]*|\[(\d+)\]")
+_FUNCTION_RE = re.compile(r"^([A-Za-z_][A-Za-z0-9_]*)\s*(?::\s*(.+))?$", re.DOTALL)
+_CALL_RE = re.compile(r"([A-Za-z_][A-Za-z0-9_.]*)\s*\(")
+
+# A template function maps a resolved value (and any literal arguments) to a new one
+TemplateFunction = Callable[..., Any]
+
+# The pure functions templates may call; 'default' is handled by the resolver
+TEMPLATE_FUNCTIONS = ("default", "domain", "json", "lat", "length", "lon", "lower", "path", "slice", "upper")
+
+# Deepest JSON nesting a literal argument (e.g. 'default:[[...]]') may have;
+# json recurses once per level
+MAX_LITERAL_DEPTH = 64
+_LITERAL_NESTING_RE = re.compile(r'"(?:[^"\\]|\\.)*"|[\[\]{}]')
+
+_MISSING = object()
+
+
+class TemplateError(JadeError):
+    """A template could not be resolved; `code` is the ExecutionError code to report."""
+
+    def __init__(self, code: str, message: str):
+        super().__init__(message)
+        self.code = code
+
+
+def parse_reference(text: str) -> Optional[List[Any]]:
+    """Split 'node.output.items[0]' into ['node', 'output', 'items', 0], or None if malformed."""
+    if not _REFERENCE_RE.match(text):
+        return None
+    return [int(m.group(1)) if m.group(1) else m.group() for m in _SEGMENT_RE.finditer(text)]
+
+
+def template_references(value: Any) -> List[Tuple[str, List[Any]]]:
+    """Every well-formed reference in a (nested) params value, as (text, segments)."""
+    found: List[Tuple[str, List[Any]]] = []
+    if isinstance(value, str):
+        for match in TEMPLATE_PATTERN.finditer(value):
+            segments = parse_reference(match.group(1))
+            if segments is not None:
+                found.append((match.group(1), segments))
+    elif isinstance(value, dict):
+        for item in value.values():
+            found.extend(template_references(item))
+    elif isinstance(value, list):
+        for item in value:
+            found.extend(template_references(item))
+    return found
+
+
+def template_functions(value: Any) -> List[Tuple[str, str]]:
+    """
+    Every function a (nested) params value's templates call, as (template,
+    name) pairs. Covers the function chain and call syntax such as
+    'eval(' in the reference; a chain entry that is not `name` or
+    `name:<literal>` is returned whole, so it never matches an allowlist.
+    """
+    found: List[Tuple[str, str]] = []
+    if isinstance(value, str):
+        for match in TEMPLATE_PATTERN.finditer(value):
+            found.extend((match.group(), name) for name in _CALL_RE.findall(match.group(1)))
+            for part in _split_chain(match.group(2) or ""):
+                function = _FUNCTION_RE.match(part)
+                found.append((match.group(), function.group(1) if function else part))
+    elif isinstance(value, dict):
+        for item in value.values():
+            found.extend(template_functions(item))
+    elif isinstance(value, list):
+        for item in value:
+            found.extend(template_functions(item))
+    return found
+
+
+def interpolate(
+    value: Any,
+    inputs: Dict[str, Any],
+    outputs: Dict[str, Any],
+    env: Optional[Dict[str, str]] = None,
+    functions: Optional[Dict[str, TemplateFunction]] = None,
+) -> Any:
+    """
+    Resolve every template in a (nested) params value. `env` holds the
+    environment variables `{{env.NAME}}` may read; None means none.
+    `functions` adds to (or replaces) the built-in template functions.
+
+    Raises TemplateError with code TEMPLATE_UNRESOLVED when a reference
+    has no value and no default or a function cannot apply to its value,
+    or TEMPLATE_UNSUPPORTED for an unknown function.
+    """
+    available = dict(BUILTIN_FUNCTIONS, **(functions or {}))
+    return _interpolate(value, inputs, outputs, env or {}, available)
+
+
+def _interpolate(
+    value: Any, inputs: Dict[str, Any], outputs: Dict[str, Any], env: Dict[str, str],
+    functions: Dict[str, TemplateFunction],
+) -> Any:
+    if isinstance(value, str):
+        return _interpolate_string(value, inputs, outputs, env, functions)
+    if isinstance(value, dict):
+        return {k: _interpolate(v, inputs, outputs, env, functions) for k, v in value.items()}
+    if isinstance(value, list):
+        return [_interpolate(v, inputs, outputs, env, functions) for v in value]
+    return value
+
+
+def _interpolate_string(
+    text: str, inputs: Dict[str, Any], outputs: Dict[str, Any], env: Dict[str, str],
+    functions: Dict[str, TemplateFunction],
+) -> Any:
+    whole = TEMPLATE_PATTERN.fullmatch(text.strip())
+    if whole:
+        return _resolve(whole, inputs, outputs, env, functions)
+    return TEMPLATE_PATTERN.sub(lambda m: _as_text(_resolve(m, inputs, outputs, env, functions)), text)
+
+
+def _resolve(
+    match: re.Match, inputs: Dict[str, Any], outputs: Dict[str, Any], env: Dict[str, str],
+    functions: Dict[str, TemplateFunction],
+) -> Any:
+    reference, chain = match.group(1), match.group(2)
+    segments = parse_reference(reference)
+    if segments is None:
+        raise TemplateError("TEMPLATE_UNRESOLVED", f"Malformed template reference '{match.group()}'")
+    value = _lookup(segments, inputs, outputs, env)
+    for part in _split_chain(chain or ""):
+        function = _FUNCTION_RE.match(part)
+        name, argument = (function.group(1), function.group(2)) if function else (part, None)
+        if name == "default" and argument is not None:
+            if value is _MISSING:
+                value = _literal(argument)
+            continue
+        if name not in functions:
+            raise TemplateError(
+                "TEMPLATE_UNSUPPORTED", f"Unsupported template function '{part}' in '{match.group()}'",
+            )
+        if value is _MISSING:
+            break
+        arguments = [_literal(a) for a in _split_chain(argument, ":")] if argument is not None else []
+        try:
+            value = functions[name](value, *arguments)
+        except Exception as exc:  # Registered functions may raise anything
+            raise TemplateError(
+                "TEMPLATE_UNRESOLVED", f"Template function '{name}' failed in '{match.group()}': {exc}",
+            ) from exc
+    if value is _MISSING:
+        raise TemplateError("TEMPLATE_UNRESOLVED", f"Template '{match.group()}' has no value")
+    return value
+
+
+def _split_chain(chain: str, separator: str = "|") -> List[str]:
+    """Split 'default:"a|b" | upper' on the separators outside quotes."""
+    parts: List[str] = []
+    current: List[str] = []
+    quote = ""
+    for ch in chain:
+        if quote:
+            quote = "" if ch == quote else quote
+        elif ch in "'\"":
+            quote = ch
+        elif ch == separator:
+            parts.append("".join(current).strip())
+            current = []
+            continue
+        current.append(ch)
+    if chain.strip():
+        parts.append("".join(current).strip())
+    return parts
+
+
+def _lookup(segments: List[Any], inputs: Dict[str, Any], outputs: Dict[str, Any], env: Dict[str, str]) -> Any:
+    """Resolve reference segments against inputs, env or node outputs; _MISSING if absent."""
+    root, rest = segments[0], list(segments[1:])
+    if root == INPUT_ROOT:
+        value: Any = inputs
+    elif root == ENV_ROOT:
+        value = env
+    elif root in outputs:
+        value = outputs[root]
+        # 'node.output.x' and 'node.x' both address the node's output
+        if rest and rest[0] == "output" and not (isinstance(value, dict) and "output" in value):
+            rest = rest[1:]
+    else:
+        return _MISSING
+    for segment in rest:
+        if isinstance(segment, int) and isinstance(value, list) and 0 <= segment < len(value):
+            value = value[segment]
+        elif isinstance(segment, str) and isinstance(value, dict) and segment in value:
+            value = value[segment]
+        else:
+            return _MISSING
+    return value
+
+
+def _literal(text: str) -> Any:
+    """A default's literal: quoted string, JSON scalar, or bare text."""
+    text = text.strip()
+    if len(text) >= 2 and text[0] == text[-1] and text[0] in "'\"":
+        return text[1:-1]
+    depth = deepest = 0
+    for token in _LITERAL_NESTING_RE.findall(text):
+        if token in "[{":
+            depth += 1
+            deepest = max(deepest, depth)
+        elif token in "]}":
+            depth -= 1
+    if deepest > MAX_LITERAL_DEPTH:
+        raise TemplateError(
+            "TEMPLATE_UNRESOLVED", f"Template literal nests deeper than {MAX_LITERAL_DEPTH} levels",
+        )
+    try:
+        return json.loads(text)
+    except ValueError:
+        return text
+
+
+def _as_text(value: Any) -> str:
+    if isinstance(value, str):
+        return value
+    return json.dumps(value, ensure_ascii=False)
+
+
+BUILTIN_FUNCTIONS: Dict[str, TemplateFunction] = {
+    "domain": lambda value: _url(value).hostname or "",
+    "json": lambda value: json.dumps(value, ensure_ascii=False),
+    "lat": lambda value: _coordinate(value, 0),
+    "length": len,
+    "lon": lambda value: _coordinate(value, 1),
+    "lower": lambda value: _as_text(value).lower(),
+    "path": lambda value: _url(value).path,
+    "slice": lambda value, start=None, stop=None: value[start:stop],
+    "upper": lambda value: _as_text(value).upper(),
+}
+
+
+def _url(value: Any):
+    """`value` parsed as an absolute URL."""
+    parts = urlparse(_as_text(value))
+    if not (parts.scheme and parts.netloc):
+        raise ValueError(f"expected a URL, got {value!r}")
+    return parts
+
+
+def _coordinate(value: Any, index: int) -> float:
+    """One half of a 'lat,lon' pair."""
+    parts = _as_text(value).split(",")
+    if len(parts) != 2:
+        raise ValueError(f"expected 'lat,lon', got {value!r}")
+    return float(parts[index])
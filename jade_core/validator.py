@@ -2,32 +2,435 @@
 Project JADE - Validator
 The "Supreme Court" of the JADE protocol.
 
-Validates JADE skill files against:
-1. JSON Schema compliance (structural correctness)
-2. Security policy enforcement (zero-trust)
-3. DAG structural integrity (acyclicity, reachability)
+Validates JADE skill files in five layers (see ValidationLayer):
+1. JSON Schema compliance (structural correctness, input/output schemas, node params)
+2. DAG structural integrity (acyclicity, reachability)
+3. Security policy enforcement (zero-trust)
 4. Semantic consistency (cross-field validation)
+5. Signature verification (Ed25519 over canonical JSON)
 """
 
 from __future__ import annotations
 
+import codecs
+import copy
+import difflib
 import hashlib
 import json
+import logging
 import os
 import re
 import time
+import unicodedata
+from dataclasses import dataclass, field
+from datetime import datetime, timedelta, timezone
 from pathlib import Path
-from typing import Any, Dict, List, Optional
+from typing import Any, BinaryIO, Callable, Dict, Iterable, Iterator, List, Optional, Set, Tuple
 
 from .models import (
+    CommunitySigResult,
+    CommunitySignature,
+    ExecutionDAG,
+    FreshnessPolicy,
+    IssueCode,
+    JadeSignature,
     JadeSkill,
+    SecurityPolicy,
+    SignatureRecord,
+    SignerRole,
+    SkillIdPolicy,
+    SkillMetadata,
+    ResourceLimits,
+    Suppression,
+    Trigger,
+    TriggerType,
+    TrustLevel,
+    TrustPolicy,
     ValidationIssue,
+    ValidationLayer,
     ValidationResult,
     ValidationSeverity,
     SandboxLevel,
 )
-from .security import SecurityEngine
+from .security import (
+    CAPABILITY_RISK_WEIGHTS,
+    ActionOutputs,
+    ActionSchemas,
+    SecurityEngine,
+    SecurityRules,
+    action_outputs_from_catalog,
+    action_schemas_from_catalog,
+    unknown_actions,
+    validate_node_params,
+    validate_output_consistency,
+)
 from .dag import DAGAnalyzer
+from .diff import diff_skills
+from .errors import JadeIOError, JadeParseError
+from .cache import ValidationCache
+from .events import emit
+from .layers import FIRST_CUSTOM_LAYER, CustomLayer
+from .locations import SourceMap
+from .templates import INPUT_ROOT, RESERVED_ROOTS
+from .crypto import (
+    DEFAULT_HASH_ALGORITHM,
+    IMPLEMENTED_ALGORITHMS,
+    KeyRegistry,
+    SignatureAlgorithm,
+    content_hash,
+    key_fingerprint,
+    key_role,
+    parse_content_hash,
+    signable_and_hash,
+    verify_signature,
+)
+
+logger = logging.getLogger("jade_core.validator")
+
+# Range of jade_version values this engine understands: [min, max)
+SUPPORTED_JADE_VERSION_RANGE = ("1.0.0", "1.1.0")
+
+SEMVER_PATTERN = re.compile(
+    r'^(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)'
+    r'(?:-((?:0|[1-9]\d*|\d*[A-Za-z-][0-9A-Za-z-]*)(?:\.(?:0|[1-9]\d*|\d*[A-Za-z-][0-9A-Za-z-]*))*))?'
+    r'(?:\+([0-9A-Za-z-]+(?:\.[0-9A-Za-z-]+)*))?$'
+)
+
+
+def parse_semver(version: str) -> Optional[Tuple[int, int, int, str]]:
+    """
+    Parse a semantic version (x.y.z with optional -pre-release and +build).
+
+    Returns (major, minor, patch, pre_release) or None if unparseable.
+    Build metadata is accepted but dropped, since it doesn't affect precedence.
+    """
+    match = SEMVER_PATTERN.match(version) if isinstance(version, str) else None
+    if not match:
+        return None
+    return int(match.group(1)), int(match.group(2)), int(match.group(3)), match.group(4) or ""
+
+
+def semver_precedence(version: Tuple[int, int, int, str]) -> Tuple[Any, ...]:
+    """
+    Sort key for a parse_semver result, following semver precedence.
+
+    A pre-release ranks below its release (1.1.0-rc.1 < 1.1.0). Pre-release
+    identifiers compare left to right: numeric ones numerically and below
+    alphanumeric ones, which compare in ASCII order; a shorter list of
+    otherwise equal identifiers ranks lower.
+    """
+    major, minor, patch, pre = version
+    if not pre:
+        return major, minor, patch, (1,)
+    ids = tuple((0, int(i), "") if i.isdigit() else (1, 0, i) for i in pre.split("."))
+    return major, minor, patch, (0, ids)
+
+
+RFC3339_PATTERN = re.compile(
+    r'^(\d{4})-(\d{2})-(\d{2})[Tt ](\d{2}):(\d{2}):(\d{2})(\.\d+)?([Zz]|[+-]\d{2}:\d{2})$'
+)
+
+
+def parse_rfc3339(value: str) -> Optional[float]:
+    """
+    Parse an RFC 3339 timestamp into a Unix timestamp.
+
+    A timezone offset ('Z' or +hh:mm) is required. Returns None if unparseable.
+    """
+    match = RFC3339_PATTERN.match(value) if isinstance(value, str) else None
+    if not match:
+        return None
+    year, month, day, hour, minute, second = (int(g) for g in match.groups()[:6])
+    fraction = float(match.group(7)) if match.group(7) else 0.0
+    offset = match.group(8)
+    if offset in ("Z", "z"):
+        tz = timezone.utc
+    else:
+        sign = -1 if offset[0] == "-" else 1
+        tz = timezone(sign * timedelta(hours=int(offset[1:3]), minutes=int(offset[4:6])))
+    try:
+        moment = datetime(year, month, day, hour, minute, min(second, 59), tzinfo=tz)
+    except ValueError:
+        return None
+    # Leap second 60 is folded into :59
+    return moment.timestamp() + fraction
+
+
+# Longest metadata tag accepted without a warning (schema maxLength)
+MAX_TAG_LENGTH = 32
+
+# Bounds on a node's retry policy (schema maximums)
+MAX_RETRY_ATTEMPTS = 5
+MAX_RETRY_BACKOFF_MS = 60000
+
+
+def normalize_tags(tags: Iterable[str]) -> List[str]:
+    """
+    Canonical form of metadata.tags: trimmed, lowercased, without empty
+    entries or case-insensitive duplicates, in first-seen order.
+
+    Tags are part of the signed content, so tooling should normalize
+    before signing rather than after.
+    """
+    return list(dict.fromkeys(t.strip().lower() for t in tags if t.strip()))
+
+
+# Invisible characters that reorder the text around them, so an identifier
+# can display as something other than what it is (e.g. U+202E, RTL override)
+BIDI_CONTROLS = frozenset(
+    "\u061c\u200e\u200f\u202a\u202b\u202c\u202d\u202e\u2066\u2067\u2068\u2069"
+)
+
+
+def identifier_problem(value: str) -> Optional[str]:
+    """
+    What makes `value` unfit as an identifier, or None: a control character
+    (NUL, newline, escape, ...) or a bidirectional formatting character.
+    """
+    for char in value:
+        if unicodedata.category(char) == "Cc":
+            return f"control character U+{ord(char):04X}"
+        if char in BIDI_CONTROLS:
+            return f"bidirectional formatting character U+{ord(char):04X}"
+    return None
+
+
+# Issue paths name a node either by position or by id:
+# 'execution_dag.nodes[2].params' or 'execution_dag.nodes.fetch.action'
+NODE_PATH_PATTERN = re.compile(r'^execution_dag\.nodes(?:\[(\d+)\]|\.([^.\[]+))')
+
+
+def skill_id_matches_path(skill_id: str, path: str) -> bool:
+    """
+    True if the file path ends with the skill_id: 'weather_api' matches
+    skills/weather_api.json, 'acme/weather' matches skills/acme/weather.json.
+    """
+    file = Path(path)
+    parts = list(file.with_suffix("").parts) if file.suffix == ".json" else list(file.parts)
+    segments = str(skill_id).split("/")
+    return parts[-len(segments):] == segments
+
+
+_JSON_NESTING_TOKEN_RE = re.compile(r'"(?:[^"\\]|\\.)*"|[\[\]{}]')
+
+
+def json_nesting_depth(text: str, stop_above: Optional[int] = None) -> int:
+    """
+    Deepest nesting of objects and arrays in JSON text, without parsing it.
+
+    Brackets inside strings are ignored. With stop_above, scanning ends as
+    soon as the depth exceeds it.
+    """
+    depth = deepest = 0
+    for match in _JSON_NESTING_TOKEN_RE.finditer(text):
+        token = match.group()
+        if token in "[{":
+            depth += 1
+            if depth > deepest:
+                deepest = depth
+                if stop_above is not None and deepest > stop_above:
+                    break
+        elif token in "]}":
+            depth -= 1
+    return deepest
+
+
+def _nesting_depth(value: Any, stop_above: int) -> int:
+    """Deepest nesting of dicts and lists in parsed data, iteratively."""
+    deepest = 0
+    stack: List[Tuple[Any, int]] = [(value, 1)]
+    while stack:
+        item, depth = stack.pop()
+        if isinstance(item, dict):
+            children: Iterable[Any] = item.values()
+        elif isinstance(item, list):
+            children = item
+        else:
+            continue
+        deepest = max(deepest, depth)
+        if deepest > stop_above:
+            break
+        stack.extend((child, depth + 1) for child in children)
+    return deepest
+
+
+def _is_int(value: Any) -> bool:
+    return isinstance(value, int) and not isinstance(value, bool)
+
+
+class _KeyCheckedDict(dict):
+    """A JSON object that remembers which of its keys appeared more than once."""
+
+    duplicates: List[str]
+
+    @classmethod
+    def from_pairs(cls, pairs: List[Tuple[str, Any]]) -> "_KeyCheckedDict":
+        obj = cls()
+        obj.duplicates = []
+        for key, value in pairs:
+            if key in obj and key not in obj.duplicates:
+                obj.duplicates.append(key)
+            obj[key] = value
+        return obj
+
+
+def _decode_utf8(data: bytes) -> str:
+    """Skill file bytes as text, without a leading UTF-8 byte order mark."""
+    if data.startswith(codecs.BOM_UTF8):
+        data = data[len(codecs.BOM_UTF8):]
+    return data.decode("utf-8")
+
+
+# Bytes requested per read when validating files and streams
+_READ_CHUNK_BYTES = 64 * 1024
+
+
+def _read_hashed(reader: BinaryIO, limit: Optional[int]) -> Optional[Tuple[bytearray, str]]:
+    """
+    Read a binary stream to the end in chunks, hashing them on the way.
+
+    Returns the bytes without a leading UTF-8 byte order mark and their
+    sha256 digest, which for UTF-8 content equals content_hash() of the
+    decoded text. Returns None once more than `limit` bytes follow the
+    byte order mark, having read at most one byte more.
+    """
+    data = bytearray()
+    digest = hashlib.sha256()
+    start: Optional[int] = None  # Offset past a byte order mark, once enough is read to tell
+    total = 0
+    while True:
+        budget = _READ_CHUNK_BYTES if limit is None else limit + 1 + (start or 0) - total
+        chunk = reader.read(min(_READ_CHUNK_BYTES, budget))
+        if not chunk:
+            break
+        total += len(chunk)
+        data += chunk
+        if start is not None:
+            digest.update(chunk)
+        elif len(data) >= len(codecs.BOM_UTF8):
+            start = len(codecs.BOM_UTF8) if data.startswith(codecs.BOM_UTF8) else 0
+            digest.update(data[start:])
+        if limit is not None and total - (start or 0) > limit:
+            return None
+    if start is None:  # Shorter than a byte order mark
+        start = 0
+        digest.update(data)
+    del data[:start]
+    return data, "sha256:" + digest.hexdigest()
+
+
+def _fingerprint_or_empty(public_key: str) -> str:
+    try:
+        return key_fingerprint(public_key)
+    except ValueError:
+        return ""
+
+
+def _attach_locations(issues: List[ValidationIssue], text: str, data: Dict[str, Any]) -> None:
+    """Set each pathed issue's line/column in `text`; the text is only scanned if needed."""
+    if not any(issue.path for issue in issues):
+        return
+    dag = data.get("execution_dag")
+    nodes = dag.get("nodes") if isinstance(dag, dict) else None
+    node_ids = [n.get("id") if isinstance(n, dict) else None for n in nodes] if isinstance(nodes, list) else []
+    source = SourceMap(text, node_ids)
+    for issue in issues:
+        if issue.path and issue.location is None:
+            issue.location = source.locate(issue.path)
+
+
+def _duplicate_key_paths(value: Any, path: str = "") -> List[str]:
+    """Paths such as 'security' or 'execution_dag.nodes[0].params.url' of repeated keys."""
+    found: List[str] = []
+    if isinstance(value, dict):
+        for key in getattr(value, "duplicates", ()):
+            found.append(f"{path}.{key}" if path else key)
+        for key, item in value.items():
+            found.extend(_duplicate_key_paths(item, f"{path}.{key}" if path else key))
+    elif isinstance(value, list):
+        for i, item in enumerate(value):
+            found.extend(_duplicate_key_paths(item, f"{path}[{i}]"))
+    return found
+
+
+# Keys each part of a skill may hold, by path with list items as '[]'.
+# Free-form values (params, input_schema, output_schema) are not checked.
+_KNOWN_FIELDS: Dict[str, Tuple[str, ...]] = {
+    "": (
+        "jade_version", "skill_id", "metadata", "trigger", "input_schema", "output_schema",
+        "execution_dag", "security", "mcp_compatible", "required_mcp_capabilities",
+        "mcp_compatibility", "attestation", "jade_signature", "community_signatures",
+    ),
+    "metadata": ("name", "version", "description", "author", "tags", "license", "created_at", "updated_at"),
+    "trigger": ("type", "conditions"),
+    "trigger.conditions[]": ("field", "operator", "value"),
+    "execution_dag": ("nodes", "edges", "entry_node", "exit_node", "max_retries"),
+    "execution_dag.nodes[]": ("id", "action", "params", "timeout_ms", "retry"),
+    "execution_dag.nodes[].retry": ("max_attempts", "backoff_ms", "retry_on_status"),
+    "execution_dag.edges[]": ("from", "to", "condition"),
+    "security": (
+        "network_whitelist", "file_permissions", "max_execution_time_ms", "max_retries",
+        "sandbox_level", "dangerous_patterns", "env_whitelist",
+        "filesystem_access", "required_permissions", "forbidden_patterns",
+    ),
+    "security.file_permissions": ("read", "write"),
+    "jade_signature": ("signer", "algorithm", "public_key", "content_hash", "signature", "signed_at"),
+    "community_signatures[]": ("signer", "public_key", "signature", "trust_level", "signed_at"),
+}
+
+
+def _unknown_fields(value: Any, path: str = "", shape: str = "") -> List[Tuple[str, str, Tuple[str, ...]]]:
+    """(path, key, known keys) for each key _KNOWN_FIELDS does not list."""
+    found: List[Tuple[str, str, Tuple[str, ...]]] = []
+    known = _KNOWN_FIELDS.get(shape)
+    if isinstance(value, dict) and known is not None:
+        for key, item in value.items():
+            child = f"{path}.{key}" if path else key
+            if key not in known:
+                found.append((child, key, known))
+                continue
+            found.extend(_unknown_fields(item, child, f"{shape}.{key}" if shape else key))
+    elif isinstance(value, list) and f"{shape}[]" in _KNOWN_FIELDS:
+        for i, item in enumerate(value):
+            found.extend(_unknown_fields(item, f"{path}[{i}]", f"{shape}[]"))
+    return found
+
+
+def _declared_node_ids(data: Dict[str, Any]) -> List[str]:
+    """Node ids in declaration order, read leniently from raw skill data."""
+    dag = data.get("execution_dag")
+    nodes = dag.get("nodes") if isinstance(dag, dict) else None
+    if not isinstance(nodes, list):
+        return []
+    return [str(n.get("id", "")) if isinstance(n, dict) else "" for n in nodes]
+
+
+def _path_node_id(path: str, node_ids: List[str]) -> Optional[str]:
+    """The DAG node an issue path points into, or None."""
+    match = NODE_PATH_PATTERN.match(path)
+    if not match:
+        return None
+    if match.group(2) is not None:
+        return match.group(2)
+    index = int(match.group(1))
+    return (node_ids[index] or None) if index < len(node_ids) else None
+
+
+@dataclass
+class _StreamRun:
+    """State a validation stream leaves behind for building a ValidationResult."""
+    selected: Set[int] = field(default_factory=set)
+    skill_hash: str = ""
+    content_hash: Optional[str] = None
+    community_results: List[CommunitySigResult] = field(default_factory=list)
+    signer_role: Optional[SignerRole] = None
+    signatures: List[SignatureRecord] = field(default_factory=list)
+    timings_ms: Dict[int, float] = field(default_factory=dict)
+    finished: bool = False  # False if the stream stopped before the layers ran
+    node_ids: List[str] = field(default_factory=list)  # By position, for 'nodes[i]' paths
+    source_path: str = "<memory>"
+    skill_id: str = ""  # Raw value, for log events
+    root_verified: Optional[bool] = None  # Root signature check, once it has run
 
 
 class JadeValidator:
@@ -42,34 +445,109 @@ class JadeValidator:
         else:
             for issue in result.errors:
                 print(f"[{issue.code}] {issue.message}")
+
+    Thread safety: everything expensive (schema, action catalog, compiled
+    injection rules, skill_id pattern) is built by the constructor, and
+    validation never modifies the validator (past memoizing its cache
+    namespace), so a server can build one instance at startup and call
+    validate_* from many threads at once. The set_*/load_*/use_* methods
+    are for setup and must not run while other threads validate; to change
+    settings of a validator in use, clone() it and swap in the reconfigured
+    copy.
     """
 
-    SUPPORTED_VERSIONS = {"1.0.0"}
-    SKILL_ID_PATTERN = re.compile(r'^[a-z][a-z0-9_]{2,63}$')
-    VERSION_PATTERN = re.compile(r'^\d+\.\d+\.\d+$')
-    JADE_VERSION_PATTERN = re.compile(r'^1\.\d+\.\d+$')
+    SKILL_ID_PATTERN = re.compile(SkillIdPolicy.pattern)
+    # Sections each layer needs parsed; validate_all skips a layer otherwise
+    LAYER_SECTIONS = {
+        ValidationLayer.DAG: {"execution_dag"},
+        ValidationLayer.SECURITY: {"execution_dag", "security"},
+        ValidationLayer.SEMANTIC: {"execution_dag", "security"},
+        ValidationLayer.SIGNATURE: {"jade_signature", "community_signatures"},
+    }
+    JSON_SCHEMA_TYPES = {"object", "array", "string", "number", "integer", "boolean", "null"}
+    PARAM_TYPES = {"string", "number", "boolean", "array", "object"}
 
     def __init__(
         self,
         schema_path: Optional[str] = None,
         allowed_actions_path: Optional[str] = None,
+        trust_policy: Optional[TrustPolicy] = None,
+        security_rules: Optional[SecurityRules] = None,
+        key_registry: Optional[KeyRegistry] = None,
+        freshness_policy: Optional[FreshnessPolicy] = None,
+        clock: Callable[[], float] = time.time,
+        allowed_actions: Optional[Iterable[str]] = None,
+        multiple_roots: Optional[ValidationSeverity] = ValidationSeverity.WARNING,
+        schema: Optional[Dict[str, Any]] = None,
+        action_catalog: Optional[Dict[str, Any]] = None,
+        suppressions: Optional[Iterable[Suppression]] = None,
+        skill_id_policy: Optional[SkillIdPolicy] = None,
+        resource_limits: Optional[ResourceLimits] = None,
+        custom_layers: Optional[Iterable[CustomLayer]] = None,
+        previous_version: Optional[JadeSkill] = None,
+        cache: Optional[ValidationCache] = None,
+        trust_signed_scan: bool = False,
+        min_severity: ValidationSeverity = ValidationSeverity.INFO,
     ):
+        if trust_signed_scan and (key_registry is None or cache is None):
+            raise ValueError("trust_signed_scan needs a key_registry and a cache")
+        self._trust_signed_scan = trust_signed_scan
+        self._min_severity = ValidationSeverity(min_severity)
         self._schema: Optional[Dict[str, Any]] = None
+        self._suppressions: List[Suppression] = list(suppressions or [])
+        self._skill_id_policy = skill_id_policy or SkillIdPolicy()
+        self._skill_id_re = re.compile(self._skill_id_policy.pattern)
+        self._limits = resource_limits or ResourceLimits()
+        self._custom_layers: List[CustomLayer] = list(custom_layers or [])
+        self._previous_version = previous_version
+        self._cache = cache
+        self._settings_digest: Optional[str] = None  # Cache namespace; reset by every setter
+        if trust_policy is not None:
+            unknown = [n for n in trust_policy.require_signature_when if n not in CAPABILITY_RISK_WEIGHTS]
+            if unknown:
+                raise ValueError(
+                    f"Unknown capability in require_signature_when: {unknown[0]!r}. "
+                    f"Expected one of {sorted(CAPABILITY_RISK_WEIGHTS)}"
+                )
+        self._trust_policy = trust_policy
+        self._key_registry = key_registry
+        self._freshness_policy = freshness_policy
+        self._clock = clock  # Injectable "now" for signature freshness
         self._allowed_actions: List[str] = []
-        self._security_engine = SecurityEngine()
-        self._dag_analyzer = DAGAnalyzer()
+        self._action_schemas: ActionSchemas = {}
+        self._action_outputs: ActionOutputs = {}
+        self._security_engine = SecurityEngine(rules=security_rules)
+        self._dag_analyzer = DAGAnalyzer(multiple_roots=multiple_roots)
 
-        # Load schema if provided
-        if schema_path:
+        # In-memory resources win over paths, so a validator given both
+        # `schema` and `action_catalog` never touches the filesystem
+        if schema is not None:
+            self._schema = schema
+        elif schema_path:
             self.load_schema(schema_path)
         else:
             self._load_default_schema()
 
-        # Load allowed actions if provided
-        if allowed_actions_path:
+        # Allowed actions: an explicit set wins over a catalog, which wins over
+        # the bundled allowed_atomic_actions.json catalog
+        if action_catalog is not None:
+            self.use_action_catalog(action_catalog)
+        elif allowed_actions_path:
             self.load_allowed_actions(allowed_actions_path)
         else:
             self._load_default_allowed_actions()
+        if allowed_actions is not None:
+            self.set_allowed_actions(allowed_actions)  # Param schemas still come from the catalog
+
+    @classmethod
+    def with_key_registry(cls, key_registry: KeyRegistry, **kwargs: Any) -> JadeValidator:
+        """Create a validator that only accepts root signatures from registered keys."""
+        return cls(key_registry=key_registry, **kwargs)
+
+    @classmethod
+    def with_trust_policy(cls, trust_policy: TrustPolicy, **kwargs: Any) -> JadeValidator:
+        """Create a validator that enforces a trust policy on top of the usual layers."""
+        return cls(trust_policy=trust_policy, **kwargs)
 
     def _load_default_schema(self) -> None:
         """Load the default schema from jade_schema directory."""
@@ -87,6 +565,8 @@ class JadeValidator:
             with open(actions_file, "r", encoding="utf-8") as f:
                 data = json.load(f)
                 self._allowed_actions = self._extract_action_names(data)
+                self._action_schemas = action_schemas_from_catalog(data)
+                self._action_outputs = action_outputs_from_catalog(data)
                 self._security_engine.set_allowed_actions(self._allowed_actions)
 
     def _extract_action_names(self, data: Dict[str, Any]) -> List[str]:
@@ -99,16 +579,101 @@ class JadeValidator:
         return actions
 
     def load_schema(self, path: str) -> None:
-        """Load a JADE schema from file."""
-        with open(path, "r", encoding="utf-8") as f:
-            self._schema = json.load(f)
+        """Load a JADE schema from file. Raises JadeIOError or JadeParseError."""
+        self._schema = self._read_json(path, "schema")
+        self._settings_digest = None
 
     def load_allowed_actions(self, path: str) -> None:
-        """Load allowed atomic actions from file."""
-        with open(path, "r", encoding="utf-8") as f:
-            data = json.load(f)
-            self._allowed_actions = self._extract_action_names(data)
-            self._security_engine.set_allowed_actions(self._allowed_actions)
+        """Load allowed atomic actions from file. Raises JadeIOError or JadeParseError."""
+        self.use_action_catalog(self._read_json(path, "allowed actions"))
+
+    def use_action_catalog(self, catalog: Dict[str, Any]) -> None:
+        """Use an allowed_atomic_actions.json-shaped catalog already in memory."""
+        self._allowed_actions = self._extract_action_names(catalog)
+        self._action_schemas = action_schemas_from_catalog(catalog)
+        self._action_outputs = action_outputs_from_catalog(catalog)
+        self._security_engine.set_allowed_actions(self._allowed_actions)
+        self._settings_digest = None
+
+    @staticmethod
+    def _read_json(path: str, what: str) -> Dict[str, Any]:
+        try:
+            with open(path, "r", encoding="utf-8") as f:
+                return json.load(f)
+        except OSError as e:
+            raise JadeIOError(f"Cannot read {what} file {path}: {e}", path) from e
+        except json.JSONDecodeError as e:
+            raise JadeParseError(f"{what.capitalize()} file {path} is not valid JSON: {e}") from e
+
+    def set_allowed_actions(self, actions: Iterable[str]) -> None:
+        """Replace the allowed atomic actions with an explicit set."""
+        self._allowed_actions = sorted(set(actions))
+        self._security_engine.set_allowed_actions(self._allowed_actions)
+        self._settings_digest = None
+
+    @property
+    def allowed_actions(self) -> List[str]:
+        return list(self._allowed_actions)
+
+    @property
+    def custom_layers(self) -> Dict[int, CustomLayer]:
+        """Custom layers by the layer number their issues are tagged with."""
+        return {FIRST_CUSTOM_LAYER + i: layer for i, layer in enumerate(self._custom_layers)}
+
+    @property
+    def layer_numbers(self) -> List[int]:
+        """Every layer this validator runs: the built-in five, then custom layers."""
+        return [int(layer) for layer in ValidationLayer] + list(self.custom_layers)
+
+    def clone(self) -> JadeValidator:
+        """
+        A validator with the same settings whose setters do not affect this one.
+
+        Compiled state is shared rather than rebuilt, so cloning is cheap.
+        The key registry is shared too: keys added to it are trusted by both.
+        """
+        other = copy.copy(self)
+        other._suppressions = list(self._suppressions)
+        other._custom_layers = list(self._custom_layers)
+        other._allowed_actions = list(self._allowed_actions)
+        other._security_engine = copy.copy(self._security_engine)
+        return other
+
+    def set_suppressions(self, suppressions: Iterable[Suppression]) -> None:
+        """Replace the suppressions applied to every later validation."""
+        self._suppressions = list(suppressions)
+        self._settings_digest = None
+
+    @property
+    def suppressions(self) -> List[Suppression]:
+        return list(self._suppressions)
+
+    def set_previous_version(self, skill: Optional[JadeSkill]) -> None:
+        """
+        Compare every later skill against a trusted earlier version of it.
+
+        Anything the new version is granted beyond `skill` (see
+        diff.SkillDiff.escalations) is reported as a SEC_POLICY_RELAXED
+        error, so a routine-looking update cannot quietly loosen the policy.
+        A relaxation that is intended is acknowledged with
+        Suppression("SEC_POLICY_RELAXED"). `skill` must already be trusted,
+        e.g. the version currently installed; None turns the check off.
+        """
+        self._previous_version = skill
+        self._settings_digest = None
+
+    @property
+    def previous_version(self) -> Optional[JadeSkill]:
+        return self._previous_version
+
+    @property
+    def min_severity(self) -> ValidationSeverity:
+        """
+        The least severe issue results report. Less severe issues are
+        dropped from ValidationResult.issues and counted in its `hidden`;
+        `valid` still depends only on errors, which are never dropped.
+        """
+        return self._min_severity
 
     def validate_file(self, file_path: str) -> ValidationResult:
         """Validate a JADE skill JSON file."""
@@ -120,77 +685,704 @@ class JadeValidator:
                 valid=False,
                 issues=[ValidationIssue(
                     severity=ValidationSeverity.ERROR,
-                    code="FILE_NOT_FOUND",
+                    code=IssueCode.FILE_NOT_FOUND,
                     message=f"File not found: {file_path}",
+                    layer=ValidationLayer.SCHEMA,
                 )],
             )
 
+        with open(file_path, "rb") as f:
+            return self.validate_reader(f, source_path=file_path)
+
+    def validate_bytes(self, data: bytes, source_path: str = "<memory>") -> ValidationResult:
+        """
+        Validate a JADE skill from raw file bytes.
+
+        A leading UTF-8 byte order mark, as written by some Windows tools,
+        is stripped; bytes that are not UTF-8 are reported as INVALID_JSON.
+        """
+        try:
+            text = _decode_utf8(data)
+        except UnicodeDecodeError as e:
+            return self._invalid_json(f"Invalid UTF-8: {e}")
+        return self.validate_str(text, source_path=source_path)
+
+    def validate_reader(self, reader: BinaryIO, source_path: str = "<stdin>") -> ValidationResult:
+        """
+        Validate a JADE skill read to the end from a binary stream, e.g.
+        sys.stdin.buffer. With ResourceLimits.max_bytes set, at most one
+        byte more than the limit is read before the skill is rejected.
+
+        The stream is read in chunks and hashed as it arrives, so a cached
+        result is returned without decoding or parsing anything, and the
+        raw bytes are released as soon as they are decoded. The text itself
+        is still held once: json has no incremental parser, and issue
+        locations are computed from it.
+        """
+        limit = self._limits.max_bytes
+        read = _read_hashed(reader, limit)
+        if read is None:
+            return self._resource_limit(f"Skill JSON is larger than {limit} bytes")
+        data, text_hash = read
+        key = self._cache_key(text_hash, source_path) if self._caching else None
+        cached = self._cached(key)
+        if cached is not None:
+            return cached
+        try:
+            text = data.decode("utf-8")
+        except UnicodeDecodeError as e:
+            return self._invalid_json(f"Invalid UTF-8: {e}")
+        size = len(data)
+        data.clear()
+        return self._validate_and_store(text, source_path, key, size=size)
+
+    def validate_str(self, text: str, source_path: str = "<memory>") -> ValidationResult:
+        """Validate a JADE skill from a JSON string."""
+        return self._validate_text(text, source_path)
+
+    def validate_value(self, value: Any, source_path: str = "<memory>") -> ValidationResult:
+        """
+        Validate an already-parsed JSON value, e.g. a web framework's
+        request body, without the caller serializing it first.
+
+        The value is encoded as pretty-printed JSON text (json.dumps,
+        indent=2) and validated like validate_str, so resource limits, the
+        cache and duplicate-key checks apply, and issue locations refer to
+        that text. A value that is not an object, or that json cannot encode
+        (bytes, sets, circular references), is reported as INVALID_JSON.
+        """
+        try:
+            text = json.dumps(value, indent=2, ensure_ascii=False)
+        except (TypeError, ValueError) as e:
+            return self._invalid_json(f"Not a JSON value: {e}")
+        return self._validate_text(text, source_path)
+
+    def verify_detached(
+        self,
+        skill_bytes: bytes,
+        signature: str,
+        public_key: str,
+        algorithm: str = "ed25519",
+        source_path: str = "<memory>",
+    ) -> ValidationResult:
+        """
+        Validate a skill whose root signature is kept outside the file.
+
+        `signature` (base64) and `public_key` (any encoding parse_public_key
+        accepts) stand in for the jade_signature block, and any embedded
+        block is ignored. The signature covers the same signable content as
+        an embedded one, so a skill signed with sign_skill and then stripped
+        verifies here with the stripped block's signature and key. Issues
+        about it carry jade_signature paths but no line and column.
+        """
+        detached = JadeSignature(public_key=public_key, signature=signature, algorithm=algorithm)
+        try:
+            text = _decode_utf8(skill_bytes)
+        except UnicodeDecodeError as e:
+            return self._invalid_json(f"Invalid UTF-8: {e}")
+        return self._validate_text(text, source_path, detached)
+
+    def _validate_text(
+        self, text: str, source_path: str, detached: Optional[JadeSignature] = None,
+    ) -> ValidationResult:
+        key = self._cache_key(content_hash(text), source_path) if detached is None and self._caching else None
+        cached = self._cached(key)
+        if cached is not None:
+            return cached
+        return self._validate_and_store(text, source_path, key, detached)
+
+    def _validate_and_store(
+        self,
+        text: str,
+        source_path: str,
+        key: Optional[str],
+        detached: Optional[JadeSignature] = None,
+        size: Optional[int] = None,
+    ) -> ValidationResult:
+        """Validate text that missed the cache, storing a valid result under `key`."""
+        result = self._validate_uncached(text, source_path, detached, size)
+        if key is not None and result.valid:
+            self._cache.put(key, result)  # type: ignore[union-attr]
+        return result
+
+    def _cached(self, key: Optional[str]) -> Optional[ValidationResult]:
+        """The result cached under `key`, stamped with the time of this check."""
+        if key is None:
+            return None
+        cached = self._cache.get(key)  # type: ignore[union-attr]
+        if cached is not None:
+            cached.checked_at = time.time()
+        return cached
+
+    @property
+    def _caching(self) -> bool:
+        """
+        Whether results may be cached: not without a cache, nor with a
+        freshness policy, whose verdict depends on the clock rather than
+        the content, nor with a custom layer that has no cache_token().
+        """
+        return (
+            self._cache is not None and self._freshness_policy is None
+            and all(layer.cache_token() is not None for layer in self._custom_layers)
+        )
+
+    def _cache_key(self, text_hash: str, source_path: str) -> str:
+        """Cache key for a skill text with the given content_hash."""
+        parts = [self._settings(), text_hash]
+        if self._key_registry is not None:
+            parts += sorted(f"{key['fingerprint']}:{key['role']}" for key in self._key_registry.to_list())
+        if self._skill_id_policy.match_filename:
+            parts.append(source_path)
+        return hashlib.sha256("\n".join(parts).encode("utf-8")).hexdigest()
+
+    def _settings(self) -> str:
+        """Digest of every setting that can change a result, computed once per configuration."""
+        if self._settings_digest is None:
+            from . import __version__
+
+            settings = [
+                __version__, self._schema, self._allowed_actions, self._action_schemas, self._action_outputs,
+                self._security_engine.rules, self._trust_policy, self._skill_id_policy, self._limits,
+                self._suppressions, self._dag_analyzer.multiple_roots, self._previous_version, self._min_severity,
+                [
+                    f"{type(layer).__module__}.{type(layer).__qualname__}:{layer.name}:{layer.cache_token()}"
+                    for layer in self._custom_layers
+                ],
+            ]
+            encoded = json.dumps(settings, sort_keys=True, default=repr).encode("utf-8")
+            self._settings_digest = hashlib.sha256(encoded).hexdigest()
+        return self._settings_digest
+
+    def _validate_uncached(
+        self, text: str, source_path: str, detached: Optional[JadeSignature], size: Optional[int] = None,
+    ) -> ValidationResult:
+        # 1. Resource limits, before the parser recurses into the text
+        limit_error = self._text_limit_error(text, size)
+        if limit_error:
+            return self._resource_limit(limit_error)
+
         # 2. JSON parsing
         try:
-            with open(file_path, "r", encoding="utf-8") as f:
-                raw_data = json.load(f)
+            raw_data = json.loads(text, object_pairs_hook=_KeyCheckedDict.from_pairs)
         except json.JSONDecodeError as e:
-            return ValidationResult(
+            return self._invalid_json(f"Invalid JSON: {e}", location=(e.lineno, e.colno))
+        if not isinstance(raw_data, dict):
+            return self._invalid_json(
+                f"Invalid JSON: top-level value must be an object, got {type(raw_data).__name__}"
+            )
+
+        # Duplicate keys: json keeps the last value, so a reviewer reading
+        # the first one would see something other than what takes effect
+        duplicates = _duplicate_key_paths(raw_data)
+        if duplicates:
+            result = ValidationResult(
                 valid=False,
                 issues=[ValidationIssue(
                     severity=ValidationSeverity.ERROR,
-                    code="INVALID_JSON",
-                    message=f"Invalid JSON: {e}",
-                )],
+                    code=IssueCode.DUPLICATE_KEY,
+                    message=f"Duplicate JSON key: {path}",
+                    path=path,
+                    layer=ValidationLayer.SCHEMA,
+                ) for path in duplicates],
             )
+        elif detached is not None:
+            data = dict(raw_data, jade_signature=detached.to_dict())
+            result = self.validate_dict(data, source_path=source_path)
+        else:
+            result = self.validate_dict(raw_data, source_path=source_path)
+        _attach_locations(result.issues, text, raw_data)
+        if detached is not None:
+            for issue in result.issues:
+                if issue.path.startswith("jade_signature"):
+                    issue.location = None  # Not in the text; don't point at an ignored block
+        return result
+
+    def _text_limit_error(self, text: str, size: Optional[int] = None) -> Optional[str]:
+        """`size` is the text's encoded length in bytes, when the caller already knows it."""
+        limits = self._limits
+        if limits.max_bytes is not None:
+            if size is None:
+                # Every character takes at least one byte, so only encode when needed
+                size = len(text) if len(text) > limits.max_bytes else len(text.encode("utf-8"))
+            if size > limits.max_bytes:
+                return f"Skill JSON is larger than {limits.max_bytes} bytes"
+        if limits.max_depth is not None and json_nesting_depth(text, limits.max_depth) > limits.max_depth:
+            return f"Skill JSON nests deeper than {limits.max_depth} levels"
+        return None
+
+    def _data_limit_errors(self, data: Dict[str, Any]) -> List[str]:
+        limits = self._limits
+        errors: List[str] = []
+        if limits.max_depth is not None and _nesting_depth(data, limits.max_depth) > limits.max_depth:
+            errors.append(f"Skill nests deeper than {limits.max_depth} levels")
+        dag = data.get("execution_dag")
+        if isinstance(dag, dict):
+            for key, limit in (("nodes", limits.max_nodes), ("edges", limits.max_edges)):
+                items = dag.get(key)
+                if limit is not None and isinstance(items, list) and len(items) > limit:
+                    errors.append(f"execution_dag has {len(items)} {key}, more than the limit of {limit}")
+        return errors
+
+    def _resource_limit(self, message: str) -> ValidationResult:
+        return ValidationResult(
+            valid=False,
+            issues=[ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.RESOURCE_LIMIT,
+                message=message,
+                layer=ValidationLayer.SCHEMA,
+            )],
+        )
 
-        return self.validate_dict(raw_data, source_path=file_path)
+    def _invalid_json(self, message: str, location: Optional[Tuple[int, int]] = None) -> ValidationResult:
+        return ValidationResult(
+            valid=False,
+            issues=[ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.INVALID_JSON,
+                message=message,
+                layer=ValidationLayer.SCHEMA,
+                location=location,
+            )],
+        )
 
     def validate_dict(self, data: Dict[str, Any], source_path: str = "<memory>") -> ValidationResult:
-        """Validate a JADE skill from a dictionary."""
-        issues: List[ValidationIssue] = []
+        """Validate a JADE skill from a dictionary, running every layer."""
+        return self.validate_with_layers(data, self.layer_numbers, source_path=source_path)
 
-        # 3. Structural validation (required fields)
-        issues.extend(self._check_required_fields(data))
-        if any(i.severity == ValidationSeverity.ERROR for i in issues):
-            return ValidationResult(valid=False, issues=issues)
+    def validate_all(self, data: Dict[str, Any], source_path: str = "<memory>") -> ValidationResult:
+        """
+        Validate a JADE skill without short-circuiting, collecting every issue.
+
+        Missing or malformed sections are reported and replaced by empty
+        placeholders so later layers still run. A layer whose inputs could
+        not be parsed is listed in layers_skipped instead of layers_passed.
+        """
+        return self.validate_with_layers(
+            data, self.layer_numbers, source_path=source_path, continue_on_error=True,
+        )
+
+    def validate_with_layers(
+        self,
+        data: Dict[str, Any],
+        layers: Iterable[int],
+        source_path: str = "<memory>",
+        continue_on_error: bool = False,
+    ) -> ValidationResult:
+        """
+        Validate a JADE skill, running only the requested layers.
+
+        Required fields and model parsing always run since every layer needs
+        a parsed skill; their issues are reported under Layer 1. Layers not
+        requested are listed in ValidationResult.layers_skipped.
+        """
+        run = _StreamRun(source_path=source_path)
+        emit(logger, logging.DEBUG, "validation_started", f"Validating {source_path}",
+             skill_id=str(data.get("skill_id", "")), source_path=source_path)
+        issues = list(self._stream(data, self._layer_mask(layers), continue_on_error, run))
+        skipped = [layer for layer in self.layer_numbers if layer not in run.selected]
+        errors = sum(1 for i in issues if i.severity == ValidationSeverity.ERROR)
+        warnings = sum(1 for i in issues if i.severity == ValidationSeverity.WARNING)
+        emit(logger, logging.INFO, "validation_finished",
+             f"{source_path}: {'valid' if run.finished and not errors else 'invalid'} "
+             f"({errors} errors, {warnings} warnings)",
+             skill_id=run.skill_id, valid=run.finished and not errors,
+             error_count=errors, warning_count=warnings)
+        if not run.finished:
+            result = ValidationResult(valid=False, issues=issues, layers_skipped=skipped)
+            return result.at_least(self._min_severity)
+
+        has_errors = errors > 0
+        result = ValidationResult(
+            valid=not has_errors,
+            issues=issues,
+            skill_hash=run.skill_hash,
+            checked_at=time.time(),
+            content_hash=run.content_hash,
+            community_results=run.community_results,
+            signer_role=run.signer_role,
+            signatures=run.signatures,
+            layers_passed=self._layers_passed(issues, sorted(run.selected)),
+            layers_skipped=skipped,
+            timings_ms=run.timings_ms,
+        )
+        return result.at_least(self._min_severity)
+
+    def validate_streaming(
+        self,
+        data: Dict[str, Any],
+        layers: Optional[Iterable[int]] = None,
+        continue_on_error: bool = False,
+    ) -> Iterator[ValidationIssue]:
+        """
+        Yield validation issues as each layer produces them.
+
+        Issues arrive in the same order validate_with_layers reports them:
+        required fields and parsing first, then layers 1 to 5 and any custom
+        layers in turn, each issue already tagged with its layer. Work is
+        lazy between layers, so a caller that stops iterating (e.g. at the
+        first error) skips the remaining layers. Without continue_on_error
+        the stream ends after a missing required field or a parse failure,
+        as validate_dict does. Issues below min_severity are not yielded.
+        """
+        selected = self._layer_mask(self.layer_numbers if layers is None else layers)
+        issues = self._stream(data, selected, continue_on_error, _StreamRun())
+        return (i for i in issues if i.severity >= self._min_severity)
+
+    def _stream(
+        self,
+        data: Dict[str, Any],
+        selected: Set[int],
+        continue_on_error: bool,
+        run: _StreamRun,
+    ) -> Iterator[ValidationIssue]:
+        """Generator behind validate_streaming; records result state in `run`."""
+        run.selected = selected
+
+        # Oversized documents stop here even with continue_on_error: the
+        # checks below recurse into the data and would run out of stack
+        limit_errors = [ValidationIssue(
+            severity=ValidationSeverity.ERROR,
+            code=IssueCode.RESOURCE_LIMIT,
+            message=message,
+        ) for message in self._data_limit_errors(data)]
+        if limit_errors:
+            yield from self._in_layer(ValidationLayer.SCHEMA, limit_errors, run)
+            return
+        run.node_ids = _declared_node_ids(data)
+        run.skill_id = str(data.get("skill_id", ""))
+
+        # 3. Structural validation (required fields); unknown keys only warn
+        sandbox = self._check_sandbox_level(data)
+        structural = self._check_required_fields(data) + self._check_unknown_fields(data) + sandbox
+        required = self._in_layer(ValidationLayer.SCHEMA, structural, run)
+        yield from required
+        if not continue_on_error and any(i.severity == ValidationSeverity.ERROR for i in required):
+            return
 
         # 4. Parse into model
-        try:
-            skill = JadeSkill.from_dict(data)
-        except Exception as e:
-            return ValidationResult(
-                valid=False,
-                issues=[ValidationIssue(
+        broken: Set[str] = set()
+        if continue_on_error:
+            reported = ["security"] if any(i.severity == ValidationSeverity.ERROR for i in sandbox) else []
+            skill, parse_issues, broken = self._parse_sections(data, reported)
+            yield from self._in_layer(ValidationLayer.SCHEMA, parse_issues, run)
+            selected -= {layer for layer, needs in self.LAYER_SECTIONS.items() if needs & broken}
+        else:
+            try:
+                skill = JadeSkill.from_dict(data)
+            except Exception as e:
+                yield ValidationIssue(
                     severity=ValidationSeverity.ERROR,
-                    code="PARSE_ERROR",
+                    code=IssueCode.PARSE_ERROR,
                     message=f"Failed to parse skill: {e}",
-                )],
+                    layer=ValidationLayer.SCHEMA,
+                )
+                return
+
+        # Canonical serialization feeds hashing and signatures; `signable` is
+        # crypto.signable_content(data), the signing contract. A skill that
+        # cannot be serialized (e.g. a NaN parameter) is flagged rather than
+        # hashed or verified as something it is not.
+        signable: Optional[str] = None
+        try:
+            signable, run.skill_hash = signable_and_hash(data)
+            run.content_hash = content_hash(signable)
+        except (TypeError, ValueError) as e:
+            selected.discard(ValidationLayer.SIGNATURE)
+            yield ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.SERIALIZE_ERROR,
+                message=f"Skill cannot be serialized to canonical JSON: {e}",
+                layer=ValidationLayer.SCHEMA,
+            )
+
+        # 5-7. Layer 1: version, metadata, trigger, input/output schemas
+        if ValidationLayer.SCHEMA in selected:
+            def schema_checks() -> List[ValidationIssue]:
+                schema_issues = self.validate_io_schemas(skill)
+                if "jade_version" not in broken:
+                    schema_issues += self._check_version(skill)
+                if not broken & {"skill_id", "metadata"}:
+                    schema_issues += self._check_metadata(skill)
+                    schema_issues += self._check_identifiers(
+                        [("skill_id", skill.skill_id), ("metadata.name", skill.metadata.name)]
+                    )
+                    schema_issues += self._check_skill_id_filename(skill, run.source_path)
+                if "trigger" not in broken:
+                    schema_issues += self._check_trigger(skill)
+                if "execution_dag" not in broken:
+                    schema_issues += self._check_actions(skill)
+                    schema_issues += self._check_node_params(skill)
+                    schema_issues += self._check_params_without_action(skill)
+                    schema_issues += self._check_retry_policies(skill)
+                    schema_issues += self._check_reserved_names(skill)
+                    schema_issues += self._check_identifiers(
+                        (f"execution_dag.nodes[{i}].id", node.id) for i, node in enumerate(skill.execution_dag.nodes)
+                    )
+                    schema_issues += self._check_output_consistency(skill)
+                return schema_issues
+            yield from self._timed(run, ValidationLayer.SCHEMA, schema_checks)
+
+        # 8. Layer 2: DAG validation
+        if ValidationLayer.DAG in selected:
+            yield from self._timed(run, ValidationLayer.DAG, lambda: self._dag_analyzer.validate(skill))
+
+        # Layers 3 and 4 of content signed by a trusted key may come from the scan cache
+        scan_key = self._scan_cache_key(skill, signable, run, selected)
+        scanned = self._cache.get(scan_key) if scan_key is not None else None  # type: ignore[union-attr]
+
+        def scan(layer: int, check: Callable[[], List[ValidationIssue]]) -> Callable[[], List[ValidationIssue]]:
+            """`check`, or the cached issues of `layer` on a scan cache hit."""
+            if scanned is None:
+                return check
+            return lambda: [i for i in scanned.issues if i.layer == layer]
+
+        # 9. Layer 3: Security validation
+        security: List[ValidationIssue] = []
+        if ValidationLayer.SECURITY in selected:
+            security = self._timed(run, ValidationLayer.SECURITY, scan(
+                ValidationLayer.SECURITY,
+                lambda: self._security_engine.check_all(skill) + self._check_policy_relaxation(skill),
+            ))
+            yield from security
+
+        # 10. Layer 4: Semantic cross-validation
+        semantic: List[ValidationIssue] = []
+        if ValidationLayer.SEMANTIC in selected:
+            semantic = self._timed(run, ValidationLayer.SEMANTIC, scan(
+                ValidationLayer.SEMANTIC, lambda: self._check_semantic_consistency(skill),
+            ))
+            yield from semantic
+        if scan_key is not None and scanned is None:
+            issues = security + semantic
+            if not any(i.severity == ValidationSeverity.ERROR for i in issues):
+                self._cache.put(scan_key, ValidationResult(valid=True, issues=issues))  # type: ignore[union-attr]
+
+        # 11. Layer 5: Signature verification (skipped above if unserializable)
+        if ValidationLayer.SIGNATURE in selected and signable is not None:
+            yield from self._timed(
+                run, ValidationLayer.SIGNATURE, lambda: self._check_signatures(skill, signable, run),
             )
 
-        # 5. Version check
-        issues.extend(self._check_version(skill))
+        # 12. Custom layers, in the order they were given
+        for number, custom in self.custom_layers.items():
+            if number in selected:
+                yield from self._timed(run, number, lambda: list(custom.validate(skill, data)))
+        run.finished = True
 
-        # 6. Metadata validation
-        issues.extend(self._check_metadata(skill))
+    def _scan_cache_key(
+        self, skill: JadeSkill, signable: Optional[str], run: _StreamRun, selected: Set[int],
+    ) -> Optional[str]:
+        """
+        Scan cache key for layers 3 and 4 with trust_signed_scan, or None
+        unless both run and the root signature is trusted: it declares the
+        content_hash of this content and verifies with a registered key.
+        """
+        if not self._trust_signed_scan or signable is None:
+            return None
+        if not {ValidationLayer.SECURITY, ValidationLayer.SEMANTIC} <= selected:
+            return None
+        sig = skill.jade_signature
+        if sig is None or not run.content_hash or sig.content_hash != self._declared_hash_of(sig, signable, run):
+            return None
+        if _fingerprint_or_empty(sig.public_key) not in self._key_registry:  # type: ignore[operator]
+            return None
+        if not self._root_verifies(sig, signable, run):
+            return None
+        return hashlib.sha256(f"{self._settings()}\nscan\n{run.content_hash}".encode("utf-8")).hexdigest()
+
+    @staticmethod
+    def _declared_hash_of(sig: JadeSignature, signable: str, run: _StreamRun) -> Optional[str]:
+        """content_hash of `signable` with the algorithm sig.content_hash names; None if it names none."""
+        try:
+            hash_algorithm, _ = parse_content_hash(sig.content_hash)
+        except ValueError:
+            return None
+        if hash_algorithm == DEFAULT_HASH_ALGORITHM:
+            return run.content_hash
+        return content_hash(signable, hash_algorithm)
 
-        # 7. Trigger validation
-        issues.extend(self._check_trigger(skill))
+    def _root_verifies(self, sig: JadeSignature, signable: str, run: _StreamRun) -> bool:
+        """Whether the root signature verifies, checked at most once per run."""
+        if run.root_verified is None:
+            try:
+                algorithm = SignatureAlgorithm.parse(sig.algorithm)
+            except ValueError:
+                algorithm = None
+            run.root_verified = algorithm in IMPLEMENTED_ALGORITHMS and verify_signature(
+                signable.encode("utf-8"), sig.signature, sig.public_key, algorithm,  # type: ignore[arg-type]
+            )
+        return run.root_verified
 
-        # 8. DAG validation
-        issues.extend(self._dag_analyzer.validate(skill))
+    def _check_policy_relaxation(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """Escalations relative to the trusted previous version, if one is set."""
+        if self._previous_version is None:
+            return []
+        change = diff_skills(self._previous_version, skill)
+        return [
+            ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.SEC_POLICY_RELAXED,
+                message=(
+                    f"{escalation.reason} (was not granted by trusted version "
+                    f"{change.old_version}); suppress SEC_POLICY_RELAXED to accept"
+                ),
+                path=escalation.path,
+                layer=ValidationLayer.SECURITY,
+                node_id=escalation.node_id,
+            )
+            for escalation in change.escalations
+        ]
 
-        # 9. Security validation
-        issues.extend(self._security_engine.check_all(skill))
+    def _timed(
+        self,
+        run: _StreamRun,
+        layer: int,
+        check: Callable[[], List[ValidationIssue]],
+    ) -> List[ValidationIssue]:
+        """Run one layer's checks, tagging the issues and recording its wall time."""
+        emit(logger, logging.DEBUG, "layer_started", f"Layer {int(layer)} started",
+             skill_id=run.skill_id, layer=int(layer))
+        start = time.perf_counter()
+        issues = self._in_layer(layer, check(), run)
+        elapsed_ms = (time.perf_counter() - start) * 1000.0
+        run.timings_ms[int(layer)] = elapsed_ms
+        emit(logger, logging.DEBUG, "layer_finished",
+             f"Layer {int(layer)} finished in {elapsed_ms:.2f}ms ({len(issues)} issues)",
+             skill_id=run.skill_id, layer=int(layer), duration_ms=elapsed_ms, issue_count=len(issues))
+        return issues
 
-        # 10. Semantic cross-validation
-        issues.extend(self._check_semantic_consistency(skill))
+    def _check_signatures(self, skill: JadeSkill, signable: str, run: _StreamRun) -> List[ValidationIssue]:
+        """Layer 5: root signature, community co-signatures, freshness and trust policy."""
+        sig_issues = self._check_signature(skill, signable, run.content_hash or "", run)
+        run.community_results = self._verify_community_signatures(skill, signable)
+        run.signatures += [SignatureRecord(
+            kind="community",
+            signer=res.signer,
+            algorithm=SignatureAlgorithm.ED25519.value,  # Co-signatures are always Ed25519
+            fingerprint=res.fingerprint,
+            verified=res.valid,
+            signed_at=sig.signed_at,
+            path=f"community_signatures[{i}]",
+        ) for i, (sig, res) in enumerate(zip(skill.community_signatures, run.community_results))]
+        for record in run.signatures:
+            emit(logger, logging.INFO, "signature_verified",
+                 f"{record.kind} signature by {record.fingerprint or '?'} "
+                 f"{'verified' if record.verified else 'did not verify'}",
+                 skill_id=run.skill_id, kind=record.kind, signer=record.signer,
+                 fingerprint=record.fingerprint, verified=record.verified)
+        root_valid = skill.jade_signature is not None and not sig_issues
+        if root_valid:
+            run.signer_role = self._signer_role(skill.jade_signature.public_key)
+        freshness_issues = self._check_community_freshness(skill)
+        stale = {i.path for i in freshness_issues}
+        return (
+            sig_issues
+            + self._check_community_results(run.community_results)
+            + freshness_issues
+            + self._check_trust_policy(skill, run.community_results, run.signer_role, stale)
+        )
 
-        # Compute skill hash
-        skill_hash = self._compute_skill_hash(data)
+    def _parse_sections(
+        self, data: Dict[str, Any], reported: Iterable[str] = (),
+    ) -> Tuple[JadeSkill, List[ValidationIssue], Set[str]]:
+        """
+        Parse each top-level section independently for validate_all.
 
-        has_errors = any(i.severity == ValidationSeverity.ERROR for i in issues)
-        return ValidationResult(
-            valid=not has_errors,
-            issues=issues,
-            skill_hash=skill_hash,
-            checked_at=time.time(),
+        Returns the skill (with placeholders for unusable sections), a
+        PARSE_ERROR per malformed section and the names of unusable sections.
+        Sections in `reported` already have a more specific error, so they
+        get no PARSE_ERROR.
+        """
+        issues: List[ValidationIssue] = []
+        broken: Set[str] = set()
+
+        def section(name: str, parse: Any, placeholder: Any) -> Any:
+            if name not in data:
+                broken.add(name)  # Already reported as MISSING_FIELD
+                return placeholder
+            try:
+                return parse(data[name])
+            except Exception as e:
+                broken.add(name)
+                if name in reported:
+                    return placeholder
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.ERROR,
+                    code=IssueCode.PARSE_ERROR,
+                    message=f"Failed to parse {name}: {e!r}",
+                    path=name,
+                ))
+                return placeholder
+
+        def string(value: Any) -> str:
+            if not isinstance(value, str):
+                raise TypeError(f"expected a string, got {type(value).__name__}")
+            return value
+
+        signatures = section(
+            "jade_signature",
+            lambda v: JadeSignature.from_dict(v) if v else None,
+            None,
+        ) if "jade_signature" in data else None
+        community = section(
+            "community_signatures",
+            lambda v: [CommunitySignature.from_dict(c) for c in v],
+            [],
+        ) if "community_signatures" in data else []
+
+        skill = JadeSkill(
+            jade_version=section("jade_version", string, ""),
+            skill_id=section("skill_id", string, ""),
+            metadata=section("metadata", SkillMetadata.from_dict, SkillMetadata("", "", "", "", [])),
+            trigger=section("trigger", Trigger.from_dict, Trigger(TriggerType.MANUAL, [])),
+            execution_dag=section("execution_dag", ExecutionDAG.from_dict, ExecutionDAG([], [], "", [])),
+            security=section("security", SecurityPolicy.from_dict, SecurityPolicy.from_dict({})),
+            input_schema=data.get("input_schema"),
+            output_schema=data.get("output_schema"),
+            mcp_compatible=data.get("mcp_compatible", False),
+            required_mcp_capabilities=data.get("required_mcp_capabilities", []),
+            jade_signature=signatures,
+            community_signatures=community,
+            raw_data=data,
         )
+        return skill, issues, broken
+
+    def _layer_mask(self, layers: Iterable[int]) -> Set[int]:
+        """Convert layer numbers into a set (ValidationLayer for built-ins), rejecting unknown ones."""
+        mask: Set[int] = set()
+        for layer in layers:
+            if layer in self.custom_layers:
+                mask.add(int(layer))
+                continue
+            try:
+                mask.add(ValidationLayer(layer))
+            except ValueError:
+                raise ValueError(
+                    f"Unknown validation layer: {layer!r}. Expected one of {self.layer_numbers}"
+                ) from None
+        return mask
+
+    def _in_layer(
+        self, layer: int, issues: List[ValidationIssue], run: _StreamRun,
+    ) -> List[ValidationIssue]:
+        """Tag issues with their layer and node, then apply suppressions."""
+        for issue in issues:
+            issue.layer = int(layer)
+            if issue.node_id is None:
+                issue.node_id = _path_node_id(issue.path, run.node_ids)
+            if any(s.matches(issue) for s in self._suppressions):
+                issue.severity = ValidationSeverity.INFO
+                issue.suppressed = True
+            emit(logger, logging.DEBUG, "issue_found", f"[{issue.code}] {issue.message}",
+                 skill_id=run.skill_id, layer=issue.layer, code=str(issue.code),
+                 severity=issue.severity.value, path=issue.path, node_id=issue.node_id)
+        return issues
+
+    def _layers_passed(self, issues: List[ValidationIssue], ran: List[int]) -> List[int]:
+        """Layers that ran without producing an error."""
+        failed = {i.layer for i in issues if i.severity == ValidationSeverity.ERROR}
+        return [int(layer) for layer in ran if int(layer) not in failed]
 
     def _check_required_fields(self, data: Dict[str, Any]) -> List[ValidationIssue]:
         """Check that all required top-level fields are present."""
@@ -200,28 +1392,89 @@ class JadeValidator:
             if field_name not in data:
                 issues.append(ValidationIssue(
                     severity=ValidationSeverity.ERROR,
-                    code="MISSING_FIELD",
+                    code=IssueCode.MISSING_FIELD,
                     message=f"Required field '{field_name}' is missing",
                     path=field_name,
                 ))
         return issues
 
+    def _check_unknown_fields(self, data: Dict[str, Any]) -> List[ValidationIssue]:
+        """Warn about keys the format does not define, suggesting the closest known one."""
+        issues: List[ValidationIssue] = []
+        for path, key, known in _unknown_fields(data):
+            close = difflib.get_close_matches(key, known, n=1)
+            hint = f", did you mean '{close[0]}'?" if close else ""
+            issues.append(ValidationIssue(
+                severity=ValidationSeverity.WARNING,
+                code=IssueCode.UNKNOWN_FIELD,
+                message=f"Unknown field '{key}'{hint}",
+                path=path,
+            ))
+        return issues
+
+    def _check_sandbox_level(self, data: Dict[str, Any]) -> List[ValidationIssue]:
+        """
+        An absent or empty sandbox_level falls back to strict, with a warning
+        so the choice is explicit; an unrecognized value is an error.
+        """
+        security = data.get("security")
+        if not isinstance(security, dict):
+            return []
+        level = security.get("sandbox_level")
+        levels = [member.value for member in SandboxLevel]
+        if level is None or level == "":
+            return [ValidationIssue(
+                severity=ValidationSeverity.WARNING,
+                code=IssueCode.SANDBOX_MISSING,
+                message=(
+                    f"security.sandbox_level is {'empty' if level == '' else 'not set'}; "
+                    f"defaulting to 'strict' (expected one of: {', '.join(levels)})"
+                ),
+                path="security.sandbox_level" if "sandbox_level" in security else "security",
+            )]
+        if level in levels:
+            return []
+        close = difflib.get_close_matches(level, levels, n=1) if isinstance(level, str) else []
+        hint = f", did you mean '{close[0]}'?" if close else f" (expected one of: {', '.join(levels)})"
+        return [ValidationIssue(
+            severity=ValidationSeverity.ERROR,
+            code=IssueCode.INVALID_SANDBOX_LEVEL,
+            message=f"Unknown sandbox level {level!r}{hint}",
+            path="security.sandbox_level",
+        )]
+
     def _check_version(self, skill: JadeSkill) -> List[ValidationIssue]:
-        """Validate JADE protocol version."""
+        """Validate JADE protocol version against SUPPORTED_JADE_VERSION_RANGE."""
         issues: List[ValidationIssue] = []
-        if not self.JADE_VERSION_PATTERN.match(skill.jade_version):
+        version = parse_semver(skill.jade_version)
+        low, high = (parse_semver(v) for v in SUPPORTED_JADE_VERSION_RANGE)
+        supported = f">={SUPPORTED_JADE_VERSION_RANGE[0]}, <{SUPPORTED_JADE_VERSION_RANGE[1]}"
+
+        if version is None:
             issues.append(ValidationIssue(
                 severity=ValidationSeverity.ERROR,
-                code="UNSUPPORTED_VERSION",
-                message=f"jade_version '{skill.jade_version}' must match pattern '1.x.x'. "
-                        f"Supported versions: {self.SUPPORTED_VERSIONS}",
+                code=IssueCode.UNSUPPORTED_VERSION,
+                message=f"jade_version '{skill.jade_version}' is not a valid semantic version. "
+                        f"Supported range: {supported}",
                 path="jade_version",
             ))
-        elif skill.jade_version not in self.SUPPORTED_VERSIONS:
+            return issues
+
+        # Pre-releases sort below their release: 1.1.0-rc1 is in range, 1.0.0-rc1 is not
+        precedence = semver_precedence(version)
+        if precedence >= semver_precedence(high):  # type: ignore[arg-type]
             issues.append(ValidationIssue(
                 severity=ValidationSeverity.WARNING,
-                code="UNSUPPORTED_VERSION",
-                message=f"jade_version '{skill.jade_version}' is not in supported versions: {self.SUPPORTED_VERSIONS}",
+                code=IssueCode.JADE_VERSION_TOO_NEW,
+                message=f"jade_version '{skill.jade_version}' is newer than this engine supports "
+                        f"({supported}); fields it introduces may be misinterpreted",
+                path="jade_version",
+            ))
+        if version[0] != low[0] or precedence < semver_precedence(low):  # type: ignore[arg-type,index]
+            issues.append(ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.UNSUPPORTED_VERSION,
+                message=f"jade_version '{skill.jade_version}' is outside the supported range {supported}",
                 path="jade_version",
             ))
         return issues
@@ -231,34 +1484,35 @@ class JadeValidator:
         issues: List[ValidationIssue] = []
         meta = skill.metadata
 
-        if not self.SKILL_ID_PATTERN.match(skill.skill_id):
+        if not self._skill_id_re.match(skill.skill_id):
             issues.append(ValidationIssue(
                 severity=ValidationSeverity.ERROR,
-                code="INVALID_SKILL_ID",
-                message=f"skill_id '{skill.skill_id}' must match pattern '^[a-z][a-z0-9_]{{2,63}}$'",
+                code=IssueCode.INVALID_SKILL_ID,
+                message=f"skill_id '{skill.skill_id}' must match pattern '{self._skill_id_policy.pattern}'",
                 path="skill_id",
             ))
 
         if not meta.name or len(meta.name) > 128:
             issues.append(ValidationIssue(
                 severity=ValidationSeverity.ERROR,
-                code="INVALID_METADATA_NAME",
+                code=IssueCode.INVALID_METADATA_NAME,
                 message="metadata.name must be 1-128 characters",
                 path="metadata.name",
             ))
 
-        if not self.VERSION_PATTERN.match(meta.version):
+        if parse_semver(meta.version) is None:
             issues.append(ValidationIssue(
                 severity=ValidationSeverity.ERROR,
-                code="INVALID_METADATA_VERSION",
-                message=f"metadata.version '{meta.version}' must match 'x.y.z'",
+                code=IssueCode.INVALID_METADATA_VERSION,
+                message=f"metadata.version '{meta.version}' must be a semantic version "
+                        f"(x.y.z, optionally with -pre-release and +build)",
                 path="metadata.version",
             ))
 
         if not meta.description or len(meta.description) > 1024:
             issues.append(ValidationIssue(
                 severity=ValidationSeverity.ERROR,
-                code="INVALID_METADATA_DESC",
+                code=IssueCode.INVALID_METADATA_DESC,
                 message="metadata.description must be 1-1024 characters",
                 path="metadata.description",
             ))
@@ -266,19 +1520,68 @@ class JadeValidator:
         if not meta.tags or len(meta.tags) > 10:
             issues.append(ValidationIssue(
                 severity=ValidationSeverity.ERROR,
-                code="INVALID_METADATA_TAGS",
+                code=IssueCode.INVALID_METADATA_TAGS,
                 message="metadata.tags must have 1-10 items",
                 path="metadata.tags",
             ))
 
-        if len(meta.tags) != len(set(meta.tags)):
+        return issues + self._check_tags(meta.tags)
+
+    def _check_skill_id_filename(self, skill: JadeSkill, source_path: str) -> List[ValidationIssue]:
+        """With SkillIdPolicy.match_filename, the id must name the file it is read from."""
+        if not self._skill_id_policy.match_filename or source_path == "<memory>":
+            return []
+        if skill_id_matches_path(skill.skill_id, source_path):
+            return []
+        return [ValidationIssue(
+            severity=ValidationSeverity.ERROR,
+            code=IssueCode.SKILL_ID_FILENAME_MISMATCH,
+            message=f"skill_id '{skill.skill_id}' does not match file {Path(source_path).as_posix()}",
+            path="skill_id",
+        )]
+
+    def _check_tags(self, tags: List[Any]) -> List[ValidationIssue]:
+        """Per-tag checks; duplicates compare trimmed, case-insensitive forms."""
+        issues: List[ValidationIssue] = []
+        seen: Set[str] = set()
+        duplicates: List[str] = []
+        for i, tag in enumerate(tags):
+            if not isinstance(tag, str):
+                continue
+            path = f"metadata.tags[{i}]"
+            key = tag.strip().lower()
+            if not key:
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.ERROR,
+                    code=IssueCode.EMPTY_TAG,
+                    message="metadata.tags must not contain empty tags",
+                    path=path,
+                ))
+                continue
+            if key in seen and key not in duplicates:
+                duplicates.append(key)
+            seen.add(key)
+            if len(tag) > MAX_TAG_LENGTH:
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.WARNING,
+                    code=IssueCode.TAG_TOO_LONG,
+                    message=f"Tag '{tag}' is longer than {MAX_TAG_LENGTH} characters",
+                    path=path,
+                ))
+            if any(c.isspace() for c in tag):
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.WARNING,
+                    code=IssueCode.TAG_WHITESPACE,
+                    message=f"Tag '{tag}' contains whitespace; use '-' or '_' between words",
+                    path=path,
+                ))
+        if duplicates:
             issues.append(ValidationIssue(
                 severity=ValidationSeverity.WARNING,
-                code="DUPLICATE_TAGS",
-                message="metadata.tags contains duplicate entries",
+                code=IssueCode.DUPLICATE_TAGS,
+                message=f"metadata.tags contains duplicate entries (ignoring case): {', '.join(duplicates)}",
                 path="metadata.tags",
             ))
-
         return issues
 
     def _check_trigger(self, skill: JadeSkill) -> List[ValidationIssue]:
@@ -289,13 +1592,217 @@ class JadeValidator:
         if not trigger.conditions:
             issues.append(ValidationIssue(
                 severity=ValidationSeverity.ERROR,
-                code="NO_TRIGGER_CONDITIONS",
+                code=IssueCode.NO_TRIGGER_CONDITIONS,
                 message="Trigger must have at least one condition",
                 path="trigger.conditions",
             ))
 
         return issues
 
+    def _check_node_params(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """Check node params against the action catalog's per-action param schemas."""
+        return [
+            ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.INVALID_NODE_PARAMS,
+                message=message,
+                path=f"execution_dag.nodes.{node_id}.params",
+            )
+            for node_id, message in validate_node_params(skill, self._action_schemas)
+        ]
+
+    def _check_actions(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """Every non-empty action must be an allowed atomic action; skipped with no action list."""
+        if not self._allowed_actions:
+            return []
+        return [
+            ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.UNKNOWN_ACTION,
+                message=message,
+                path=f"execution_dag.nodes.{node_id}.action",
+            )
+            for node_id, message in unknown_actions(skill, self._allowed_actions)
+        ]
+
+    def _check_params_without_action(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """Params on a node with no action are never used; only a warning, for placeholder nodes."""
+        return [
+            ValidationIssue(
+                severity=ValidationSeverity.WARNING,
+                code=IssueCode.PARAMS_WITHOUT_ACTION,
+                message=f"Node '{node.id}' has params ({', '.join(sorted(map(str, node.params)))}) "
+                        f"but no action, so they are never used",
+                path=f"execution_dag.nodes.{node.id}.params",
+            )
+            for node in skill.execution_dag.nodes
+            if not node.action and isinstance(node.params, dict) and node.params
+        ]
+
+    def _check_output_consistency(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """Warn when output_schema cannot match what the exit nodes' actions return."""
+        return [
+            ValidationIssue(
+                severity=ValidationSeverity.WARNING,
+                code=IssueCode.OUTPUT_SCHEMA_MISMATCH,
+                message=message,
+                path=path,
+            )
+            for path, message in validate_output_consistency(skill, self._action_outputs)
+        ]
+
+    def _check_retry_policies(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """A node's retry needs 1-5 attempts, a backoff of at most a minute and HTTP statuses."""
+        issues: List[ValidationIssue] = []
+        for node in skill.execution_dag.nodes:
+            retry = node.retry
+            if retry is None:
+                continue
+            problems = []
+            if not _is_int(retry.max_attempts) or not 1 <= retry.max_attempts <= MAX_RETRY_ATTEMPTS:
+                problems.append(
+                    f"max_attempts must be an integer from 1 to {MAX_RETRY_ATTEMPTS}, got {retry.max_attempts!r}"
+                )
+            if not _is_int(retry.backoff_ms) or not 0 <= retry.backoff_ms <= MAX_RETRY_BACKOFF_MS:
+                problems.append(
+                    f"backoff_ms must be an integer from 0 to {MAX_RETRY_BACKOFF_MS}, got {retry.backoff_ms!r}"
+                )
+            statuses = retry.retry_on_status
+            if not isinstance(statuses, list) or not all(
+                _is_int(s) and 100 <= s <= 599 for s in statuses
+            ):
+                problems.append(f"retry_on_status must be a list of HTTP status codes, got {statuses!r}")
+            for problem in problems:
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.ERROR,
+                    code=IssueCode.INVALID_RETRY,
+                    message=f"Node '{node.id}' retry: {problem}",
+                    path=f"execution_dag.nodes.{node.id}.retry",
+                ))
+        return issues
+
+    def _check_reserved_names(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """A node id that is a reserved reference root (templates.RESERVED_ROOTS) can't be referenced."""
+        issues: List[ValidationIssue] = []
+        for i, node in enumerate(skill.execution_dag.nodes):
+            if node.id in RESERVED_ROOTS:
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.ERROR,
+                    code=IssueCode.RESERVED_NAME,
+                    message=f"Node id '{node.id}' is reserved: '{{{{{node.id}.…}}}}' reads the "
+                            f"{'skill inputs' if node.id == INPUT_ROOT else 'environment'}, "
+                            f"never this node's output",
+                    path=f"execution_dag.nodes[{i}].id",
+                ))
+        return issues
+
+    def _check_identifiers(self, fields: Iterable[Tuple[str, Any]]) -> List[ValidationIssue]:
+        """
+        Identifier fields, as (path, value) pairs, must not carry characters
+        that break log lines and file paths or disguise what is displayed.
+        """
+        issues: List[ValidationIssue] = []
+        for path, value in fields:
+            problem = identifier_problem(value) if isinstance(value, str) else None
+            if problem:
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.ERROR,
+                    code=IssueCode.INVALID_IDENTIFIER,
+                    message=f"{path} contains a {problem}: {value!r}",
+                    path=path,
+                ))
+        return issues
+
+    def validate_io_schemas(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """
+        Validate input_schema and output_schema.
+
+        Two forms are accepted: JADE parameter lists (required_params /
+        optional_params for input, fields for output) or a JSON Schema with
+        a recognized 'type'. For 'type: object', properties must be an object
+        and required must list declared properties.
+        """
+        issues: List[ValidationIssue] = []
+        schemas = [
+            ("input_schema", skill.input_schema, ("required_params", "optional_params")),
+            ("output_schema", skill.output_schema, ("fields",)),
+        ]
+        for name, schema, list_keys in schemas:
+            if schema is None:
+                continue
+            if not isinstance(schema, dict):
+                issues.append(self._io_issue(
+                    name, f"{name} must be a JSON object, got {type(schema).__name__}"))
+            elif "type" in schema or "properties" in schema:
+                issues.extend(self._check_json_schema(name, schema))
+            elif any(key in schema for key in list_keys):
+                issues.extend(self._check_param_lists(name, schema, list_keys))
+            else:
+                issues.append(self._io_issue(
+                    name, f"{name} must declare a JSON Schema 'type' or one of "
+                          f"{', '.join(list_keys)}"))
+        return issues
+
+    def _check_json_schema(self, path: str, schema: Dict[str, Any]) -> List[ValidationIssue]:
+        issues: List[ValidationIssue] = []
+        schema_type = schema.get("type", "object")
+        if schema_type not in self.JSON_SCHEMA_TYPES:
+            issues.append(self._io_issue(
+                f"{path}.type", f"Unrecognized JSON Schema type: {schema_type!r}"))
+            return issues
+        if schema_type != "object":
+            return issues
+
+        properties = schema.get("properties", {})
+        if not isinstance(properties, dict):
+            issues.append(self._io_issue(
+                f"{path}.properties", "'properties' must be an object"))
+            properties = {}
+
+        required = schema.get("required")
+        if required is None:
+            return issues
+        if not isinstance(required, list) or not all(isinstance(r, str) for r in required):
+            issues.append(self._io_issue(
+                f"{path}.required", "'required' must be an array of strings"))
+            return issues
+        for name in required:
+            if name not in properties:
+                issues.append(self._io_issue(
+                    f"{path}.required", f"Required property '{name}' is not declared in properties"))
+        return issues
+
+    def _check_param_lists(
+        self, path: str, schema: Dict[str, Any], list_keys: tuple,
+    ) -> List[ValidationIssue]:
+        issues: List[ValidationIssue] = []
+        seen: Set[str] = set()
+        for key in list_keys:
+            params = schema.get(key, [])
+            if not isinstance(params, list):
+                issues.append(self._io_issue(f"{path}.{key}", f"'{key}' must be an array"))
+                continue
+            for i, param in enumerate(params):
+                item_path = f"{path}.{key}[{i}]"
+                if not isinstance(param, dict) or not isinstance(param.get("name"), str):
+                    issues.append(self._io_issue(item_path, "Each entry must be an object with a 'name'"))
+                    continue
+                if param.get("type") not in self.PARAM_TYPES:
+                    issues.append(self._io_issue(
+                        item_path, f"Parameter '{param['name']}' has unrecognized type {param.get('type')!r}"))
+                if param["name"] in seen:
+                    issues.append(self._io_issue(item_path, f"Duplicate parameter name '{param['name']}'"))
+                seen.add(param["name"])
+        return issues
+
+    def _io_issue(self, path: str, message: str) -> ValidationIssue:
+        return ValidationIssue(
+            severity=ValidationSeverity.ERROR,
+            code=IssueCode.INVALID_IO_SCHEMA,
+            message=message,
+            path=path,
+        )
+
     def _check_semantic_consistency(self, skill: JadeSkill) -> List[ValidationIssue]:
         """Cross-field semantic validation."""
         issues: List[ValidationIssue] = []
@@ -309,7 +1816,7 @@ class JadeValidator:
                 if node.action in network_actions:
                     issues.append(ValidationIssue(
                         severity=ValidationSeverity.WARNING,
-                        code="NETWORK_ACTION_NO_WHITELIST",
+                        code=IssueCode.NETWORK_ACTION_NO_WHITELIST,
                         message=f"Node '{node.id}' uses network action '{node.action}' "
                                 f"but network_whitelist is empty",
                         path=f"execution_dag.nodes.{node.id}",
@@ -322,7 +1829,7 @@ class JadeValidator:
                 if node.action in file_actions:
                     issues.append(ValidationIssue(
                         severity=ValidationSeverity.WARNING,
-                        code="FILE_ACTION_NO_PERMS",
+                        code=IssueCode.FILE_ACTION_NO_PERMS,
                         message=f"Node '{node.id}' uses file action '{node.action}' "
                                 f"but no file permissions are declared",
                         path=f"execution_dag.nodes.{node.id}",
@@ -334,17 +1841,284 @@ class JadeValidator:
             if retry_config and retry_config.get("max_count", 0) > 0:
                 issues.append(ValidationIssue(
                     severity=ValidationSeverity.WARNING,
-                    code="RETRY_MISMATCH",
+                    code=IssueCode.RETRY_MISMATCH,
                     message="execution_dag defines retries but security.max_retries is 0",
                     path="security.max_retries",
                 ))
 
         return issues
 
-    def _compute_skill_hash(self, data: Dict[str, Any]) -> str:
-        """Compute a deterministic SHA-256 hash of the skill content."""
-        canonical = json.dumps(data, sort_keys=True, ensure_ascii=True, separators=(",", ":"))
-        return hashlib.sha256(canonical.encode("utf-8")).hexdigest()
+    def _check_signature(
+        self, skill: JadeSkill, content: str, computed_hash: str, run: _StreamRun,
+    ) -> List[ValidationIssue]:
+        """Verify the root signature, if present, over the canonical content; records it in `run`."""
+        issues: List[ValidationIssue] = []
+        sig = skill.jade_signature
+        if sig is None:
+            return issues
+        record = SignatureRecord(
+            kind="root",
+            signer=sig.signer,
+            algorithm=sig.algorithm,
+            fingerprint=_fingerprint_or_empty(sig.public_key),
+            verified=False,
+            signed_at=sig.signed_at,
+            path="jade_signature",
+        )
+        run.signatures.append(record)
+
+        # A declared hash that disagrees with the content means the signature
+        # block was lifted from another file or the content was edited.
+        # One that is not '<algorithm>:<hex>' cannot be compared at all;
+        # otherwise the content is hashed again with the declared algorithm.
+        hash_format_error = None
+        if sig.content_hash:
+            try:
+                hash_algorithm, _ = parse_content_hash(sig.content_hash)
+            except ValueError as e:
+                hash_format_error = str(e)
+            else:
+                if hash_algorithm != DEFAULT_HASH_ALGORITHM:
+                    computed_hash = content_hash(content, hash_algorithm)
+        if hash_format_error is not None:
+            issues.append(ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.HASH_FORMAT_INVALID,
+                message=f"Declared content_hash {sig.content_hash!r} is malformed: {hash_format_error}",
+                path="jade_signature.content_hash",
+            ))
+        elif sig.content_hash and sig.content_hash != computed_hash:
+            issues.append(ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.HASH_MISMATCH,
+                message=f"Declared content_hash '{sig.content_hash}' does not match "
+                        f"computed '{computed_hash}'",
+                path="jade_signature.content_hash",
+            ))
+
+        try:
+            algorithm = SignatureAlgorithm.parse(sig.algorithm)
+        except ValueError:
+            algorithm = None
+        if algorithm in IMPLEMENTED_ALGORITHMS:
+            record.verified = self._root_verifies(sig, content, run)
+        if algorithm not in IMPLEMENTED_ALGORITHMS:
+            supported = ", ".join(sorted(a.value for a in IMPLEMENTED_ALGORITHMS))
+            issues.append(ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.UNSUPPORTED_ALGORITHM,
+                message=f"Signature algorithm '{sig.algorithm}' is not supported. "
+                        f"Supported: {supported}",
+                path="jade_signature.algorithm",
+            ))
+        elif not record.verified:
+            issues.append(ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.SIG_INVALID,
+                message=f"Signature by '{sig.signer or 'unknown'}' does not verify "
+                        f"against the skill content",
+                path="jade_signature.signature",
+            ))
+        elif self._key_registry is not None:
+            # A valid signature only proves possession of *a* key; pinning
+            # decides whether that key is one we trust
+            fingerprint = record.fingerprint
+            if fingerprint not in self._key_registry:
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.ERROR,
+                    code=IssueCode.UNKNOWN_SIGNER,
+                    message=f"Signature by '{sig.signer or 'unknown'}' uses key {fingerprint}, "
+                            f"which is not in the trusted key registry",
+                    path="jade_signature.public_key",
+                ))
+            else:
+                claimed, registered = key_role(sig.public_key), self._signer_role(sig.public_key)
+                if claimed not in (SignerRole.UNKNOWN, registered):
+                    issues.append(ValidationIssue(
+                        severity=ValidationSeverity.ERROR,
+                        code=IssueCode.SIGNER_ROLE_MISMATCH,
+                        message=f"Key prefix claims role {claimed.name.lower()}, but key {fingerprint} "
+                                f"is registered as {registered.name.lower()}",
+                        path="jade_signature.public_key",
+                    ))
+        return issues + self._check_freshness(
+            sig.signed_at, f"Signature by '{sig.signer or 'unknown'}'",
+            "jade_signature.signed_at", ValidationSeverity.ERROR,
+        )
+
+    def _signer_role(self, public_key: str) -> SignerRole:
+        """
+        The role the key registry grants `public_key`; UNKNOWN without a
+        registry, for unregistered keys and for roles SignerRole lacks.
+        The key's own 'jade-pk-<role>-' prefix is never trusted for this.
+        """
+        if self._key_registry is None:
+            return SignerRole.UNKNOWN
+        entry = self._key_registry.get(_fingerprint_or_empty(public_key))
+        if entry is None:
+            return SignerRole.UNKNOWN
+        try:
+            return SignerRole.parse(entry.role)
+        except ValueError:
+            return SignerRole.UNKNOWN
+
+    def _check_community_freshness(self, skill: JadeSkill) -> List[ValidationIssue]:
+        """Stale co-signatures warn and do not count toward the trust policy."""
+        issues: List[ValidationIssue] = []
+        for i, sig in enumerate(skill.community_signatures):
+            issues += self._check_freshness(
+                sig.signed_at, f"Community signature by '{sig.signer or 'unknown'}'",
+                f"community_signatures[{i}]", ValidationSeverity.WARNING,
+            )
+        return issues
+
+    def _check_freshness(
+        self, signed_at: str, what: str, path: str, severity: ValidationSeverity,
+    ) -> List[ValidationIssue]:
+        """Check signed_at against the FreshnessPolicy, if any."""
+        policy = self._freshness_policy
+        if policy is None:
+            return []
+        signed = parse_rfc3339(signed_at)
+        if signed is None:
+            return [ValidationIssue(
+                severity=severity,
+                code=IssueCode.SIG_INVALID_TIMESTAMP,
+                message=f"{what} has missing or non-RFC 3339 signed_at '{signed_at}'",
+                path=path,
+            )]
+        now = self._clock()
+        if signed - now > policy.clock_skew_s:
+            return [ValidationIssue(
+                severity=severity,
+                code=IssueCode.SIG_FUTURE_DATED,
+                message=f"{what} is dated {signed_at}, {signed - now:.0f}s in the future "
+                        f"(allowed clock skew {policy.clock_skew_s:.0f}s)",
+                path=path,
+            )]
+        if policy.max_age_s is not None and now - signed > policy.max_age_s:
+            return [ValidationIssue(
+                severity=severity,
+                code=IssueCode.SIG_EXPIRED,
+                message=f"{what} was made at {signed_at}, older than the maximum "
+                        f"age of {policy.max_age_s:.0f}s",
+                path=path,
+            )]
+        return []
+
+    def _verify_community_signatures(
+        self, skill: JadeSkill, content: str,
+    ) -> List[CommunitySigResult]:
+        """Verify every community co-signature. Failures are reported, never raised."""
+        results: List[CommunitySigResult] = []
+        payload = content.encode("utf-8")
+        for sig in skill.community_signatures:
+            fingerprint = _fingerprint_or_empty(sig.public_key)
+            results.append(CommunitySigResult(
+                signer=sig.signer,
+                fingerprint=fingerprint,
+                trust_level=sig.trust_level,
+                valid=bool(fingerprint) and verify_signature(payload, sig.signature, sig.public_key),
+            ))
+        return results
+
+    def _check_community_results(
+        self, results: List[CommunitySigResult],
+    ) -> List[ValidationIssue]:
+        """Invalid co-signatures warn rather than fail an otherwise valid skill."""
+        issues: List[ValidationIssue] = []
+        for i, res in enumerate(results):
+            try:
+                TrustLevel.parse(res.trust_level)
+            except ValueError:
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.ERROR,
+                    code=IssueCode.UNKNOWN_TRUST_LEVEL,
+                    message=f"Community signature by '{res.signer or 'unknown'}' has unknown "
+                            f"trust_level '{res.trust_level}'. Expected one of: "
+                            f"{', '.join(l.name.lower() for l in TrustLevel)}",
+                    path=f"community_signatures[{i}].trust_level",
+                ))
+            if not res.valid:
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.WARNING,
+                    code=IssueCode.COMMUNITY_SIG_INVALID,
+                    message=f"Community signature by '{res.signer or 'unknown'}' "
+                            f"does not verify against the skill content",
+                    path=f"community_signatures[{i}]",
+                ))
+        return issues
+
+    def _check_trust_policy(
+        self,
+        skill: JadeSkill,
+        results: List[CommunitySigResult],
+        signer_role: Optional[SignerRole],
+        stale: Optional[Set[str]] = None,
+    ) -> List[ValidationIssue]:
+        """
+        Enforce the configured TrustPolicy, if any.
+
+        `signer_role` is the role of the root signature, or None unless
+        that signature verified.
+        """
+        issues: List[ValidationIssue] = []
+        policy = self._trust_policy
+        if policy is None:
+            return issues
+
+        root_required = policy.require_root_signature or policy.min_signer_role > SignerRole.UNKNOWN
+        if root_required and signer_role is None:
+            issues.append(ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.SIGNATURE_REQUIRED,
+                message="Trust policy requires a valid root jade_signature",
+                path="jade_signature",
+            ))
+        elif signer_role is None and policy.require_signature_when:
+            triggered = [
+                risk for risk in self._security_engine.capability_summary(skill).risks
+                if risk.name in policy.require_signature_when
+            ]
+            if triggered:
+                issues.append(ValidationIssue(
+                    severity=ValidationSeverity.ERROR,
+                    code=IssueCode.SIGNATURE_REQUIRED,
+                    message=f"Trust policy requires a valid root jade_signature for skills granted "
+                            f"{', '.join(r.name for r in triggered)} "
+                            f"({'; '.join(r.detail for r in triggered)})",
+                    path="jade_signature",
+                ))
+        elif signer_role is not None and signer_role < policy.min_signer_role:
+            issues.append(ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.SIGNER_ROLE_TOO_LOW,
+                message=f"Trust policy requires a root signature with role >= "
+                        f"{policy.min_signer_role.name.lower()}, but the key is "
+                        f"{signer_role.name.lower()}",
+                path="jade_signature.public_key",
+            ))
+
+        qualified = 0
+        for i, res in enumerate(results):
+            if stale and f"community_signatures[{i}]" in stale:
+                continue
+            try:
+                level = TrustLevel.parse(res.trust_level)
+            except ValueError:
+                continue
+            if res.valid and level >= policy.min_trust_level:
+                qualified += 1
+        if qualified < policy.min_community_signers:
+            issues.append(ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code=IssueCode.TRUST_POLICY_UNMET,
+                message=f"Trust policy requires {policy.min_community_signers} valid community "
+                        f"signature(s) at trust_level >= {policy.min_trust_level.name.lower()}, "
+                        f"found {qualified}",
+                path="community_signatures",
+            ))
+        return issues
 
     def validate_batch(self, file_paths: List[str]) -> Dict[str, ValidationResult]:
         """Validate multiple skill files."""
@@ -352,3 +2126,21 @@ class JadeValidator:
         for path in file_paths:
             results[path] = self.validate_file(path)
         return results
+
+
+_default_validator: Optional[JadeValidator] = None
+
+
+def validate_str(text: str) -> str:
+    """
+    Validate a JSON skill string and return the ValidationResult as JSON.
+
+    A string-in, string-out entry point for embedders such as Pyodide in
+    the browser: it touches no files besides the bundled schema and action
+    catalog, which are loaded once and reused across calls.
+    """
+    global _default_validator
+    if _default_validator is None:
+        _default_validator = JadeValidator()
+    result = _default_validator.validate_str(text)
+    return json.dumps(result.to_dict(), ensure_ascii=False)
@@ -6,7 +6,11 @@ Commands:
     jadegate scan          Scan system for MCP servers and assess security
     jadegate proxy <cmd>   Run MCP server through JadeGate security proxy
     jadegate status        Show protection status
-    jadegate verify <file> Verify JADE skill file(s) (v1 compat)
+    jadegate verify <file> Verify JADE skill file(s) (v1 compat, --json for CI)
+    jadegate sign <file>   Sign a JADE skill file with an Ed25519 key
+    jadegate keygen        Generate an Ed25519 signing keypair
+    jadegate inspect <file> Show the content a signature covers and its hashes
+    jadegate diff <old> <new> Review what changed between two skill versions
     jadegate list          List registered skills (v1 compat)
     jadegate policy show   Show current security policy
     jadegate cert list     List tool certificates
@@ -16,7 +20,9 @@ Commands:
 from __future__ import annotations
 
 import argparse
+import contextlib
 import json
+import os
 import sys
 from pathlib import Path
 
@@ -37,12 +43,16 @@ class _C:
     RESET = "\033[0m"
 
 
-def _banner():
+class _NoColor(_C):
+    CYAN = GREEN = RED = YELLOW = DIM = BOLD = RESET = ""
+
+
+def _banner(c=_C):
     try:
         from jadegate import __version__
     except Exception:
         __version__ = "2.0.0"
-    return f"{_C.CYAN}💠 JadeGate{_C.RESET} {_C.DIM}v{__version__} — AI Tool Call Security Protocol{_C.RESET}"
+    return f"{c.CYAN}💠 JadeGate{c.RESET} {c.DIM}v{__version__} — AI Tool Call Security Protocol{c.RESET}"
 
 
 # ─── scan ────────────────────────────────────────────────────
@@ -343,41 +353,127 @@ def cmd_uninstall(args: argparse.Namespace):  # args required by argparse dispat
 
 # ─── verify (v1 compat) ─────────────────────────────────────
 
+# `jadegate verify -` reads the skill from standard input
+_STDIN = Path("-")
+
+# Exit status of `jadegate verify`. When files fail in different ways the
+# highest status wins, so a parse failure is never reported as invalid.
+EXIT_VALID = 0  # Every skill passed
+EXIT_INVALID = 1  # A skill has validation errors
+EXIT_USAGE = 2  # Bad arguments (argparse also exits with 2)
+EXIT_IO = 3  # A skill, key, baseline or previous version could not be read
+EXIT_PARSE = 4  # A file is not JSON, or not a JADE document
+
+VERIFY_EXIT_STATUS = """exit status:
+  0  every skill is valid
+  1  a skill has validation errors
+  2  usage error
+  3  a file could not be read
+  4  a file is not JSON, or not a JADE document"""
+
+# Error codes that mean the skill was never validated
+_IO_CODES = {"FILE_NOT_FOUND", "NOT_FOUND", "READ_ERROR"}
+_PARSE_CODES = {"INVALID_JSON", "DUPLICATE_KEY", "PARSE_ERROR"}
+
+
+def _exit_status(valid, error_codes):
+    """Exit status for one verified file, from its validity and error codes."""
+    if valid:
+        return EXIT_VALID
+    codes = set(error_codes)
+    if codes & _IO_CODES:
+        return EXIT_IO
+    if codes & _PARSE_CODES:
+        return EXIT_PARSE
+    return EXIT_INVALID
+
+
+def _result_status(result):
+    """Exit status for a ValidationResult."""
+    return _exit_status(result.valid, (i.code for i in result.errors))
+
+
+def _error_status(error):
+    """Exit status for an exception raised while reading an input file."""
+    return EXIT_IO if isinstance(error, OSError) else EXIT_PARSE
+
+
+def _verify_targets(file_path: str):
+    """Resolve a verify argument to the files it covers, or None if not found."""
+    if file_path == str(_STDIN):
+        return [_STDIN]
+    # 1. Try as absolute/relative path first
+    p = Path(file_path).resolve()
+    if not p.exists():
+        # 2. Try as skill name — search jade_skills/ inside the package
+        p = _find_skill_by_name(file_path)
+
+    if p is None or not p.exists():
+        return None
+
+    # Collect files: JSON for full validation, SKILL.md for content scan
+    if p.is_dir():
+        return list(p.glob("*.json")) + list(p.glob("SKILL.md"))
+    return [p]
+
+
 def cmd_verify(args):
     """Verify JADE skill files (v1 compatibility)."""
-    print(_banner())
-    print()
+    if not args.files:
+        # Nothing named: read a piped skill, but never wait on a terminal
+        if sys.stdin.isatty():
+            print(f"  {_C.RED}Error: no skill files given (use - to read stdin){_C.RESET}", file=sys.stderr)
+            sys.exit(EXIT_USAGE)
+        args.files = [str(_STDIN)]
+    if getattr(args, "quiet", False):
+        # Only the exit status reports the outcome; errors still go to stderr
+        with open(os.devnull, "w", encoding="utf-8") as devnull, contextlib.redirect_stdout(devnull):
+            _verify(args)
+    else:
+        _verify(args)
+
+
+def _verify(args):
+    """Run verify in the requested output format."""
+    if getattr(args, "format", None) == "sarif":
+        return _verify_sarif(args)
+    if getattr(args, "json", False) or getattr(args, "format", None) == "json":
+        return _verify_json(args)
+    return _verify_human(args)
+
+
+def _verify_human(args):
+    """Colored report per file, then a summary line."""
+    compact = getattr(args, "compact", False)
+    color = not getattr(args, "no_color", False) and sys.stdout.isatty()
+    c = _C if color else _NoColor
+    if not compact:
+        print(_banner(c))
+        print()
 
     try:
-        from jade_core.validator import JadeValidator
+        validator = _verify_validator(args)
+        from jade_core.report import ReportFormat, render
     except ImportError:
-        print(f"  {_C.RED}jade_core not available for v1 verification{_C.RESET}")
+        print(f"  {c.RED}jade_core not available for v1 verification{c.RESET}")
         sys.exit(1)
 
-    validator = JadeValidator()
+    baseline = _prepare_baseline(args, validator)
+    detached = _detached_signature(args)
     total = 0
     passed = 0
+    status = EXIT_VALID
 
     for file_path in args.files:
-        # 1. Try as absolute/relative path first
-        p = Path(file_path).resolve()
-        if not p.exists():
-            # 2. Try as skill name — search jade_skills/ inside the package
-            p = _find_skill_by_name(file_path)
-
-        if p is None or not p.exists():
-            print(f"  {_C.RED}❌ FAIL{_C.RESET} {file_path}")
+        files = _verify_targets(file_path)
+        if files is None:
+            print(f"  {c.RED}❌ FAIL{c.RESET} {file_path}")
             print(f"    [NOT_FOUND] No skill file found for: {file_path}")
-            print(f"    {_C.DIM}Try: jadegate list {file_path}  to search for matching skills{_C.RESET}")
+            print(f"    {c.DIM}Try: jadegate list {file_path}  to search for matching skills{c.RESET}")
             total += 1
+            status = max(status, EXIT_IO)
             continue
 
-        # Collect files: JSON for full validation, SKILL.md for content scan
-        if p.is_dir():
-            files = list(p.glob("*.json")) + list(p.glob("SKILL.md"))
-        else:
-            files = [p]
-
         for f in files:
             total += 1
 
@@ -386,47 +482,552 @@ def cmd_verify(args):
                 try:
                     content = f.read_text(encoding="utf-8")
                 except Exception as e:
-                    print(f"  {_C.RED}❌ FAIL{_C.RESET} {f.name}")
+                    print(f"  {c.RED}❌ FAIL{c.RESET} {f.name}")
                     print(f"    [READ_ERROR] {e}")
+                    status = max(status, EXIT_IO)
                     continue
 
-                issues = _scan_skill_content(content, verbose=True)
+                issues, hidden = _scan_at_least(_scan_skill_content(content, verbose=True), args)
                 critical = [(s, d) for s, d in issues if s == "CRITICAL"]
                 high = [(s, d) for s, d in issues if s == "HIGH"]
                 medium = [(s, d) for s, d in issues if s == "MEDIUM"]
                 low = [(s, d) for s, d in issues if s == "LOW"]
 
                 if critical or high:
-                    print(f"  {_C.RED}❌ FAIL{_C.RESET} {f.name}  ({len(critical)} critical, {len(high)} high)")
+                    status = max(status, EXIT_INVALID)
+                    print(f"  {c.RED}❌ FAIL{c.RESET} {f.name}  ({len(critical)} critical, {len(high)} high)")
                     for sev, desc in critical + high:
-                        print(f"    {_C.RED}[{sev}]{_C.RESET} {desc}")
+                        print(f"    {c.RED}[{sev}]{c.RESET} {desc}")
                     for sev, desc in medium:
-                        print(f"    {_C.YELLOW}[{sev}]{_C.RESET} {desc}")
+                        print(f"    {c.YELLOW}[{sev}]{c.RESET} {desc}")
                 elif medium:
                     passed += 1
-                    print(f"  {_C.YELLOW}⚠ WARN{_C.RESET} {f.name}  ({len(medium)} warnings)")
+                    print(f"  {c.YELLOW}⚠ WARN{c.RESET} {f.name}  ({len(medium)} warnings)")
                     for sev, desc in medium:
-                        print(f"    {_C.YELLOW}[{sev}]{_C.RESET} {desc}")
+                        print(f"    {c.YELLOW}[{sev}]{c.RESET} {desc}")
                 else:
                     passed += 1
                     if low:
-                        print(f"  {_C.GREEN}✅ PASS{_C.RESET} {f.name}  ({len(low)} info)")
+                        print(f"  {c.GREEN}✅ PASS{c.RESET} {f.name}  ({len(low)} info)")
                         for sev, desc in low:
-                            print(f"    {_C.DIM}[{sev}]{_C.RESET} {desc}")
+                            print(f"    {c.DIM}[{sev}]{c.RESET} {desc}")
                     else:
-                        print(f"  {_C.GREEN}✅ PASS{_C.RESET} {f.name}")
+                        print(f"  {c.GREEN}✅ PASS{c.RESET} {f.name}")
+                if hidden:
+                    counts = ", ".join(f"{count} {severity}" for severity, count in hidden.items())
+                    print(f"    {c.DIM}({counts} hidden){c.RESET}")
             else:
                 # JSON → full 5-layer JadeValidator
-                result = validator.validate_file(str(f))
+                _use_baseline(validator, baseline, args, f)
+                result = _validate_skill_file(validator, f, detached).ignoring(_ignored_codes(args))
                 if result.valid:
                     passed += 1
-                    print(f"  {_C.GREEN}✅ PASS{_C.RESET} {f.name}")
+                status = max(status, _result_status(result))
+                if compact:
+                    if not result.valid:
+                        print(render(result, ReportFormat.COMPACT, name=_display_name(f, full=True)))
+                    continue
+                print(render(result, ReportFormat.HUMAN, name=_display_name(f), color=color,
+                             timings=getattr(args, "timings", False)))
+
+    if not compact:
+        print(f"\n  {total} scanned, {c.GREEN}{passed} passed{c.RESET}, {c.RED}{total - passed} failed{c.RESET}")
+    if status != EXIT_VALID:
+        sys.exit(status)
+
+
+def _verify_validator(args):
+    """A validator configured by the verify options."""
+    from jade_core.models import SkillIdPolicy
+    from jade_core.validator import JadeValidator
+
+    match_filename = getattr(args, "check_filename", False)
+    if getattr(args, "namespaced_ids", False):
+        policy = SkillIdPolicy.namespaced(match_filename=match_filename)
+    else:
+        policy = SkillIdPolicy(match_filename=match_filename)
+    previous = None
+    if getattr(args, "previous", None):
+        try:
+            previous = _read_skill(Path(args.previous))
+        except (OSError, ValueError, KeyError, TypeError) as e:
+            print(f"  {_C.RED}Error: cannot read previous version {args.previous}: {e}{_C.RESET}", file=sys.stderr)
+            sys.exit(_error_status(e))
+    return JadeValidator(skill_id_policy=policy, previous_version=previous, min_severity=_min_severity(args))
+
+
+def _detached_signature(args):
+    """(signature, public key) read from --sig and --pubkey, or None without them."""
+    sig_path, key_path = getattr(args, "sig", None), getattr(args, "pubkey", None)
+    if not sig_path and not key_path:
+        return None
+    problem, status = None, EXIT_USAGE
+    if not (sig_path and key_path):
+        problem = "--sig and --pubkey must be given together"
+    elif len(args.files) != 1:
+        problem = "--sig verifies a single skill file"
+    else:
+        try:
+            return (Path(sig_path).read_text(encoding="utf-8").strip(),
+                    Path(key_path).read_text(encoding="utf-8").strip())
+        except OSError as e:
+            problem, status = f"cannot read detached signature: {e}", EXIT_IO
+    print(f"  {_C.RED}Error: {problem}{_C.RESET}", file=sys.stderr)
+    sys.exit(status)
+
+
+def _validate_skill_file(validator, skill_file, detached):
+    """
+    Validate one JSON skill, against a detached signature if one was given.
+    A file that exists but cannot be read fails with READ_ERROR.
+    """
+    from jade_core.models import ValidationIssue, ValidationResult, ValidationSeverity
+
+    stdin = skill_file == _STDIN
+    try:
+        if detached is None:
+            return validator.validate_reader(sys.stdin.buffer) if stdin else validator.validate_file(str(skill_file))
+        signature, public_key = detached
+        data = sys.stdin.buffer.read() if stdin else skill_file.read_bytes()
+    except OSError as e:
+        issue = ValidationIssue(ValidationSeverity.ERROR, "READ_ERROR", f"Cannot read skill: {e}", "")
+        return ValidationResult(valid=False, issues=[issue])
+    return validator.verify_detached(data, signature, public_key, source_path=_display_name(skill_file, full=True))
+
+
+def _display_name(skill_file, full=False):
+    """How a verified file is labelled: '<stdin>', its name, or (full) its path."""
+    if skill_file == _STDIN:
+        return "<stdin>"
+    return str(skill_file) if full else skill_file.name
+
+
+def _baseline_key(baseline_path, skill_file):
+    """Skill files are keyed relative to the baseline, so it can be committed."""
+    base = Path(baseline_path).resolve().parent
+    try:
+        return Path(skill_file).resolve().relative_to(base).as_posix()
+    except ValueError:
+        return Path(skill_file).resolve().as_posix()
+
+
+def _prepare_baseline(args, validator):
+    """
+    Load the --baseline file, recording it first if it does not exist yet.
+
+    Returns {skill file: [Suppression, ...]}, or None without --baseline.
+    Every current error and warning is recorded, scoped to its node when it
+    has one; delete the file to record a fresh baseline.
+    """
+    from jade_core.models import Suppression, ValidationSeverity
+
+    path = getattr(args, "baseline", None)
+    if not path:
+        return None
+    if not Path(path).exists():
+        files = {}
+        for file_path in args.files:
+            for f in _verify_targets(file_path) or []:
+                if f.suffix != ".json":
+                    continue
+                validator.set_suppressions([])
+                entries = []
+                for issue in validator.validate_file(str(f)).issues:
+                    if issue.severity == ValidationSeverity.INFO:
+                        continue
+                    entry = Suppression(issue.code, issue.node_id).to_dict()
+                    if entry not in entries:
+                        entries.append(entry)
+                if entries:
+                    files[_baseline_key(path, f)] = entries
+        Path(path).write_text(
+            json.dumps({"jade_baseline_version": "1.0.0", "files": files}, indent=2) + "\n",
+            encoding="utf-8",
+        )
+    try:
+        data = json.loads(Path(path).read_text(encoding="utf-8"))
+    except (OSError, json.JSONDecodeError) as e:
+        print(f"  {_C.RED}Error: cannot read baseline {path}: {e}{_C.RESET}", file=sys.stderr)
+        sys.exit(_error_status(e))
+    return {
+        key: [Suppression.from_dict(s) for s in entries]
+        for key, entries in data.get("files", {}).items()
+    }
+
+
+def _use_baseline(validator, baseline, args, skill_file):
+    """Suppress the baselined issues of one skill file, if a baseline is in use."""
+    if baseline is not None:
+        validator.set_suppressions(baseline.get(_baseline_key(args.baseline, skill_file), []))
+
+
+def _ignored_codes(args):
+    """Issue codes from repeated or comma-separated --ignore options."""
+    codes = set()
+    for value in getattr(args, "ignore", None) or []:
+        codes.update(c.strip() for c in value.split(",") if c.strip())
+    return codes
+
+
+def _min_severity(args):
+    """The --min-severity threshold; INFO (everything) without it."""
+    from jade_core.models import ValidationSeverity
+
+    return ValidationSeverity.parse(getattr(args, "min_severity", None) or "info")
+
+
+# SKILL.md content-scan severities → ValidationIssue severities
+_SCAN_SEVERITY = {"CRITICAL": "error", "HIGH": "error", "MEDIUM": "warning", "LOW": "info"}
+
+
+def _scan_severity(scan_severity):
+    """The ValidationSeverity of a SKILL.md content-scan finding."""
+    from jade_core.models import ValidationSeverity
+
+    return ValidationSeverity.parse(_SCAN_SEVERITY.get(scan_severity, "warning"))
+
+
+def _scan_at_least(found, args):
+    """
+    SKILL.md content-scan findings at or above --min-severity, and how many
+    below it were left out, by severity, as in ValidationResult.hidden.
+    """
+    from jade_core.models import ValidationSeverity
+
+    minimum = _min_severity(args)
+    kept, hidden = [], {}
+    for sev, desc in found:
+        severity = _scan_severity(sev)
+        if severity >= minimum:
+            kept.append((sev, desc))
+        else:
+            hidden[severity.value] = hidden.get(severity.value, 0) + 1
+    return kept, {s.value: hidden[s.value] for s in ValidationSeverity if s.value in hidden}
+
+
+def _verify_json(args):
+    """
+    Machine-readable verify: one JSON object on stdout, non-zero exit on failure.
+
+    A single file prints its ValidationResult; several files print
+    {"valid": ..., "results": [...]}, so `jq '.valid'` works either way.
+    """
+    try:
+        validator = _verify_validator(args)
+    except ImportError:
+        print(json.dumps({"valid": False, "error": "jade_core not available"}))
+        sys.exit(1)
+
+    baseline = _prepare_baseline(args, validator)
+    detached = _detached_signature(args)
+    results = []
+
+    for file_path in args.files:
+        files = _verify_targets(file_path)
+        if files is None:
+            results.append({
+                "file": file_path,
+                "valid": False,
+                "layers_passed": [],
+                "issues": [{
+                    "layer": None,
+                    "severity": "error",
+                    "code": "NOT_FOUND",
+                    "message": f"No skill file found for: {file_path}",
+                    "path": "",
+                }],
+            })
+            continue
+
+        for f in files:
+            if f.suffix == ".md" or f.name == "SKILL.md":
+                hidden = {}
+                try:
+                    content = f.read_text(encoding="utf-8")
+                except Exception as e:
+                    issues = [{"layer": None, "severity": "error", "code": "READ_ERROR",
+                               "message": str(e), "path": ""}]
                 else:
-                    print(f"  {_C.RED}❌ FAIL{_C.RESET} {f.name}")
-                    for issue in result.errors:
-                        print(f"    [{issue.code}] {issue.message}")
+                    found, hidden = _scan_at_least(_scan_skill_content(content), args)
+                    issues = [
+                        {"layer": 3, "severity": _scan_severity(sev).value,
+                         "code": f"CONTENT_{sev}", "message": desc, "path": ""}
+                        for sev, desc in found
+                    ]
+                results.append({
+                    "file": str(f),
+                    "valid": not any(i["severity"] == "error" for i in issues),
+                    "layers_passed": [],
+                    "issues": issues,
+                    "hidden_counts": hidden,
+                })
+            else:
+                _use_baseline(validator, baseline, args, f)
+                entry = _validate_skill_file(validator, f, detached).ignoring(_ignored_codes(args)).to_dict()
+                entry["file"] = _display_name(f, full=True)
+                results.append(entry)
+
+    if len(results) == 1:
+        report = results[0]
+    else:
+        report = {"valid": all(r["valid"] for r in results), "results": results}
+    print(json.dumps(report, indent=2, ensure_ascii=False))
+    status = max(
+        _exit_status(r["valid"], (i["code"] for i in r["issues"] if i["severity"] == "error"))
+        for r in results
+    )
+    if status != EXIT_VALID:
+        sys.exit(status)
+
+
+def _verify_sarif(args):
+    """Code-scanning verify: one SARIF 2.1.0 log on stdout, non-zero exit on failure."""
+    try:
+        validator = _verify_validator(args)
+        from jade_core.models import ValidationIssue, ValidationResult, ValidationSeverity
+        from jade_core.report import sarif_log
+    except ImportError:
+        print(json.dumps({"valid": False, "error": "jade_core not available"}))
+        sys.exit(1)
+
+    baseline = _prepare_baseline(args, validator)
+    detached = _detached_signature(args)
+    results = []
+
+    for file_path in args.files:
+        files = _verify_targets(file_path)
+        if files is None:
+            issue = ValidationIssue(ValidationSeverity.ERROR, "NOT_FOUND",
+                                    f"No skill file found for: {file_path}", "")
+            results.append((file_path, ValidationResult(valid=False, issues=[issue])))
+            continue
+        for f in files:
+            if f.suffix == ".md" or f.name == "SKILL.md":
+                try:
+                    found, _ = _scan_at_least(_scan_skill_content(f.read_text(encoding="utf-8")), args)
+                    issues = [ValidationIssue(_scan_severity(sev), f"CONTENT_{sev}", desc, "")
+                              for sev, desc in found]
+                except Exception as e:
+                    issues = [ValidationIssue(ValidationSeverity.ERROR, "READ_ERROR", str(e), "")]
+                valid = not any(i.severity == ValidationSeverity.ERROR for i in issues)
+                results.append((_sarif_uri(f), ValidationResult(valid=valid, issues=issues)))
+            else:
+                _use_baseline(validator, baseline, args, f)
+                result = _validate_skill_file(validator, f, detached).ignoring(_ignored_codes(args))
+                results.append((_sarif_uri(f), result))
+
+    print(json.dumps(sarif_log(results), indent=2, ensure_ascii=False))
+    status = max(_result_status(result) for _, result in results)
+    if status != EXIT_VALID:
+        sys.exit(status)
+
+
+def _sarif_uri(skill_file):
+    """Artifact URI for SARIF: relative to the working directory when possible."""
+    if skill_file == _STDIN:
+        return "stdin"
+    try:
+        return skill_file.resolve().relative_to(Path.cwd().resolve()).as_posix()
+    except ValueError:
+        return skill_file.resolve().as_uri()
+
+
+# ─── sign ────────────────────────────────────────────────────
+
+def _read_private_key(key_path: str) -> str:
+    """Read a private key file: a bare (optionally 'jade-sk-') key or {"private_key": ...}."""
+    text = Path(key_path).read_text(encoding="utf-8").strip()
+    if text.startswith("{"):
+        return json.loads(text)["private_key"]
+    return text
+
+
+def cmd_sign(args):
+    """Sign a JADE skill file in place (or to --output)."""
+    from jade_core.crypto import HashAlgorithm, sign_skill
+
+    src = Path(args.file)
+    try:
+        data = json.loads(src.read_text(encoding="utf-8"))
+    except (OSError, json.JSONDecodeError) as e:
+        print(f"  {_C.RED}❌ Cannot read skill {src}: {e}{_C.RESET}")
+        sys.exit(1)
+    try:
+        private_key = _read_private_key(args.key)
+        signed = sign_skill(data, private_key, signer=args.signer or "", role=args.role,
+                            hash_algorithm=HashAlgorithm(getattr(args, "hash", None) or "sha256"))
+    except (OSError, KeyError, ValueError) as e:
+        print(f"  {_C.RED}❌ Cannot load private key {args.key}: {e}{_C.RESET}")
+        sys.exit(1)
+
+    dest = Path(args.output) if args.output else src
+    dest.write_text(json.dumps(signed, indent=2, ensure_ascii=False) + "\n", encoding="utf-8")
+    sig = signed["jade_signature"]
+    print(f"  {_C.GREEN}✅ Signed{_C.RESET} {dest}")
+    print(f"    {_C.DIM}content_hash: {sig['content_hash']}{_C.RESET}")
+    print(f"    {_C.DIM}public_key:   {sig['public_key']}{_C.RESET}")
+
+
+def cmd_keygen(args):
+    """Generate an Ed25519 keypair (seeded from the OS CSPRNG)."""
+    from jade_core.crypto import generate_keypair, key_fingerprint
+
+    try:
+        public_key, private_key = generate_keypair(args.prefix)
+    except ValueError as e:
+        print(f"  {_C.RED}❌ {e}{_C.RESET}")
+        sys.exit(1)
+    fingerprint = key_fingerprint(public_key)
+
+    print(f"  {_C.BOLD}Public key:{_C.RESET}  {public_key}")
+    print(f"  {_C.BOLD}Fingerprint:{_C.RESET} {fingerprint}")
+    if args.output:
+        out = Path(args.output)
+        if out.exists() and not args.force:
+            print(f"  {_C.RED}❌ {out} already exists (use --force to overwrite){_C.RESET}")
+            sys.exit(1)
+        key_file = {"private_key": private_key, "public_key": public_key, "fingerprint": fingerprint}
+        if args.force and out.exists():
+            out.unlink()
+        try:
+            # Created owner-only, so the key is never readable by others, even briefly
+            fd = os.open(out, os.O_WRONLY | os.O_CREAT | os.O_EXCL, 0o600)
+        except FileExistsError:
+            print(f"  {_C.RED}❌ {out} already exists (use --force to overwrite){_C.RESET}")
+            sys.exit(1)
+        except OSError as e:
+            print(f"  {_C.RED}❌ Cannot write {out}: {e}{_C.RESET}")
+            sys.exit(1)
+        with os.fdopen(fd, "w", encoding="utf-8") as f:
+            f.write(json.dumps(key_file, indent=2) + "\n")
+        print(f"  {_C.GREEN}✅ Private key written to {out}{_C.RESET}")
+        print(f"    {_C.DIM}Sign with: jadegate sign <skill.json> --key {out}{_C.RESET}")
+    else:
+        print(f"  {_C.BOLD}Private key:{_C.RESET} {private_key}")
+        print(f"    {_C.YELLOW}Keep the private key secret; use --output to save it to a file{_C.RESET}")
+
+
+def cmd_inspect(args):
+    """Show what Layer 5 hashes and verifies, to debug a failing signature."""
+    from jade_core.crypto import inspect_signature
+
+    src = Path(args.file)
+    try:
+        data = json.loads(src.read_text(encoding="utf-8-sig"))
+        report = inspect_signature(data)
+    except (OSError, ValueError) as e:
+        print(f"  {_C.RED}❌ Cannot inspect {src}: {e}{_C.RESET}")
+        sys.exit(1)
+
+    if args.json:
+        print(json.dumps(report.to_dict(), indent=2, ensure_ascii=False))
+        return
+    if args.content:
+        # Exactly the signed bytes, so two copies can be diffed directly
+        sys.stdout.write(report.signed_content)
+        return
+
+    stripped = ", ".join(report.stripped_fields) or "none"
+    print(f"  {_C.BOLD}{src}{_C.RESET}")
+    print(f"    signed content: {len(report.signed_content.encode('utf-8'))} bytes "
+          f"{_C.DIM}(excludes: {stripped}; --content prints it){_C.RESET}")
+    print(f"    computed hash:  {report.computed_hash}")
+    if not report.public_key and not report.declared_hash:
+        print(f"    {_C.YELLOW}⚠️  No jade_signature{_C.RESET}")
+        return
+    mark = f"{_C.GREEN}✅{_C.RESET}" if report.hash_matches else f"{_C.RED}❌{_C.RESET}"
+    print(f"    declared hash:  {report.declared_hash or '-'} {mark}")
+    print(f"    public key:     {report.public_key or '-'}")
+    print(f"    fingerprint:    {report.fingerprint or '-'}")
+    print(f"    algorithm:      {report.algorithm}")
+    if report.signature_valid is None:
+        print(f"    signature:      {_C.YELLOW}not checked (unsupported algorithm){_C.RESET}")
+    elif report.signature_valid:
+        print(f"    signature:      {_C.GREEN}✅ verifies{_C.RESET}")
+    else:
+        print(f"    signature:      {_C.RED}❌ does not verify{_C.RESET}")
+        sys.exit(1)
+
+
+def cmd_diff(args):
+    """Semantic diff of two skill versions, escalations first."""
+    from jade_core.diff import diff_skills
+
+    skills = []
+    for src in (Path(args.old), Path(args.new)):
+        try:
+            skills.append(_read_skill(src))
+        except (OSError, ValueError, KeyError, TypeError) as e:
+            print(f"  {_C.RED}❌ Cannot read skill {src}: {e}{_C.RESET}")
+            sys.exit(1)
+    change = diff_skills(*skills)
+
+    if args.json:
+        print(json.dumps(change.to_dict(), indent=2, ensure_ascii=False))
+    else:
+        for line in change.summary().splitlines():
+            color = _C.RED if line.startswith(("PRIVILEGE", "  !")) else ""
+            print(f"  {color}{line}{_C.RESET if color else ''}")
+    if args.fail_on_escalation and change.privilege_escalating:
+        sys.exit(1)
 
-    print(f"\n  {total} scanned, {_C.GREEN}{passed} passed{_C.RESET}, {_C.RED}{total - passed} failed{_C.RESET}")
+
+def _read_skill(src):
+    """Parse a skill file into a JadeSkill without validating it."""
+    from jade_core.models import JadeSkill
+
+    return JadeSkill.from_dict(json.loads(src.read_text(encoding="utf-8-sig")))
+
+
+# ─── stats ───────────────────────────────────────────────────
+
+def cmd_stats(args):
+    """Print a skill's DAG metrics as JSON, for catalog dashboards."""
+    from jade_core.dag import DAGAnalyzer
+
+    src = Path(args.file)
+    try:
+        skill = _read_skill(src)
+    except (OSError, ValueError, KeyError, TypeError) as e:
+        print(f"  {_C.RED}❌ Cannot read skill {src}: {e}{_C.RESET}", file=sys.stderr)
+        sys.exit(1)
+    metrics = DAGAnalyzer().get_metrics(skill.execution_dag)
+    report = {"file": str(src), "skill_id": skill.skill_id, **metrics.to_dict()}
+    print(json.dumps(report, indent=2, ensure_ascii=False))
+
+
+# ─── capabilities ────────────────────────────────────────────
+
+def cmd_capabilities(args):
+    """What a skill may do at runtime, with a risk score, for review."""
+    from jade_core.security import SecurityEngine
+
+    src = Path(args.file)
+    try:
+        skill = _read_skill(src)
+    except (OSError, ValueError, KeyError, TypeError) as e:
+        print(f"  {_C.RED}❌ Cannot read skill {src}: {e}{_C.RESET}", file=sys.stderr)
+        sys.exit(1)
+    summary = SecurityEngine().capability_summary(skill)
+
+    if args.json:
+        print(json.dumps(summary.to_dict(), indent=2, ensure_ascii=False))
+        return
+
+    def listed(values):
+        return ", ".join(values) if values else f"{_C.DIM}none{_C.RESET}"
+
+    limit = summary.max_execution_time_ms
+    color = _C.RED if summary.risk_score >= 50 else _C.YELLOW if summary.risks else _C.GREEN
+    print(f"  {_C.BOLD}{summary.skill_id}{_C.RESET}  {color}risk {summary.risk_score}/100{_C.RESET}")
+    print(f"    network:  {listed(summary.network_hosts)}")
+    print(f"    env vars: {listed(summary.env_vars)}")
+    print(f"    reads:    {listed(summary.file_read)}")
+    print(f"    writes:   {listed(summary.file_write)}")
+    print(f"    sandbox:  {summary.sandbox_level}")
+    print(f"    max time: {f'{limit}ms' if limit is not None else 'no limit'}")
+    for risk in summary.risks:
+        print(f"    {_C.YELLOW}⚠️  {risk.name} (+{risk.weight}): {risk.detail}{_C.RESET}")
 
 
 # ─── list ────────────────────────────────────────────────────
@@ -773,10 +1374,87 @@ def main():
     p_uninstall.set_defaults(func=cmd_uninstall)
 
     # verify (v1 compat)
-    p_verify = sub.add_parser("verify", help="Verify JADE skill file(s)")
-    p_verify.add_argument("files", nargs="+")
+    p_verify = sub.add_parser("verify", help="Verify JADE skill file(s)", epilog=VERIFY_EXIT_STATUS,
+                              formatter_class=argparse.RawDescriptionHelpFormatter)
+    p_verify.add_argument("files", nargs="*",
+                          help="Skill files, directories or names; - or none (when piped) reads stdin")
+    p_verify.add_argument("--json", action="store_true", help="Print results as a single JSON object")
+    p_verify.add_argument("--format", choices=["human", "json", "sarif"], default="human",
+                          help="Output format; sarif emits one SARIF 2.1.0 log for code scanning")
+    p_verify.add_argument("--timings", action="store_true", help="Print per-layer validation time")
+    p_verify.add_argument("--quiet", "--silent", action="store_true",
+                          help="Print nothing on stdout; report only through the exit status")
+    p_verify.add_argument("--compact", action="store_true",
+                          help="No banner or summary; JSON skills print only failures, one line per issue")
+    p_verify.add_argument("--no-color", action="store_true",
+                          help="Never color the report (default: color only on a terminal)")
+    p_verify.add_argument("--ignore", action="append", metavar="CODE",
+                          help="Drop issues with this code, e.g. SEC_WILDCARD_NETWORK (repeatable)")
+    p_verify.add_argument("--min-severity", choices=["info", "warning", "error"], default="info",
+                          help="Report only issues at or above this severity (default: info)")
+    p_verify.add_argument("--baseline", metavar="FILE",
+                          help="Fail only on issues not recorded in FILE (recorded on first run)")
+    p_verify.add_argument("--previous", metavar="FILE",
+                          help="Trusted earlier version; flag any policy it did not grant "
+                               "(acknowledge with --ignore SEC_POLICY_RELAXED)")
+    p_verify.add_argument("--sig", metavar="SIGFILE",
+                          help="Verify against this base64 signature instead of the embedded jade_signature")
+    p_verify.add_argument("--pubkey", metavar="KEYFILE",
+                          help="Public key for --sig (base64, PEM or multibase)")
+    p_verify.add_argument("--namespaced-ids", action="store_true",
+                          help="Require skill_id in 'namespace/name' form")
+    p_verify.add_argument("--check-filename", action="store_true",
+                          help="Require skill_id to match the file name (and namespace directory)")
     p_verify.set_defaults(func=cmd_verify)
 
+    # sign
+    p_sign = sub.add_parser("sign", help="Sign a JADE skill file")
+    p_sign.add_argument("file", help="Skill JSON file to sign")
+    p_sign.add_argument("--key", required=True, help="Private key file")
+    p_sign.add_argument("--signer", help="Signer name recorded in jade_signature")
+    p_sign.add_argument("--role", choices=["root", "ci"],
+                        help="Record the public key as jade-pk-<role>-<base64>")
+    p_sign.add_argument("--hash", choices=["sha256", "sha512", "blake2b"], default="sha256",
+                        help="Algorithm of the declared content_hash (default: sha256)")
+    p_sign.add_argument("--output", "-o", help="Write the signed skill here instead of in place")
+    p_sign.set_defaults(func=cmd_sign)
+
+    # keygen
+    p_keygen = sub.add_parser("keygen", help="Generate an Ed25519 signing keypair")
+    p_keygen.add_argument("--prefix", choices=["root", "ci"],
+                          help="Emit the public key as jade-pk-<prefix>-<base64>")
+    p_keygen.add_argument("--output", "-o", help="Write the keypair to this file (mode 600)")
+    p_keygen.add_argument("--force", action="store_true", help="Overwrite an existing key file")
+    p_keygen.set_defaults(func=cmd_keygen)
+
+    # inspect
+    p_inspect = sub.add_parser("inspect", help="Show the content a skill signature covers")
+    p_inspect.add_argument("file", help="Skill JSON file")
+    p_inspect.add_argument("--json", action="store_true", help="Print the report as JSON")
+    p_inspect.add_argument("--content", action="store_true",
+                           help="Print only the canonical signed content, for diffing")
+    p_inspect.set_defaults(func=cmd_inspect)
+
+    # diff
+    p_diff = sub.add_parser("diff", help="Review what changed between two versions of a skill")
+    p_diff.add_argument("old", help="Previous skill JSON file")
+    p_diff.add_argument("new", help="Updated skill JSON file")
+    p_diff.add_argument("--json", action="store_true", help="Print the diff as JSON")
+    p_diff.add_argument("--fail-on-escalation", action="store_true",
+                        help="Exit with status 1 if the update grants more privileges")
+    p_diff.set_defaults(func=cmd_diff)
+
+    # stats
+    p_stats = sub.add_parser("stats", help="Print a skill's DAG metrics as JSON")
+    p_stats.add_argument("file", help="Skill JSON file")
+    p_stats.set_defaults(func=cmd_stats)
+
+    # capabilities
+    p_caps = sub.add_parser("capabilities", help="Summarize what a skill may do, with a risk score")
+    p_caps.add_argument("file", help="Skill JSON file")
+    p_caps.add_argument("--json", action="store_true", help="Print the summary as JSON")
+    p_caps.set_defaults(func=cmd_capabilities)
+
     # list
     p_list = sub.add_parser("list", help="List registered skills")
     p_list.add_argument("keyword", nargs="?", help="Filter by keyword")
@@ -184,3 +184,36 @@ def tmp_skills_dir(tmp_path):
 def make_skill(**overrides):
     """Public helper to create skill dicts in tests."""
     return _make_minimal_skill(**overrides)
+
+
+def sign_skill(skill_dict, keypair, signer="test-signer"):
+    """Attach a root jade_signature to a skill dict, signed by keypair."""
+    from jade_core.crypto import canonical_json, content_hash
+    unsigned = {k: v for k, v in skill_dict.items()
+                if k not in ("jade_signature", "community_signatures")}
+    content = canonical_json(unsigned)
+    skill_dict["jade_signature"] = {
+        "signer": signer,
+        "algorithm": "ed25519",
+        "public_key": keypair.public_key,
+        "content_hash": content_hash(content),
+        "signature": keypair.sign(content.encode("utf-8")),
+        "signed_at": "2026-02-21T00:00:00Z",
+    }
+    return skill_dict
+
+
+def cosign_skill(skill_dict, keypair, signer="co-signer", trust_level="community"):
+    """Append a community co-signature to a skill dict."""
+    from jade_core.crypto import canonical_json
+    unsigned = {k: v for k, v in skill_dict.items()
+                if k not in ("jade_signature", "community_signatures")}
+    content = canonical_json(unsigned).encode("utf-8")
+    skill_dict.setdefault("community_signatures", []).append({
+        "signer": signer,
+        "public_key": keypair.public_key,
+        "signature": keypair.sign(content),
+        "trust_level": trust_level,
+        "signed_at": "2026-02-21T00:00:00Z",
+    })
+    return skill_dict
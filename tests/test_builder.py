@@ -0,0 +1,88 @@
+"""
+Test suite for the JADE SkillBuilder.
+Tests fluent construction, incremental checks and build-time validation.
+"""
+
+import pytest
+from jade_core.builder import SkillBuildError, SkillBuilder
+from jade_core.errors import JadeError
+from jade_core.models import JadeSkill, RetryPolicy, SandboxLevel
+from jade_core.validator import JadeValidator
+
+
+def weather_builder():
+    return (
+        SkillBuilder("weather_lookup")
+        .metadata(name="Weather", description="Current weather for a city",
+                  author="test-author", tags=["weather"])
+        .trigger("task.type", "equals", "weather")
+        .node("fetch", "http_get", {"url": "https://api.example.com/{{input.city}}"},
+              retry=RetryPolicy(max_attempts=2, backoff_ms=100))
+        .node("done", "return_result", {"result": "{{fetch.output.body}}"})
+        .edge("fetch", "done")
+        .whitelist("api.example.com")
+    )
+
+
+class TestSkillBuilderBuild:
+    """build() returns a skill the validator accepts."""
+
+    def test_build_valid_skill(self):
+        skill = weather_builder().build()
+        assert isinstance(skill, JadeSkill)
+        assert skill.execution_dag.entry_node == "fetch"
+        assert skill.execution_dag.exit_node == ["done"]
+        assert skill.execution_dag.nodes[0].retry.max_attempts == 2
+        assert skill.security.sandbox_level == SandboxLevel.STRICT
+
+    def test_built_document_passes_all_layers(self):
+        result = JadeValidator().validate_dict(weather_builder().to_dict())
+        assert result.valid, f"Errors: {[i.message for i in result.errors]}"
+
+    def test_cyclic_dag_fails_at_build(self):
+        builder = (
+            weather_builder()
+            .node("parse", "json_parse", {"input": "{{fetch.output.body}}"})
+            .edge("fetch", "parse")
+            .edge("parse", "fetch")
+        )
+        with pytest.raises(SkillBuildError) as exc:
+            builder.build()
+        assert "DAG_CYCLE_DETECTED" in exc.value.codes
+        assert isinstance(exc.value, JadeError)
+
+    def test_schema_errors_fail_at_build(self):
+        builder = SkillBuilder("Bad-ID").node("only", "return_result")
+        with pytest.raises(SkillBuildError) as exc:
+            builder.build()
+        assert {"INVALID_SKILL_ID", "NO_TRIGGER_CONDITIONS"} <= set(exc.value.codes)
+
+
+class TestSkillBuilderIncremental:
+    """Mistakes a single call can detect raise immediately."""
+
+    def test_duplicate_node(self):
+        with pytest.raises(ValueError, match="Duplicate"):
+            weather_builder().node("fetch", "json_parse")
+
+    def test_edge_to_unknown_node(self):
+        with pytest.raises(ValueError, match="Unknown node 'ghost'"):
+            weather_builder().edge("fetch", "ghost")
+
+    def test_bad_sandbox_level(self):
+        with pytest.raises(ValueError):
+            weather_builder().sandbox("lenient")
+
+    def test_malformed_whitelist_entry(self):
+        with pytest.raises(ValueError, match="Malformed whitelist entry"):
+            weather_builder().whitelist("api..example.com")
+
+    def test_conditional_edge_recorded(self):
+        data = (
+            weather_builder()
+            .node("fallback", "return_error", {"message": "failed"})
+            .edge("fetch", "fallback", condition="failure")
+            .to_dict()
+        )
+        assert {"from": "fetch", "to": "fallback", "condition": "failure"} in data["execution_dag"]["edges"]
+        assert data["execution_dag"]["exit_node"] == ["done", "fallback"]
@@ -0,0 +1,253 @@
+"""
+Test suite for the jadegate command line.
+Runs the CLI in a subprocess, as CI pipelines do.
+"""
+
+import json
+import os
+import pytest
+import stat
+import subprocess
+import sys
+from pathlib import Path
+from jade_core.crypto import JadeKeyPair, signable_content
+from tests.conftest import make_skill, sign_skill
+
+REPO_ROOT = Path(__file__).resolve().parent.parent
+
+
+def jadegate(*args, stdin=b""):
+    return subprocess.run(
+        [sys.executable, "-m", "jadegate.cli", *args],
+        input=stdin, capture_output=True, cwd=REPO_ROOT,
+    )
+
+
+class TestVerifyStdin:
+    """`jadegate verify -` and a bare `jadegate verify` read a piped skill."""
+
+    def test_valid_skill_from_pipe(self):
+        run = jadegate("verify", "-", stdin=json.dumps(make_skill()).encode("utf-8"))
+        assert run.returncode == 0, run.stdout.decode()
+        assert "PASS <stdin>" in run.stdout.decode()
+
+    def test_invalid_skill_from_pipe(self):
+        skill = make_skill()
+        skill["execution_dag"]["edges"].append({"from": "step_one", "to": "step_one"})
+        run = jadegate("verify", "--json", stdin=json.dumps(skill).encode("utf-8"))
+        assert run.returncode == 1
+        report = json.loads(run.stdout)
+        assert report["file"] == "<stdin>"
+        assert "DAG_SELF_LOOP" in {i["code"] for i in report["issues"]}
+
+    def test_malformed_json_from_pipe(self):
+        run = jadegate("verify", "--compact", "-", stdin=b"{not json")
+        assert run.returncode == 4
+        assert run.stdout.decode().startswith("<stdin>:1:2: error: [INVALID_JSON]")
+
+
+class TestVerifyExitStatus:
+    """Each class of failure has its own exit status."""
+
+    def write(self, tmp_path, name, skill):
+        path = tmp_path / name
+        path.write_text(skill if isinstance(skill, str) else json.dumps(skill), encoding="utf-8")
+        return str(path)
+
+    def cyclic(self):
+        skill = make_skill()
+        skill["execution_dag"]["edges"].append({"from": "step_two", "to": "step_one"})
+        return skill
+
+    def injected(self):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][0]["params"]["input"] = "os.system('rm -rf /')"
+        return skill
+
+    def test_valid(self, tmp_path):
+        assert jadegate("verify", self.write(tmp_path, "ok.json", make_skill())).returncode == 0
+
+    def test_missing_file(self, tmp_path):
+        assert jadegate("verify", str(tmp_path / "missing.json")).returncode == 3
+
+    def test_bad_json(self, tmp_path):
+        assert jadegate("verify", self.write(tmp_path, "bad.json", "{not json")).returncode == 4
+
+    def test_cyclic_dag(self, tmp_path):
+        path = self.write(tmp_path, "cyclic.json", self.cyclic())
+        for fmt in ("human", "json", "sarif"):
+            assert jadegate("verify", "--format", fmt, path).returncode == 1, fmt
+
+    def test_injection(self, tmp_path):
+        assert jadegate("verify", self.write(tmp_path, "injected.json", self.injected())).returncode == 1
+
+    def test_usage_error(self, tmp_path):
+        path = self.write(tmp_path, "ok.json", make_skill())
+        assert jadegate("verify", "--sig", path, path).returncode == 2
+        assert jadegate("verify", "--no-such-flag", path).returncode == 2
+
+    def test_highest_status_wins(self, tmp_path):
+        bad = self.write(tmp_path, "bad.json", "{not json")
+        cyclic = self.write(tmp_path, "cyclic.json", self.cyclic())
+        assert jadegate("verify", cyclic, bad).returncode == 4
+        assert jadegate("verify", "--json", bad, cyclic).returncode == 4
+
+    def test_quiet(self, tmp_path):
+        for skill, status in ((make_skill(), 0), (self.cyclic(), 1), ("{not json", 4)):
+            path = self.write(tmp_path, "skill.json", skill)
+            for flag in ("--quiet", "--silent"):
+                for fmt in ("human", "json"):
+                    run = jadegate("verify", flag, "--format", fmt, path)
+                    assert (run.returncode, run.stdout) == (status, b""), (flag, fmt)
+
+
+class TestVerifyColor:
+    """Piped or --no-color output carries no ANSI escape codes."""
+
+    def test_no_escape_codes_when_piped(self, tmp_path):
+        skill = tmp_path / "skill.json"
+        skill.write_text(json.dumps(make_skill()), encoding="utf-8")
+        doc = tmp_path / "SKILL.md"
+        doc.write_text("# Weather\n\nFetches the forecast.\n", encoding="utf-8")
+        targets = (str(skill), str(doc), str(tmp_path / "missing.json"))
+        for args in ((), ("--no-color",)):
+            run = jadegate("verify", *args, *targets)
+            out = run.stdout.decode()
+            assert "JadeGate" in out and "NOT_FOUND" in out and "scanned" in out
+            assert "\033[" not in out, args
+
+
+class TestVerifyMinSeverity:
+    """`--min-severity` hides issues below a threshold without changing the verdict."""
+
+    def warned(self):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][0]["params"]["hint"] = "password"
+        return skill
+
+    def codes(self, skill, *args):
+        run = jadegate("verify", "--json", *args, "-", stdin=json.dumps(skill).encode("utf-8"))
+        return run.returncode, [(i["severity"], i["code"]) for i in json.loads(run.stdout)["issues"]]
+
+    def test_default_reports_warnings(self):
+        assert self.codes(self.warned()) == (0, [("warning", "SEC_DATA_EXFIL_RISK")])
+
+    def test_error_threshold_hides_warnings(self):
+        assert self.codes(self.warned(), "--min-severity", "error") == (0, [])
+        run = jadegate("verify", "--json", "--min-severity", "error", "-",
+                       stdin=json.dumps(self.warned()).encode("utf-8"))
+        assert json.loads(run.stdout)["hidden_counts"] == {"warning": 1}
+        skill = self.warned()
+        skill["execution_dag"]["edges"].append({"from": "step_one", "to": "step_one"})
+        status, codes = self.codes(skill, "--min-severity", "error")
+        assert status == 1 and ("error", "DAG_SELF_LOOP") in codes
+        assert all(severity == "error" for severity, _ in codes)
+
+    def test_unknown_severity_is_usage_error(self):
+        run = jadegate("verify", "--min-severity", "fatal", "-", stdin=json.dumps(make_skill()).encode("utf-8"))
+        assert run.returncode == 2
+
+
+class TestInspect:
+    """`jadegate inspect` shows the signed content and whether the signature verifies."""
+
+    def write(self, tmp_path, skill):
+        path = tmp_path / "skill.json"
+        path.write_text(json.dumps(skill), encoding="utf-8")
+        return str(path)
+
+    def test_signed_skill(self, tmp_path):
+        keypair = JadeKeyPair.generate()
+        path = self.write(tmp_path, sign_skill(make_skill(), keypair))
+        run = jadegate("inspect", path)
+        assert run.returncode == 0, run.stdout.decode()
+        out = run.stdout.decode()
+        assert "✅ verifies" in out and keypair.public_key in out
+        report = json.loads(jadegate("inspect", "--json", path).stdout)
+        assert report["signature_valid"] is True and report["hash_matches"] is True
+        assert report["stripped_fields"] == ["jade_signature"]
+
+    def test_tampered_signed_skill(self, tmp_path):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        skill["metadata"]["description"] += " (edited)"
+        run = jadegate("inspect", self.write(tmp_path, skill))
+        assert run.returncode == 1
+        assert "does not verify" in run.stdout.decode()
+
+    def test_unsigned_skill(self, tmp_path):
+        path = self.write(tmp_path, make_skill())
+        run = jadegate("inspect", path)
+        assert run.returncode == 0, run.stdout.decode()
+        assert "No jade_signature" in run.stdout.decode()
+        report = json.loads(jadegate("inspect", "--json", path).stdout)
+        assert report["signature_valid"] is None and report["declared_hash"] == ""
+        content = jadegate("inspect", "--content", path).stdout.decode("utf-8")
+        assert content == signable_content(make_skill())
+
+    def test_missing_file(self, tmp_path):
+        assert jadegate("inspect", str(tmp_path / "missing.json")).returncode == 1
+
+
+class TestStats:
+    """`jadegate stats` prints DAG metrics as JSON."""
+
+    def test_stats_json(self, tmp_path):
+        path = tmp_path / "skill.json"
+        path.write_text(json.dumps(make_skill()), encoding="utf-8")
+        run = jadegate("stats", str(path))
+        assert run.returncode == 0, run.stderr.decode()
+        report = json.loads(run.stdout)
+        assert report["skill_id"] == make_skill()["skill_id"]
+        assert report["node_count"] == 2 and report["edge_count"] == 1
+        assert report["max_depth"] == 1 and report["connected"] is True
+
+    def test_unreadable_skill(self, tmp_path):
+        path = tmp_path / "broken.json"
+        path.write_text("{}", encoding="utf-8")
+        run = jadegate("stats", str(path))
+        assert run.returncode == 1
+        assert run.stdout == b""
+
+
+class TestCapabilities:
+    """`jadegate capabilities` summarizes what a skill may do."""
+
+    def test_json_summary(self, tmp_path):
+        skill = make_skill()
+        skill["security"]["network_whitelist"] = ["*"]
+        skill["security"]["sandbox_level"] = "standard"
+        path = tmp_path / "skill.json"
+        path.write_text(json.dumps(skill), encoding="utf-8")
+        run = jadegate("capabilities", "--json", str(path))
+        assert run.returncode == 0, run.stderr.decode()
+        summary = json.loads(run.stdout)
+        assert summary["network_hosts"] == ["*"]
+        assert summary["risk_score"] == 50
+        assert [r["name"] for r in summary["risks"]] == ["wildcard_egress", "relaxed_sandbox"]
+
+    def test_human_summary(self, tmp_path):
+        path = tmp_path / "skill.json"
+        path.write_text(json.dumps(make_skill()), encoding="utf-8")
+        out = jadegate("capabilities", str(path)).stdout.decode()
+        assert "risk 0/100" in out
+        assert "max time: 10000ms" in out
+
+
+class TestKeygen:
+    """`jadegate keygen --output` writes an owner-only key file."""
+
+    @pytest.mark.skipif(os.name != "posix", reason="POSIX file modes")
+    def test_key_file_is_owner_only(self, tmp_path):
+        out = tmp_path / "signing.key"
+        run = jadegate("keygen", "--output", str(out))
+        assert run.returncode == 0, run.stderr.decode()
+        assert stat.S_IMODE(out.stat().st_mode) == 0o600
+        assert json.loads(out.read_text(encoding="utf-8"))["private_key"]
+
+    def test_existing_file_needs_force(self, tmp_path):
+        out = tmp_path / "signing.key"
+        out.write_text("keep me", encoding="utf-8")
+        assert jadegate("keygen", "--output", str(out)).returncode == 1
+        assert out.read_text(encoding="utf-8") == "keep me"
+        assert jadegate("keygen", "--force", "--output", str(out)).returncode == 0
+        assert "private_key" in json.loads(out.read_text(encoding="utf-8"))
@@ -4,8 +4,11 @@ Tests structural integrity, acyclicity, reachability, etc.
 """
 
 import pytest
-from jade_core.dag import DAGAnalyzer
+from jade_core.conditions import MAX_CONDITION_DEPTH, evaluate_condition
+from jade_core.dag import DEFAULT_NODE_TIMEOUT_MS, DAGAnalyzer, node_timeout_ms
+from jade_core.errors import CycleError
 from jade_core.models import JadeSkill, ValidationSeverity
+from jade_core.validator import JadeValidator
 from tests.conftest import make_skill
 
 
@@ -76,6 +79,70 @@ class TestDAGEdgeReferences:
         issues = dag_analyzer.validate(skill)
         assert any(i.code == "DAG_INVALID_EDGE_DST" for i in issues)
 
+    def test_undeclared_source_does_not_connect_target(self, dag_analyzer):
+        """A single edge from an undeclared node leaves its target orphaned."""
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["edges"] = [{"from": "ghost_node", "to": "step_two"}]
+        issues = dag_analyzer.validate(JadeSkill.from_dict(skill_dict))
+        by_code = {i.code: i for i in issues}
+        assert by_code["DAG_INVALID_EDGE_SRC"].path == "execution_dag.edges[0].from"
+        orphan = by_code["DAG_NO_INCOMING"]
+        assert orphan.path == "execution_dag.nodes.step_two"
+        assert "only from undeclared 'ghost_node'" in orphan.message
+        # The undeclared node itself is never reported as an orphan
+        assert not any("ghost_node" in i.path for i in issues if i.code in ("DAG_NO_INCOMING", "DAG_DEAD_END"))
+
+    def test_undeclared_target_leaves_dead_end(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["nodes"].append({"id": "step_three", "action": "json_parse", "params": {}})
+        skill_dict["execution_dag"]["edges"].append({"from": "step_one", "to": "step_three"})
+        skill_dict["execution_dag"]["edges"].append({"from": "step_three", "to": "ghost_node"})
+        issues = dag_analyzer.validate(JadeSkill.from_dict(skill_dict))
+        [dead_end] = [i for i in issues if i.code == "DAG_DEAD_END"]
+        assert dead_end.path == "execution_dag.nodes.step_three"
+        assert "only to undeclared 'ghost_node'" in dead_end.message
+
+    def test_truly_orphaned_node(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["nodes"].append({"id": "loner", "action": "json_parse", "params": {}})
+        issues = dag_analyzer.validate(JadeSkill.from_dict(skill_dict))
+        orphan = [i for i in issues if i.code in ("DAG_NO_INCOMING", "DAG_DEAD_END")]
+        assert {i.path for i in orphan} == {"execution_dag.nodes.loner"}
+        assert all("undeclared" not in i.message for i in orphan)
+        assert not any(i.code.startswith("DAG_INVALID_EDGE") for i in issues)
+
+
+class TestDAGDuplicateEdges:
+    """Test detection of repeated edges."""
+
+    def test_duplicate_edge_warned(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["edges"].append({"from": "step_one", "to": "step_two"})
+        skill = JadeSkill.from_dict(skill_dict)
+        dups = [i for i in dag_analyzer.validate(skill) if i.code == "DAG_DUPLICATE_EDGE"]
+        assert len(dups) == 1
+        assert dups[0].severity == ValidationSeverity.WARNING
+        assert dups[0].message == "Duplicate edge: step_one -> step_two"
+        assert dups[0].path == "execution_dag.edges[1]"
+
+    def test_edges_differing_by_condition_are_distinct(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["edges"] = [
+            {"from": "step_one", "to": "step_two", "condition": "step_one.output.ok == true"},
+            {"from": "step_one", "to": "step_two", "condition": "step_one.output.retry == true"},
+        ]
+        skill = JadeSkill.from_dict(skill_dict)
+        assert not any(i.code == "DAG_DUPLICATE_EDGE" for i in dag_analyzer.validate(skill))
+
+    def test_same_condition_is_duplicate(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["edges"] = [
+            {"from": "step_one", "to": "step_two", "condition": "success"},
+            {"from": "step_one", "to": "step_two", "condition": "success"},
+        ]
+        skill = JadeSkill.from_dict(skill_dict)
+        assert any(i.code == "DAG_DUPLICATE_EDGE" for i in dag_analyzer.validate(skill))
+
 
 class TestDAGAcyclicity:
     """Test cycle detection."""
@@ -105,7 +172,68 @@ class TestDAGAcyclicity:
         )
         skill = JadeSkill.from_dict(skill_dict)
         issues = dag_analyzer.validate(skill)
-        assert any(i.code == "DAG_CYCLE_DETECTED" for i in issues)
+        loops = [i for i in issues if i.code == "DAG_SELF_LOOP"]
+        assert len(loops) == 1
+        assert loops[0].message == "Self-loop edge on node: step_one"
+        # Reported precisely, not folded into the generic cycle message
+        assert not any(i.code == "DAG_CYCLE_DETECTED" for i in issues)
+
+    def test_self_loop_alongside_other_dag_issues(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["edges"] = [
+            {"from": "step_two", "to": "step_two"},  # self-loop, step_two now unreachable
+        ]
+        skill = JadeSkill.from_dict(skill_dict)
+        codes = {i.code for i in dag_analyzer.validate(skill)}
+        assert "DAG_SELF_LOOP" in codes
+        assert "DAG_UNREACHABLE_FROM_ROOT" in codes
+
+    def test_all_distinct_cycles_reported(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["nodes"] = [
+            {"id": n, "action": "json_parse", "params": {}}
+            for n in ("node_a", "node_b", "node_c", "node_d", "node_e", "node_f")
+        ]
+        skill_dict["execution_dag"]["edges"] = [
+            {"from": "node_a", "to": "node_b"},
+            {"from": "node_b", "to": "node_c"},
+            {"from": "node_c", "to": "node_a"},  # first cycle
+            {"from": "node_c", "to": "node_d"},
+            {"from": "node_d", "to": "node_e"},
+            {"from": "node_e", "to": "node_d"},  # second cycle
+            {"from": "node_e", "to": "node_f"},
+        ]
+        skill_dict["execution_dag"]["entry_node"] = "node_a"
+        skill_dict["execution_dag"]["exit_node"] = ["node_f"]
+        skill = JadeSkill.from_dict(skill_dict)
+        cycles = [i.message for i in dag_analyzer.validate(skill) if i.code == "DAG_CYCLE_DETECTED"]
+        assert cycles == [
+            "Cycle detected: node_a -> node_b -> node_c -> node_a",
+            "Cycle detected: node_d -> node_e -> node_d",
+        ]
+        assert dag_analyzer.find_cycles(skill.execution_dag) == [
+            ["node_a", "node_b", "node_c", "node_a"],
+            ["node_d", "node_e", "node_d"],
+        ]
+
+    def test_component_larger_than_cycle_is_described(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["nodes"] = [
+            {"id": n, "action": "json_parse", "params": {}}
+            for n in ("node_a", "node_b", "node_c")
+        ]
+        skill_dict["execution_dag"]["edges"] = [
+            {"from": "node_a", "to": "node_b"},
+            {"from": "node_b", "to": "node_a"},
+            {"from": "node_b", "to": "node_c"},
+            {"from": "node_c", "to": "node_b"},
+        ]
+        skill_dict["execution_dag"]["entry_node"] = "node_a"
+        skill_dict["execution_dag"]["exit_node"] = ["node_c"]
+        skill = JadeSkill.from_dict(skill_dict)
+        cycles = [i.message for i in dag_analyzer.validate(skill) if i.code == "DAG_CYCLE_DETECTED"]
+        assert len(cycles) == 1
+        assert "3 nodes in strongly connected component: node_a, node_b, node_c" in cycles[0]
 
     def test_acyclic_graph_passes(self, dag_analyzer, minimal_skill_dict):
         skill = JadeSkill.from_dict(minimal_skill_dict)
@@ -137,6 +265,132 @@ class TestDAGReachability:
         reach_issues = [i for i in issues if i.code == "DAG_UNREACHABLE_NODE"]
         assert len(reach_issues) == 0
 
+    def test_node_reachable_only_through_cycle(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"] = {
+            "nodes": [
+                {"id": "node_a", "action": "json_parse", "params": {}},
+                {"id": "node_b", "action": "json_parse", "params": {}},
+                {"id": "node_c", "action": "return_result", "params": {}},
+            ],
+            "edges": [
+                {"from": "node_b", "to": "node_c"},
+                {"from": "node_c", "to": "node_b"},
+            ],
+            "entry_node": "node_a",
+            "exit_node": ["node_a"],
+        }
+        skill = JadeSkill.from_dict(skill_dict)
+        issues = [i for i in dag_analyzer.validate(skill) if i.code == "DAG_UNREACHABLE_FROM_ROOT"]
+        assert [i.message for i in issues] == ["Unreachable node: node_b", "Unreachable node: node_c"]
+        assert all(i.severity == ValidationSeverity.ERROR for i in issues)
+
+    def test_detached_cycle_reported_once_per_node(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["nodes"] += [
+            {"id": "x", "action": "json_parse", "params": {}},
+            {"id": "y", "action": "json_parse", "params": {}},
+        ]
+        skill_dict["execution_dag"]["edges"] += [{"from": "x", "to": "y"}, {"from": "y", "to": "x"}]
+        skill = JadeSkill.from_dict(skill_dict)
+        issues = dag_analyzer.validate(skill)
+        per_node = [(i.path, i.code) for i in issues if i.code.startswith("DAG_UNREACHABLE")]
+        assert per_node == [
+            ("execution_dag.nodes.x", "DAG_UNREACHABLE_FROM_ROOT"),
+            ("execution_dag.nodes.y", "DAG_UNREACHABLE_FROM_ROOT"),
+        ]
+        assert [i.code for i in issues].count("DAG_CYCLE_DETECTED") == 1
+
+    def test_no_root_reported(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["edges"].append({"from": "step_two", "to": "step_one"})
+        skill = JadeSkill.from_dict(skill_dict)
+        codes = [i.code for i in dag_analyzer.validate(skill)]
+        assert "DAG_NO_ROOT" in codes
+        assert "DAG_CYCLE_DETECTED" in codes
+        assert "DAG_UNREACHABLE_FROM_ROOT" not in codes
+
+    def test_empty_action_with_outgoing_edges_warns(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["nodes"][0]["action"] = ""
+        skill = JadeSkill.from_dict(skill_dict)
+        issues = [i for i in dag_analyzer.validate(skill) if i.code == "DAG_EMPTY_ACTION"]
+        assert len(issues) == 1
+        assert issues[0].severity == ValidationSeverity.WARNING
+        assert "step_one" in issues[0].message
+
+    def test_empty_action_on_exit_node_not_flagged(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["nodes"][1]["action"] = ""
+        skill = JadeSkill.from_dict(skill_dict)
+        assert not any(i.code == "DAG_EMPTY_ACTION" for i in dag_analyzer.validate(skill))
+
+    def test_valid_dag_has_no_root_issues(self, dag_analyzer, minimal_skill_dict):
+        skill = JadeSkill.from_dict(minimal_skill_dict)
+        codes = {i.code for i in dag_analyzer.validate(skill)}
+        assert not codes & {"DAG_NO_ROOT", "DAG_UNREACHABLE_FROM_ROOT"}
+
+
+class TestDAGRootsAndSinks:
+    """Test root/sink discovery and the multiple-roots policy."""
+
+    def _diamond(self):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"] = {
+            "nodes": [
+                {"id": "start", "action": "json_parse", "params": {}},
+                {"id": "left", "action": "json_extract", "params": {}},
+                {"id": "right", "action": "json_extract", "params": {}},
+                {"id": "merge", "action": "return_result", "params": {}},
+            ],
+            "edges": [
+                {"from": "start", "to": "left"},
+                {"from": "start", "to": "right"},
+                {"from": "left", "to": "merge"},
+                {"from": "right", "to": "merge"},
+            ],
+            "entry_node": "start",
+            "exit_node": ["merge"],
+        }
+        return skill_dict
+
+    def test_diamond_has_one_root_and_one_sink(self, dag_analyzer):
+        skill = JadeSkill.from_dict(self._diamond())
+        assert dag_analyzer.get_roots(skill.execution_dag) == ["start"]
+        assert dag_analyzer.get_sinks(skill.execution_dag) == ["merge"]
+        issues = dag_analyzer.validate(skill)
+        assert not any(i.code == "DAG_MULTIPLE_ROOTS" for i in issues)
+
+    def test_multiple_roots_warned_by_default(self, dag_analyzer):
+        skill_dict = self._diamond()
+        skill_dict["execution_dag"]["nodes"].append({"id": "side", "action": "json_parse", "params": {}})
+        skill_dict["execution_dag"]["edges"].append({"from": "side", "to": "merge"})
+        skill = JadeSkill.from_dict(skill_dict)
+        assert dag_analyzer.get_roots(skill.execution_dag) == ["start", "side"]
+        issues = [i for i in dag_analyzer.validate(skill) if i.code == "DAG_MULTIPLE_ROOTS"]
+        assert len(issues) == 1
+        assert issues[0].severity == ValidationSeverity.WARNING
+        assert "start, side" in issues[0].message
+
+    def test_multiple_roots_policy(self):
+        skill_dict = self._diamond()
+        skill_dict["execution_dag"]["nodes"].append({"id": "side", "action": "json_parse", "params": {}})
+        skill_dict["execution_dag"]["edges"].append({"from": "side", "to": "merge"})
+        skill = JadeSkill.from_dict(skill_dict)
+
+        strict = DAGAnalyzer(multiple_roots=ValidationSeverity.ERROR).validate(skill)
+        assert any(i.code == "DAG_MULTIPLE_ROOTS" and i.severity == ValidationSeverity.ERROR
+                   for i in strict)
+        relaxed = DAGAnalyzer(multiple_roots=None).validate(skill)
+        assert not any(i.code == "DAG_MULTIPLE_ROOTS" for i in relaxed)
+
+    def test_isolated_node_is_root_and_sink(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["nodes"].append({"id": "alone", "action": "json_parse", "params": {}})
+        skill = JadeSkill.from_dict(skill_dict)
+        assert dag_analyzer.get_roots(skill.execution_dag) == ["step_one", "alone"]
+        assert dag_analyzer.get_sinks(skill.execution_dag) == ["step_two", "alone"]
+
 
 class TestDAGEdgeConditions:
     """Test edge condition validation."""
@@ -168,6 +422,273 @@ class TestDAGEdgeConditions:
         issues = dag_analyzer.validate(skill)
         assert any(i.code == "DAG_INVALID_CONDITION" for i in issues)
 
+    @pytest.mark.parametrize("condition", [
+        'check.output.status == "ok"',
+        "check.output.count >= 3 && !check.output.empty",
+        "(a.x == 1 || a.y != 'b') && success",
+        "fetch.output.items[0].id == null",
+    ])
+    def test_valid_expressions(self, dag_analyzer, condition):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["edges"] = [
+            {"from": "step_one", "to": "step_two", "condition": condition}
+        ]
+        skill = JadeSkill.from_dict(skill_dict)
+        assert dag_analyzer.validate_conditions(skill.execution_dag) == []
+
+    @pytest.mark.parametrize("condition, fragment", [
+        ('(check.output.status == "ok"', "Unbalanced parentheses"),
+        ('check.output.status == "ok")', "Unexpected token ')'"),
+        ("check.output.count === 3", "Unknown operator '==='"),
+        ("check.output.name ~= 'x'", "Unknown operator '~='"),
+        ("check.output.count >", "Unexpected end"),
+    ])
+    def test_malformed_expressions(self, dag_analyzer, condition, fragment):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["edges"] = [
+            {"from": "step_one", "to": "step_two", "condition": condition}
+        ]
+        skill = JadeSkill.from_dict(skill_dict)
+        issues = [i for i in dag_analyzer.validate(skill) if i.code == "DAG_INVALID_CONDITION"]
+        assert len(issues) == 1
+        assert issues[0].severity == ValidationSeverity.ERROR
+        assert "step_one -> step_two" in issues[0].message
+        assert fragment in issues[0].message
+
+    @pytest.mark.parametrize("condition", [
+        "!" * 1200 + "success",
+        "(" * 1200 + "success" + ")" * 1200,
+    ])
+    def test_deeply_nested_condition_rejected(self, condition):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["edges"][0]["condition"] = condition
+        result = JadeValidator().validate_dict(skill_dict)
+        [issue] = [i for i in result.errors if i.code == "DAG_INVALID_CONDITION"]
+        assert "nested too deeply" in issue.message
+        assert not result.valid
+
+    def test_nesting_up_to_limit_accepted(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["edges"][0]["condition"] = (
+            "!" * (MAX_CONDITION_DEPTH // 2) + "(" * (MAX_CONDITION_DEPTH // 2) + "success"
+            + ")" * (MAX_CONDITION_DEPTH // 2)
+        )
+        assert dag_analyzer.validate_conditions(JadeSkill.from_dict(skill_dict).execution_dag) == []
+
+    @pytest.mark.parametrize("condition, expected", [
+        ('check.output.status == "ok"', True),
+        ('check.status == "ok"', True),
+        ("check.output.count > 2 && success", True),
+        ("check.output.items[0] == 'a'", True),
+        ("check.output.missing == 1", False),
+        ("check.output.count == true", False),
+        ("failure", False),
+    ])
+    def test_evaluate_condition(self, condition, expected):
+        outputs = {"check": {"status": "ok", "count": 3, "items": ["a"]}}
+        assert evaluate_condition(condition, outputs) is expected
+
+    def test_evaluate_input_reference(self):
+        outputs = {"check": {"status": "ok"}}
+        assert evaluate_condition('input.mode == "fast"', outputs, inputs={"mode": "fast"}) is True
+        assert evaluate_condition('input.mode == "fast"', outputs, inputs={"mode": "slow"}) is False
+        assert evaluate_condition("input.mode == null", outputs) is True
+
+    def test_input_references_must_be_declared(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["input_schema"] = {"required_params": [{"name": "mode", "type": "string"}]}
+        skill_dict["execution_dag"]["edges"] = [
+            {"from": "step_one", "to": "step_two", "condition": 'input.mode == "fast" || input.moed == 1'}
+        ]
+        issues = [i for i in dag_analyzer.validate(JadeSkill.from_dict(skill_dict))
+                  if i.code == "DAG_UNDECLARED_INPUT"]
+        assert len(issues) == 1
+        assert "'input.moed'" in issues[0].message
+        assert issues[0].path == "execution_dag.edges[0].condition"
+
+    def test_json_schema_properties_declare_inputs(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["input_schema"] = {"type": "object", "properties": {"mode": {"type": "string"}}}
+        skill_dict["execution_dag"]["edges"][0]["condition"] = 'input.mode == "fast"'
+        issues = dag_analyzer.validate(JadeSkill.from_dict(skill_dict))
+        assert not [i for i in issues if i.code == "DAG_UNDECLARED_INPUT"]
+        del skill_dict["input_schema"]
+        issues = dag_analyzer.validate(JadeSkill.from_dict(skill_dict))
+        assert [i.code for i in issues if i.code == "DAG_UNDECLARED_INPUT"] == ["DAG_UNDECLARED_INPUT"]
+
+
+class TestDAGConflictingConditions:
+    """Incoming conditional edges that contradict or repeat each other."""
+
+    def router_skill(self, *edges):
+        """router and check both feed 'handle'; edges are (from, condition) pairs into it."""
+        skill_dict = make_skill()
+        skill_dict["execution_dag"] = {
+            "nodes": [
+                {"id": "router", "action": "json_parse", "params": {"input": "{}"}},
+                {"id": "check", "action": "json_parse", "params": {"input": "{}"}},
+                {"id": "handle", "action": "return_result", "params": {"result": "ok"}},
+            ],
+            "edges": [{"from": "router", "to": "check"}]
+            + [{"from": src, "to": "handle", "condition": cond} for src, cond in edges],
+            "entry_node": "router",
+            "exit_node": ["handle"],
+        }
+        return JadeSkill.from_dict(skill_dict)
+
+    def conflicts(self, dag_analyzer, skill):
+        return [(i.code, i.path) for i in dag_analyzer.validate(skill)
+                if i.code in ("DAG_CONTRADICTORY_CONDITIONS", "DAG_DUPLICATE_CONDITION")]
+
+    @pytest.mark.parametrize("first, second", [
+        ('router.output.mode == "a"', 'router.output.mode == "b"'),
+        ('"a" == router.mode', 'router.mode == "b" && check.ok == true'),
+        ("input.count == 1", "input.count != 1"),
+    ])
+    def test_contradictory_pair(self, dag_analyzer, first, second):
+        skill = self.router_skill(("router", first), ("check", second))
+        issues = [i for i in dag_analyzer.validate(skill) if i.code == "DAG_CONTRADICTORY_CONDITIONS"]
+        assert len(issues) == 1
+        assert issues[0].severity == ValidationSeverity.WARNING
+        assert issues[0].path == "execution_dag.edges[2].condition"
+        assert issues[0].node_id == "handle"
+        assert "router -> handle" in issues[0].message and "check -> handle" in issues[0].message
+
+    def test_duplicate_condition(self, dag_analyzer):
+        skill = self.router_skill(("router", 'router.mode == "a"'), ("check", 'router.mode=="a"'))
+        assert self.conflicts(dag_analyzer, skill) == [
+            ("DAG_DUPLICATE_CONDITION", "execution_dag.edges[2].condition"),
+        ]
+        [issue] = [i for i in dag_analyzer.validate(skill) if i.code == "DAG_DUPLICATE_CONDITION"]
+        assert "redundant" in issue.message and "twice" not in issue.message
+
+    def test_status_keywords_compared_per_source(self, dag_analyzer):
+        # 'success' of two different nodes is neither a duplicate nor a contradiction
+        assert self.conflicts(dag_analyzer, self.router_skill(("router", "success"), ("check", "success"))) == []
+        assert self.conflicts(dag_analyzer, self.router_skill(("router", "success"), ("check", "failure"))) == []
+        same = self.router_skill(("check", "success"), ("check", "failure"))
+        assert self.conflicts(dag_analyzer, same) == [
+            ("DAG_CONTRADICTORY_CONDITIONS", "execution_dag.edges[2].condition"),
+        ]
+
+    @pytest.mark.parametrize("first, second", [
+        ('router.mode == "a"', 'router.kind == "b"'),
+        ('router.mode == "a" || router.mode == "b"', 'router.mode == "c"'),
+        ("router.count > 2", "router.count < 1"),
+        ("router.count == 1", "router.count == 1.0 && check.ok"),
+        ('!(router.mode == "a")', 'router.mode == "a"'),
+    ])
+    def test_unprovable_pairs_are_not_flagged(self, dag_analyzer, first, second):
+        assert self.conflicts(dag_analyzer, self.router_skill(("router", first), ("check", second))) == []
+
+    def test_exact_duplicate_edge_reported_once(self, dag_analyzer):
+        skill = self.router_skill(("router", 'router.mode == "a"'), ("router", 'router.mode == "a"'))
+        codes = [i.code for i in dag_analyzer.validate(skill)]
+        assert "DAG_DUPLICATE_EDGE" in codes
+        assert "DAG_DUPLICATE_CONDITION" not in codes
+
+
+class TestDAGTemplateReferences:
+    """Templates may only reference outputs of upstream nodes."""
+
+    def _chain(self, **params):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"] = {
+            "nodes": [
+                {"id": "fetch", "action": "http_get", "params": params.get("fetch", {})},
+                {"id": "parse", "action": "json_parse", "params": params.get("parse", {})},
+                {"id": "other", "action": "json_parse", "params": {}},
+                {"id": "done", "action": "return_result", "params": params.get("done", {})},
+            ],
+            "edges": [
+                {"from": "fetch", "to": "parse"},
+                {"from": "parse", "to": "done"},
+                {"from": "fetch", "to": "other"},
+                {"from": "other", "to": "done"},
+            ],
+            "entry_node": "fetch",
+            "exit_node": ["done"],
+        }
+        return JadeSkill.from_dict(skill_dict)
+
+    def _flagged(self, dag_analyzer, skill):
+        return [i for i in dag_analyzer.validate(skill) if i.code == "DAG_TEMPLATE_NOT_ANCESTOR"]
+
+    def test_ancestor_reference_ok(self, dag_analyzer):
+        skill = self._chain(done={"result": "{{fetch.output.body}} / {{parse.data}}",
+                                  "city": "{{input.city}}"})
+        assert self._flagged(dag_analyzer, skill) == []
+
+    def test_forward_reference_flagged(self, dag_analyzer):
+        skill = self._chain(parse={"text": "{{done.output.result}}"})
+        [issue] = self._flagged(dag_analyzer, skill)
+        assert issue.severity == ValidationSeverity.ERROR
+        assert issue.path == "execution_dag.nodes.parse.params"
+        assert "'done'" in issue.message
+
+    def test_sibling_branch_reference_flagged(self, dag_analyzer):
+        skill = self._chain(parse={"nested": [{"x": "{{other.output.x}}"}]})
+        assert len(self._flagged(dag_analyzer, skill)) == 1
+
+    def test_self_reference_flagged(self, dag_analyzer):
+        skill = self._chain(fetch={"url": "{{fetch.output.next}}"})
+        assert len(self._flagged(dag_analyzer, skill)) == 1
+
+    def test_get_ancestors(self, dag_analyzer):
+        ancestors = dag_analyzer.get_ancestors(self._chain().execution_dag)
+        assert ancestors["fetch"] == set()
+        assert ancestors["done"] == {"fetch", "parse", "other"}
+
+
+class TestDAGConditionalReachability:
+    """Static order covers every node; conditional-only nodes are reported."""
+
+    def _branching(self):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"] = {
+            "nodes": [
+                {"id": "check", "action": "http_get", "params": {}},
+                {"id": "ok", "action": "json_parse", "params": {}},
+                {"id": "after_ok", "action": "json_parse", "params": {}},
+                {"id": "log", "action": "json_parse", "params": {}},
+                {"id": "done", "action": "return_result", "params": {}},
+            ],
+            "edges": [
+                {"from": "check", "to": "ok", "condition": 'check.output.status == "ok"'},
+                {"from": "ok", "to": "after_ok"},
+                {"from": "check", "to": "log"},
+                {"from": "log", "to": "done"},
+                {"from": "after_ok", "to": "done"},
+            ],
+            "entry_node": "check",
+            "exit_node": ["done"],
+        }
+        return JadeSkill.from_dict(skill_dict).execution_dag
+
+    def test_static_order_lists_all_nodes(self, dag_analyzer):
+        dag = self._branching()
+        order = dag_analyzer.get_static_topological_order(dag)
+        assert order == dag_analyzer.get_topological_order(dag)
+        assert set(order) == {"check", "ok", "after_ok", "log", "done"}
+
+    def test_conditional_branch_possibly_unreachable(self, dag_analyzer):
+        dag = self._branching()
+        # 'done' also has an unconditional path through 'log'
+        assert dag_analyzer.get_possibly_unreachable_nodes(dag) == ["ok", "after_ok"]
+
+    def test_unconditional_dag_has_none(self, dag_analyzer):
+        dag = JadeSkill.from_dict(make_skill()).execution_dag
+        assert dag_analyzer.get_possibly_unreachable_nodes(dag) == []
+
+    def test_fully_unreachable_not_listed(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["nodes"].append(
+            {"id": "island", "action": "json_parse", "params": {}})
+        skill_dict["execution_dag"]["edges"].append(
+            {"from": "island", "to": "island", "condition": "success"})
+        dag = JadeSkill.from_dict(skill_dict).execution_dag
+        assert "island" not in dag_analyzer.get_possibly_unreachable_nodes(dag)
+
 
 class TestDAGComplexGraphs:
     """Test with more complex DAG structures."""
@@ -207,3 +728,174 @@ class TestDAGComplexGraphs:
             errors = [i for i in issues if i.severity == ValidationSeverity.ERROR]
             fname = skill_file.split("/")[-1]
             assert len(errors) == 0, f"{fname} DAG errors: {[i.message for i in errors]}"
+
+
+class TestDAGExecutionLevels:
+    """Test grouping of nodes into topological waves."""
+
+    def test_diamond_levels(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"] = {
+            "nodes": [
+                {"id": "start", "action": "json_parse", "params": {}},
+                {"id": "branch_b", "action": "json_extract", "params": {}},
+                {"id": "branch_a", "action": "json_extract", "params": {}},
+                {"id": "merge", "action": "return_result", "params": {}},
+            ],
+            "edges": [
+                {"from": "start", "to": "branch_a"},
+                {"from": "start", "to": "branch_b"},
+                {"from": "branch_a", "to": "merge"},
+                {"from": "branch_b", "to": "merge"},
+            ],
+            "entry_node": "start",
+            "exit_node": ["merge"],
+        }
+        skill = JadeSkill.from_dict(skill_dict)
+        levels = dag_analyzer.get_execution_levels(skill.execution_dag)
+        # Nodes within a wave keep declaration order
+        assert levels == [["start"], ["branch_b", "branch_a"], ["merge"]]
+
+    def test_linear_chain_one_node_per_level(self, dag_analyzer, minimal_skill_dict):
+        skill = JadeSkill.from_dict(minimal_skill_dict)
+        levels = dag_analyzer.get_execution_levels(skill.execution_dag)
+        assert all(len(level) == 1 for level in levels)
+        assert [n for level in levels for n in level] == \
+            dag_analyzer.get_topological_order(skill.execution_dag)
+
+    def test_cycle_raises(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["nodes"] = [
+            {"id": "node_a", "action": "json_parse", "params": {}},
+            {"id": "node_b", "action": "json_parse", "params": {}},
+        ]
+        skill_dict["execution_dag"]["edges"] = [
+            {"from": "node_a", "to": "node_b"},
+            {"from": "node_b", "to": "node_a"},
+        ]
+        skill = JadeSkill.from_dict(skill_dict)
+        with pytest.raises(ValueError, match="node_a, node_b"):
+            dag_analyzer.get_execution_levels(skill.execution_dag)
+
+    def test_cycle_error_lists_nodes(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["nodes"].append({"id": "loop", "action": "json_parse", "params": {}})
+        skill_dict["execution_dag"]["edges"].append({"from": "loop", "to": "loop"})
+        skill = JadeSkill.from_dict(skill_dict)
+        with pytest.raises(CycleError) as excinfo:
+            dag_analyzer.get_execution_levels(skill.execution_dag)
+        assert excinfo.value.nodes == ["loop"]
+
+
+class TestDAGCriticalPath:
+    """Test weighted critical path and maximum depth."""
+
+    def _branches(self, left_ms, right_ms):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"] = {
+            "nodes": [
+                {"id": "start", "action": "json_parse", "params": {}, "timeout_ms": 100},
+                {"id": "fast", "action": "http_get", "params": {}, "timeout_ms": left_ms},
+                {"id": "slow", "action": "http_get", "params": {}, "timeout_ms": right_ms},
+                {"id": "merge", "action": "return_result", "params": {}},
+            ],
+            "edges": [
+                {"from": "start", "to": "fast"},
+                {"from": "start", "to": "slow"},
+                {"from": "fast", "to": "merge"},
+                {"from": "slow", "to": "merge"},
+            ],
+            "entry_node": "start",
+            "exit_node": ["merge"],
+        }
+        return JadeSkill.from_dict(skill_dict)
+
+    def test_heavier_branch_is_critical(self, dag_analyzer):
+        skill = self._branches(left_ms=500, right_ms=4000)
+        path, total = dag_analyzer.get_critical_path(skill.execution_dag, default_timeout_ms=10)
+        assert path == ["start", "slow", "merge"]
+        assert total == 100 + 4000 + 10
+
+    def test_default_weight_applies_to_missing_timeouts(self, dag_analyzer):
+        skill = self._branches(left_ms=500, right_ms=400)
+        path, total = dag_analyzer.get_critical_path(skill.execution_dag)
+        assert path == ["start", "fast", "merge"]
+        assert total == 100 + 500 + DEFAULT_NODE_TIMEOUT_MS
+
+    def test_param_timeout_weighs_like_the_executor(self, dag_analyzer):
+        skill = self._branches(left_ms=500, right_ms=400)
+        fast = skill.execution_dag.nodes[1]
+        fast.timeout_ms, fast.params["timeout_ms"] = None, 9000
+        merge = skill.execution_dag.nodes[3]
+        merge.params["timeout_ms"] = 20  # Node-level timeout_ms would win over this
+        path, total = dag_analyzer.get_critical_path(skill.execution_dag, default_timeout_ms=10)
+        assert path == ["start", "fast", "merge"]
+        assert total == 100 + 9000 + 20
+        assert node_timeout_ms(skill.execution_dag.nodes[2]) == 400
+
+    def test_max_depth(self, dag_analyzer, minimal_skill_dict):
+        assert dag_analyzer.get_max_depth(self._branches(1, 2).execution_dag) == 2
+        skill = JadeSkill.from_dict(minimal_skill_dict)
+        assert dag_analyzer.get_max_depth(skill.execution_dag) == 1
+
+    def test_cycle_is_an_error(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["edges"].append({"from": "step_two", "to": "step_one"})
+        skill = JadeSkill.from_dict(skill_dict)
+        with pytest.raises(CycleError):
+            dag_analyzer.get_critical_path(skill.execution_dag)
+        with pytest.raises(CycleError):
+            dag_analyzer.get_max_depth(skill.execution_dag)
+        assert dag_analyzer.compute_max_depth(skill.execution_dag) == -1
+
+
+class TestDAGMetrics:
+    """Test the aggregate shape metrics used by catalog dashboards."""
+
+    def test_diamond(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"] = {
+            "nodes": [
+                {"id": "start", "action": "json_parse", "params": {}},
+                {"id": "branch_a", "action": "json_extract", "params": {}},
+                {"id": "branch_b", "action": "json_extract", "params": {}},
+                {"id": "merge", "action": "return_result", "params": {}},
+            ],
+            "edges": [
+                {"from": "start", "to": "branch_a"},
+                {"from": "start", "to": "branch_b"},
+                {"from": "branch_a", "to": "merge"},
+                {"from": "branch_b", "to": "merge"},
+            ],
+            "entry_node": "start",
+            "exit_node": ["merge"],
+        }
+        metrics = dag_analyzer.get_metrics(JadeSkill.from_dict(skill_dict).execution_dag)
+        assert metrics.to_dict() == {
+            "node_count": 4, "edge_count": 4, "root_count": 1, "sink_count": 1,
+            "max_fan_out": 2, "max_fan_in": 2, "max_depth": 2, "connected": True,
+        }
+
+    def test_linear_chain(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["nodes"] = [
+            {"id": f"n{i}", "action": "json_parse", "params": {}} for i in range(5)
+        ]
+        skill_dict["execution_dag"]["edges"] = [{"from": f"n{i}", "to": f"n{i + 1}"} for i in range(4)]
+        skill_dict["execution_dag"]["entry_node"] = "n0"
+        skill_dict["execution_dag"]["exit_node"] = ["n4"]
+        metrics = dag_analyzer.get_metrics(JadeSkill.from_dict(skill_dict).execution_dag)
+        assert (metrics.node_count, metrics.edge_count) == (5, 4)
+        assert (metrics.root_count, metrics.sink_count) == (1, 1)
+        assert (metrics.max_fan_out, metrics.max_fan_in) == (1, 1)
+        assert metrics.max_depth == 4
+        assert metrics.connected
+
+    def test_disconnected_and_cyclic(self, dag_analyzer):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"]["nodes"].append({"id": "loose", "action": "json_parse", "params": {}})
+        metrics = dag_analyzer.get_metrics(JadeSkill.from_dict(skill_dict).execution_dag)
+        assert not metrics.connected
+        assert metrics.root_count == 2
+        skill_dict["execution_dag"]["edges"].append({"from": "step_two", "to": "step_one"})
+        assert dag_analyzer.get_metrics(JadeSkill.from_dict(skill_dict).execution_dag).max_depth == -1
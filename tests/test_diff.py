@@ -0,0 +1,129 @@
+"""
+Test suite for JADE skill diffs.
+Tests DAG, security-policy and signature changes and privilege escalation.
+"""
+
+import copy
+import json
+from jade_core.crypto import JadeKeyPair
+from jade_core.diff import diff_skills
+from jade_core.models import JadeSkill
+from tests.conftest import make_skill, sign_skill
+
+
+def diff(old, new):
+    return diff_skills(JadeSkill.from_dict(old), JadeSkill.from_dict(new))
+
+
+class TestSkillDiffDAG:
+    """Nodes and edges are compared by identity, not position."""
+
+    def test_identical_skills(self):
+        skill = make_skill()
+        change = diff(skill, copy.deepcopy(skill))
+        assert change.empty
+        assert not change.privilege_escalating
+        assert "No changes" in change.summary()
+
+    def test_reordering_is_not_a_change(self):
+        old = make_skill()
+        new = copy.deepcopy(old)
+        new["execution_dag"]["nodes"].reverse()
+        assert diff(old, new).empty
+
+    def test_added_removed_and_changed_nodes(self):
+        old = make_skill()
+        new = copy.deepcopy(old)
+        dag = new["execution_dag"]
+        dag["nodes"][0]["params"]["input"] = "{{input.other}}"
+        dag["nodes"][1]["id"] = "step_three"
+        dag["edges"] = [{"from": "step_one", "to": "step_three", "condition": "step_one.output.ok == true"}]
+        change = diff(old, new)
+        assert change.nodes_added == ["step_three"]
+        assert change.nodes_removed == ["step_two"]
+        assert [c.path for c in change.node_changes] == ["execution_dag.nodes.step_one.params"]
+        assert change.edges_added == [("step_one", "step_three")]
+        assert change.edges_removed == [("step_one", "step_two")]
+        assert not change.privilege_escalating
+
+    def test_edge_condition_change(self):
+        old = make_skill()
+        new = copy.deepcopy(old)
+        new["execution_dag"]["edges"][0]["condition"] = "step_one.output.ok == true"
+        [edge] = diff(old, new).edge_changes
+        assert (edge.old, edge.new) == (None, "step_one.output.ok == true")
+
+
+class TestSkillDiffSecurity:
+    """Widened whitelists and relaxed sandboxes are flagged as escalations."""
+
+    def test_broadened_whitelists_escalate(self):
+        old = make_skill()
+        old["security"]["network_whitelist"] = ["*.example.com"]
+        new = copy.deepcopy(old)
+        new["security"]["network_whitelist"] += ["api.example.com", "paste.evil.net"]
+        new["security"]["env_whitelist"] = ["GITHUB_TOKEN"]
+        change = diff(old, new)
+        # api.example.com was already covered by *.example.com
+        assert [(e.path, e.reason) for e in change.escalations] == [
+            ("security.network_whitelist[2]", "network_whitelist now allows 'paste.evil.net'"),
+            ("security.env_whitelist[0]", "env_whitelist now grants 'GITHUB_TOKEN'"),
+        ]
+        assert [c.path for c in change.security_changes] == [
+            "security.network_whitelist", "security.env_whitelist",
+        ]
+        assert "Changed security.network_whitelist: +'api.example.com', +'paste.evil.net'" in change.summary()
+
+    def test_sandbox_relaxed_and_shell_action(self):
+        old = make_skill()
+        new = copy.deepcopy(old)
+        new["security"]["sandbox_level"] = "standard"
+        new["execution_dag"]["nodes"][1]["action"] = "shell_exec"
+        change = diff(old, new)
+        assert change.privilege_escalating
+        reasons = [e.reason for e in change.escalations]
+        assert "Sandbox relaxed from strict to standard" in reasons
+        assert "Node 'step_two' adds shell action 'shell_exec'" in reasons
+        assert change.escalations[0].node_id == "step_two"
+        summary = change.summary()
+        assert summary.splitlines()[1].startswith("PRIVILEGE ESCALATION (2)")
+
+    def test_longer_timeout_escalates(self):
+        old = make_skill()
+        new = copy.deepcopy(old)
+        new["security"]["max_execution_time_ms"] = old["security"]["max_execution_time_ms"] * 4
+        [escalation] = diff(old, new).escalations
+        assert escalation.path == "security.max_execution_time_ms"
+        assert escalation.reason.startswith("max_execution_time_ms raised from")
+
+    def test_narrowing_is_not_escalation(self):
+        old = make_skill()
+        old["security"]["sandbox_level"] = "standard"
+        old["security"]["network_whitelist"] = ["a.example.com", "b.example.com"]
+        new = copy.deepcopy(old)
+        new["security"]["sandbox_level"] = "strict"
+        new["security"]["network_whitelist"] = ["a.example.com"]
+        change = diff(old, new)
+        assert not change.privilege_escalating
+        assert len(change.security_changes) == 2
+
+
+class TestSkillDiffSignature:
+    """Signature and signing-key changes."""
+
+    def test_resigned_with_other_key(self):
+        old = sign_skill(make_skill(), JadeKeyPair.generate())
+        new = sign_skill(copy.deepcopy(old), JadeKeyPair.generate())
+        change = diff(old, new)
+        assert change.signature_changed and change.signing_key_changed
+        assert change.empty
+        assert "Signed with a different key" in change.summary()
+
+    def test_to_dict_is_json(self):
+        old = make_skill()
+        new = copy.deepcopy(old)
+        new["metadata"]["version"] = "1.1.0"
+        data = json.loads(json.dumps(diff(old, new).to_dict()))
+        assert data["new_version"] == "1.1.0"
+        assert data["metadata_changes"] == [{"path": "metadata.version", "old": "1.0.0", "new": "1.1.0"}]
+        assert data["signature_changed"] is False
@@ -0,0 +1,102 @@
+"""
+Test suite for JADE structured log events.
+Captures the records validation and execution emit on the jade_core logger.
+"""
+
+import io
+import logging
+import sys
+from jade_core.crypto import JadeKeyPair, key_fingerprint
+from jade_core.executor import JadeExecutor
+from jade_core.models import JadeSkill
+from jade_core.validator import JadeValidator
+from tests.conftest import make_skill, sign_skill
+
+
+class Recorder(logging.Handler):
+    """Keeps every record emitted while attached to the jade_core logger."""
+
+    def __init__(self):
+        super().__init__(level=logging.DEBUG)
+        self.records = []
+
+    def emit(self, record):
+        self.records.append(record)
+
+    def __enter__(self):
+        self.logger = logging.getLogger("jade_core")
+        self.level = self.logger.level
+        self.logger.setLevel(logging.DEBUG)
+        self.logger.addHandler(self)
+        return self
+
+    def __exit__(self, *exc):
+        self.logger.removeHandler(self)
+        self.logger.setLevel(self.level)
+
+    def events(self, name):
+        return [r for r in self.records if getattr(r, "event", None) == name]
+
+
+class TestValidationEvents:
+    """Layers, issues and signatures are reported as they are checked."""
+
+    def test_signed_skill(self):
+        keypair = JadeKeyPair.generate()
+        skill = sign_skill(make_skill(), keypair)
+        with Recorder() as recorder:
+            result = JadeValidator().validate_dict(skill, source_path="weather.json")
+        assert result.valid
+
+        order = [r.event for r in recorder.records if r.event.startswith("validation_")]
+        assert order == ["validation_started", "validation_finished"]
+        assert [r.layer for r in recorder.events("layer_started")] == [1, 2, 3, 4, 5]
+        finished = recorder.events("layer_finished")
+        assert [r.layer for r in finished] == [1, 2, 3, 4, 5]
+        assert all(r.duration_ms >= 0 and r.skill_id == skill["skill_id"] for r in finished)
+
+        [signature] = recorder.events("signature_verified")
+        assert signature.kind == "root" and signature.verified is True
+        assert signature.fingerprint == key_fingerprint(keypair.public_key)
+        assert signature.levelno == logging.INFO
+
+        [done] = recorder.events("validation_finished")
+        assert done.valid is True and done.error_count == 0
+        assert done.getMessage() == "weather.json: valid (0 errors, 0 warnings)"
+
+    def test_issue_found(self):
+        skill = make_skill()
+        skill["execution_dag"]["edges"].append({"from": "step_one", "to": "step_one"})
+        with Recorder() as recorder:
+            JadeValidator().validate_dict(skill)
+        loop = [r for r in recorder.events("issue_found") if r.code == "DAG_SELF_LOOP"]
+        assert len(loop) == 1
+        assert loop[0].layer == 2 and loop[0].severity == "error"
+        assert recorder.events("validation_finished")[0].valid is False
+
+    def test_library_prints_nothing(self):
+        stdout, sys.stdout = sys.stdout, io.StringIO()
+        try:
+            JadeValidator().validate_dict(make_skill())
+            captured = sys.stdout.getvalue()
+        finally:
+            sys.stdout = stdout
+        assert captured == ""
+
+
+class TestExecutionEvents:
+    """Each node that runs or is skipped is reported."""
+
+    def test_nodes_executed(self):
+        executor = JadeExecutor()
+        executor.register_action("json_parse", lambda node, context: {"data": {}})
+        executor.register_action("return_result", lambda node, context: "done")
+        skill = JadeSkill.from_dict(make_skill())
+        with Recorder() as recorder:
+            executor.execute(skill, inputs={"input_text": "{}"})
+        assert [r.event for r in recorder.records] == [
+            "execution_started", "node_executed", "node_executed", "execution_finished",
+        ]
+        first = recorder.events("node_executed")[0]
+        assert (first.node_id, first.action, first.attempts) == ("step_one", "json_parse", 1)
+        assert recorder.records[-1].skipped_count == 0
@@ -0,0 +1,761 @@
+"""
+Test suite for JADE Executor.
+Tests node ordering, HTTP execution, whitelist enforcement, timeouts,
+branching and parallel waves.
+"""
+
+import json
+import threading
+import time
+import pytest
+from http.server import BaseHTTPRequestHandler, HTTPServer
+from jade_core.executor import MAX_REDIRECTS, ExecutionError, JadeExecutor, urllib_transport
+from jade_core.models import JadeSkill, SandboxLevel
+from tests.conftest import make_skill
+
+
+class FakeTransport:
+    """Records HTTP calls and answers with a canned response."""
+
+    def __init__(self, status_code=200, body='{"ok": true}', delay_s=0.0):
+        self.calls = []
+        self.status_code = status_code
+        self.body = body
+        self.delay_s = delay_s
+
+    def __call__(self, method, url, headers, body, timeout_s):
+        self.calls.append({"method": method, "url": url, "headers": headers,
+                           "body": body, "timeout_s": timeout_s})
+        if self.delay_s:
+            time.sleep(self.delay_s)
+        return {"status_code": self.status_code, "body": self.body, "headers": {}}
+
+
+class RedirectTransport(FakeTransport):
+    """Answers the first `hops` calls with a redirect to `location`."""
+
+    def __init__(self, location, hops=1, status_code=302):
+        super().__init__()
+        self.location = location
+        self.hops = hops
+        self.redirect_status = status_code
+
+    def __call__(self, method, url, headers, body, timeout_s):
+        response = super().__call__(method, url, headers, body, timeout_s)
+        if len(self.calls) <= self.hops:
+            return {"status_code": self.redirect_status, "body": "", "headers": {"Location": self.location}}
+        return response
+
+
+def http_skill(url="https://api.example.com/v1/items", whitelist=("api.example.com",), **node):
+    """A two-node skill: an http_request followed by return_result."""
+    skill_dict = make_skill()
+    fetch = {"id": "fetch", "action": "http_request", "params": {"method": "GET", "url": url}}
+    fetch.update(node)
+    skill_dict["execution_dag"] = {
+        "nodes": [
+            fetch,
+            {"id": "done", "action": "return_result", "params": {"result": "{{fetch.output.body}}"}},
+        ],
+        "edges": [{"from": "fetch", "to": "done"}],
+        "entry_node": "fetch",
+        "exit_node": ["done"],
+    }
+    skill_dict["security"]["network_whitelist"] = list(whitelist)
+    return JadeSkill.from_dict(skill_dict)
+
+
+def passthrough(node, context):
+    """Test handler: return the outputs seen so far."""
+    return {"seen": sorted(context.outputs)}
+
+
+class TestExecutorHttp:
+    """Test the built-in HTTP action handlers."""
+
+    def test_whitelisted_request_executes(self):
+        transport = FakeTransport()
+        executor = JadeExecutor(transport=transport)
+        executor.register_action("return_result", passthrough)
+        result = executor.execute(http_skill())
+        assert result.order == ["fetch", "done"]
+        assert result.outputs["fetch"]["status_code"] == 200
+        assert transport.calls[0]["method"] == "GET"
+
+    def test_output_passed_downstream(self):
+        executor = JadeExecutor(transport=FakeTransport())
+        executor.register_action("return_result", passthrough)
+        result = executor.execute(http_skill())
+        assert result.outputs["done"] == {"seen": ["fetch"]}
+
+    def test_non_whitelisted_host_refused(self):
+        transport = FakeTransport()
+        executor = JadeExecutor(transport=transport)
+        skill = http_skill(url="https://evil.example.net/steal")
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(skill)
+        assert exc.value.code == "DOMAIN_NOT_WHITELISTED"
+        assert exc.value.node_id == "fetch"
+        assert transport.calls == []
+
+    def test_plain_http_refused_in_strict_sandbox(self):
+        transport = FakeTransport()
+        executor = JadeExecutor(transport=transport)
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(http_skill(url="http://api.example.com/v1/items"))
+        assert exc.value.code == "INSECURE_SCHEME"
+        assert transport.calls == []
+
+    def test_whitelisted_port_enforced(self):
+        transport = FakeTransport()
+        executor = JadeExecutor(transport=transport)
+        executor.register_action("return_result", passthrough)
+        executor.execute(http_skill(url="https://api.example.com:8443/v1", whitelist=("api.example.com:8443",)))
+        assert len(transport.calls) == 1
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(http_skill(url="https://api.example.com/v1", whitelist=("api.example.com:8443",)))
+        assert exc.value.code == "DOMAIN_NOT_WHITELISTED"
+
+    def test_cidr_whitelist_enforced(self):
+        transport = FakeTransport()
+        executor = JadeExecutor(transport=transport)
+        executor.register_action("return_result", passthrough)
+        executor.execute(http_skill(url="https://10.1.2.3/v1", whitelist=("10.0.0.0/8",)))
+        executor.execute(http_skill(url="https://[2001:db8::7]/v1", whitelist=("2001:db8::/32",)))
+        assert len(transport.calls) == 2
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(http_skill(url="https://192.168.1.1/v1", whitelist=("10.0.0.0/8",)))
+        assert exc.value.code == "DOMAIN_NOT_WHITELISTED"
+
+    def test_redirect_to_non_whitelisted_host_refused(self):
+        transport = RedirectTransport("http://169.254.169.254/latest/meta-data/")
+        executor = JadeExecutor(transport=transport)
+        executor.register_action("return_result", passthrough)
+        skill = http_skill()
+        skill.security.sandbox_level = SandboxLevel.STANDARD
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(skill)
+        assert exc.value.code == "DOMAIN_NOT_WHITELISTED"
+        assert "169.254.169.254" in str(exc.value)
+        assert [c["url"] for c in transport.calls] == ["https://api.example.com/v1/items"]
+
+    def test_whitelisted_redirect_followed(self):
+        transport = RedirectTransport("/v2/items", status_code=303)
+        executor = JadeExecutor(transport=transport)
+        executor.register_action("return_result", passthrough)
+        skill = http_skill()
+        skill.execution_dag.nodes[0].params.update({"method": "POST", "body": {"a": 1},
+                                                    "headers": {"Authorization": "Bearer t"}})
+        result = executor.execute(skill)
+        assert result.outputs["fetch"]["status_code"] == 200
+        second = transport.calls[1]
+        assert second["url"] == "https://api.example.com/v2/items"
+        assert (second["method"], second["body"]) == ("GET", None)
+        assert second["headers"] == {"Authorization": "Bearer t"}
+
+    def test_credentials_dropped_on_cross_host_redirect(self):
+        transport = RedirectTransport("https://cdn.example.com/items", status_code=307)
+        executor = JadeExecutor(transport=transport)
+        executor.register_action("return_result", passthrough)
+        skill = http_skill(whitelist=("*.example.com",))
+        skill.execution_dag.nodes[0].params["headers"] = {"Authorization": "Bearer t", "Accept": "text/plain"}
+        executor.execute(skill)
+        assert transport.calls[1]["headers"] == {"Accept": "text/plain"}
+
+    def test_redirect_loop_bounded(self):
+        transport = RedirectTransport("https://api.example.com/v1/items", hops=100)
+        executor = JadeExecutor(transport=transport)
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(http_skill())
+        assert exc.value.code == "HTTP_ERROR"
+        assert len(transport.calls) == MAX_REDIRECTS + 1
+
+    def test_urllib_transport_does_not_follow_redirects(self):
+        class Redirect(BaseHTTPRequestHandler):
+            def do_GET(self):
+                self.send_response(302)
+                self.send_header("Location", "http://169.254.169.254/")
+                self.send_header("Content-Length", "0")
+                self.end_headers()
+
+            def log_message(self, *args):
+                pass
+
+        server = HTTPServer(("127.0.0.1", 0), Redirect)
+        threading.Thread(target=server.serve_forever, daemon=True).start()
+        try:
+            url = f"http://127.0.0.1:{server.server_port}/"
+            response = urllib_transport("GET", url, {}, None, 5.0)
+        finally:
+            server.shutdown()
+            server.server_close()
+        assert response["status_code"] == 302
+        assert response["headers"]["Location"] == "http://169.254.169.254/"
+
+    def test_wildcard_subdomain_whitelist(self):
+        transport = FakeTransport()
+        executor = JadeExecutor(transport=transport)
+        executor.register_action("return_result", passthrough)
+        executor.execute(http_skill(whitelist=("*.example.com",)))
+        assert len(transport.calls) == 1
+
+    def test_json_body_encoded(self):
+        transport = FakeTransport()
+        executor = JadeExecutor(transport=transport)
+        executor.register_action("return_result", passthrough)
+        skill = http_skill()
+        skill.execution_dag.nodes[0].params.update({"method": "POST", "body": {"a": 1}})
+        executor.execute(skill)
+        call = transport.calls[0]
+        assert json.loads(call["body"]) == {"a": 1}
+        assert call["headers"]["Content-Type"] == "application/json"
+
+    def test_node_timeout_enforced(self):
+        executor = JadeExecutor(transport=FakeTransport(delay_s=1.0))
+        skill = http_skill(timeout_ms=100)
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(skill)
+        assert exc.value.code == "NODE_TIMEOUT"
+
+    def test_node_timeout_passed_to_transport(self):
+        transport = FakeTransport()
+        executor = JadeExecutor(transport=transport)
+        executor.register_action("return_result", passthrough)
+        executor.execute(http_skill(timeout_ms=2500))
+        assert transport.calls[0]["timeout_s"] == 2.5
+
+
+class TestExecutorBasics:
+    """Test ordering, dry-run and unsupported actions."""
+
+    def test_dry_run_executes_nothing(self):
+        transport = FakeTransport()
+        result = JadeExecutor(transport=transport).execute(http_skill(), dry_run=True)
+        assert result.dry_run
+        assert result.order == ["fetch", "done"]
+        assert result.node_count == 2
+        assert result.outputs == {}
+        assert transport.calls == []
+
+    def test_dry_run_plan(self):
+        result = JadeExecutor(transport=FakeTransport()).execute(http_skill(), dry_run=True)
+        fetch, done = result.plan
+        assert (fetch.node_id, fetch.level, fetch.depends_on, fetch.references) == ("fetch", 0, [], [])
+        assert (done.node_id, done.level, done.depends_on) == ("done", 1, ["fetch"])
+        assert done.references == ["fetch.output.body"]
+        assert json.loads(json.dumps(result.to_dict()))["plan"][1]["depends_on"] == ["fetch"]
+
+    def test_unsupported_action_fails(self):
+        executor = JadeExecutor(transport=FakeTransport())
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(http_skill())
+        assert exc.value.code == "UNSUPPORTED_ACTION"
+        assert exc.value.node_id == "done"
+
+    def test_handler_exception_wrapped(self):
+        def broken(node, context):
+            raise KeyError("missing")
+
+        executor = JadeExecutor(transport=FakeTransport())
+        executor.register_action("return_result", broken)
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(http_skill())
+        assert exc.value.code == "NODE_FAILED"
+
+
+class TestExecutorBudget:
+    """Test the global max_execution_time_ms budget."""
+
+    def slow_skill(self, budget_ms, node_timeout_ms=5000):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"] = {
+            "nodes": [
+                {"id": "slow_a", "action": "sleep", "params": {}, "timeout_ms": node_timeout_ms},
+                {"id": "slow_b", "action": "sleep", "params": {}, "timeout_ms": node_timeout_ms},
+            ],
+            "edges": [{"from": "slow_a", "to": "slow_b"}],
+            "entry_node": "slow_a",
+            "exit_node": ["slow_b"],
+        }
+        skill_dict["security"]["max_execution_time_ms"] = budget_ms
+        return JadeSkill.from_dict(skill_dict)
+
+    def executor(self, delay_s):
+        executor = JadeExecutor(transport=FakeTransport())
+        executor.register_action("sleep", lambda node, context: time.sleep(delay_s) or "slept")
+        return executor
+
+    def test_global_budget_triggers(self):
+        with pytest.raises(ExecutionError) as exc:
+            self.executor(0.15).execute(self.slow_skill(budget_ms=200))
+        assert exc.value.code == "EXECUTION_TIMEOUT"
+        assert exc.value.node_id == "slow_b"
+        assert "slow_a" in exc.value.message
+
+    def test_node_timeout_smaller_than_budget_wins(self):
+        with pytest.raises(ExecutionError) as exc:
+            self.executor(0.5).execute(self.slow_skill(budget_ms=10000, node_timeout_ms=100))
+        assert exc.value.code == "NODE_TIMEOUT"
+        assert exc.value.node_id == "slow_a"
+
+    def test_within_budget_completes(self):
+        result = self.executor(0.01).execute(self.slow_skill(budget_ms=5000))
+        assert result.outputs == {"slow_a": "slept", "slow_b": "slept"}
+
+
+class TestExecutorSandbox:
+    """Test that sandbox_level capabilities are enforced at runtime."""
+
+    def sandboxed(self, level, **kwargs):
+        skill = http_skill(**kwargs)
+        skill.security.sandbox_level = SandboxLevel(level)
+        return skill
+
+    def executor(self, transport):
+        executor = JadeExecutor(transport=transport)
+        executor.register_action("return_result", passthrough)
+        return executor
+
+    def test_strict_refuses_plain_http(self):
+        transport = FakeTransport()
+        with pytest.raises(ExecutionError) as exc:
+            self.executor(transport).execute(self.sandboxed("strict", url="http://api.example.com/v1"))
+        assert exc.value.code == "INSECURE_SCHEME"
+        assert "strict" in exc.value.message
+        assert transport.calls == []
+
+    def test_standard_allows_plain_http(self):
+        transport = FakeTransport()
+        self.executor(transport).execute(self.sandboxed("standard", url="http://api.example.com/v1"))
+        assert len(transport.calls) == 1
+
+    def test_strict_ignores_wildcard(self):
+        transport = FakeTransport()
+        skill = self.sandboxed("strict", url="https://anywhere.example.org/", whitelist=("*",))
+        with pytest.raises(ExecutionError) as exc:
+            self.executor(transport).execute(skill)
+        assert exc.value.code == "DOMAIN_NOT_WHITELISTED"
+        assert "'*' is ignored" in exc.value.message
+        assert transport.calls == []
+
+    def test_standard_honors_wildcard(self):
+        transport = FakeTransport()
+        skill = self.sandboxed("standard", url="https://anywhere.example.org/", whitelist=("*",))
+        self.executor(transport).execute(skill)
+        assert len(transport.calls) == 1
+
+    def test_strict_blocks_registered_shell_action(self):
+        ran = []
+        skill = self.sandboxed("strict")
+        skill.execution_dag.nodes[1].action = "shell_exec"
+        executor = self.executor(FakeTransport())
+        executor.register_action("shell_exec", lambda node, context: ran.append(node.id))
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(skill)
+        assert exc.value.code == "SANDBOX_VIOLATION"
+        assert exc.value.node_id == "done"
+        assert ran == []
+
+    def test_standard_runs_shell_action(self):
+        ran = []
+        skill = self.sandboxed("standard")
+        skill.execution_dag.nodes[1].action = "shell_exec"
+        executor = self.executor(FakeTransport())
+        executor.register_action("shell_exec", lambda node, context: ran.append(node.id))
+        executor.execute(skill)
+        assert ran == ["done"]
+
+
+class TestExecutorRetry:
+    """Test per-node retry policies and their backoff."""
+
+    def flaky(self, failures):
+        calls = []
+
+        def handler(node, context):
+            calls.append(context.timeout_s)
+            if len(calls) <= failures:
+                raise ConnectionError(f"attempt {len(calls)} failed")
+            return {"body": "value"}
+        return handler, calls
+
+    def executor(self, handler, delays):
+        executor = JadeExecutor(transport=FakeTransport(), sleep=delays.append)
+        executor.register_action("http_request", handler)
+        executor.register_action("return_result", passthrough)
+        return executor
+
+    def test_third_attempt_returns_value(self):
+        handler, calls = self.flaky(failures=2)
+        delays = []
+        skill = http_skill(retry={"max_attempts": 3, "backoff_ms": 100})
+        result = self.executor(handler, delays).execute(skill)
+        assert result.outputs["fetch"] == {"body": "value"}
+        assert result.attempts == {"fetch": 3, "done": 1}
+        assert len(calls) == 3
+        assert delays == [0.1, 0.2]
+
+    def test_attempts_exhausted_raises_last_error(self):
+        handler, calls = self.flaky(failures=5)
+        skill = http_skill(retry={"max_attempts": 2, "backoff_ms": 0})
+        with pytest.raises(ExecutionError) as exc:
+            self.executor(handler, []).execute(skill)
+        assert exc.value.code == "NODE_FAILED"
+        assert "attempt 2 failed" in exc.value.message
+        assert len(calls) == 2
+
+    def test_no_retry_policy_runs_once(self):
+        handler, calls = self.flaky(failures=1)
+        with pytest.raises(ExecutionError):
+            self.executor(handler, []).execute(http_skill())
+        assert len(calls) == 1
+
+    def test_retry_on_status(self):
+        statuses = [503, 503, 200]
+
+        def transport(method, url, headers, body, timeout_s):
+            return {"status_code": statuses.pop(0), "body": "", "headers": {}}
+
+        executor = JadeExecutor(transport=transport, sleep=lambda s: None)
+        executor.register_action("return_result", passthrough)
+        skill = http_skill(retry={"max_attempts": 3, "retry_on_status": [503]})
+        result = executor.execute(skill)
+        assert result.outputs["fetch"]["status_code"] == 200
+        assert result.attempts["fetch"] == 3
+
+    def test_last_retryable_status_is_returned(self):
+        transport = FakeTransport(status_code=503)
+        executor = JadeExecutor(transport=transport, sleep=lambda s: None)
+        executor.register_action("return_result", passthrough)
+        skill = http_skill(retry={"max_attempts": 2, "retry_on_status": [503]})
+        assert executor.execute(skill).outputs["fetch"]["status_code"] == 503
+        assert len(transport.calls) == 2
+
+    def test_policy_violation_not_retried(self):
+        transport = FakeTransport()
+        executor = JadeExecutor(transport=transport, sleep=lambda s: None)
+        skill = http_skill(whitelist=("other.example.com",), retry={"max_attempts": 3})
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(skill)
+        assert exc.value.code == "DOMAIN_NOT_WHITELISTED"
+        assert transport.calls == []
+
+    def test_backoff_beyond_node_timeout_gives_up(self):
+        handler, calls = self.flaky(failures=5)
+        delays = []
+        skill = http_skill(timeout_ms=1000, retry={"max_attempts": 5, "backoff_ms": 5000})
+        with pytest.raises(ExecutionError) as exc:
+            self.executor(handler, delays).execute(skill)
+        assert exc.value.code == "NODE_FAILED"
+        assert len(calls) == 1
+        assert delays == []
+
+    def test_attempts_share_node_timeout(self):
+        handler, calls = self.flaky(failures=1)
+        skill = http_skill(timeout_ms=2000, retry={"max_attempts": 2, "backoff_ms": 0})
+        self.executor(handler, []).execute(skill)
+        assert calls[0] == 2.0
+        assert calls[1] <= 2.0
+
+
+class TestExecutorTemplates:
+    """Test interpolation of upstream outputs and inputs into params."""
+
+    def capture(self, seen):
+        def handler(node, context):
+            seen.append(node.params)
+            return {"ok": True}
+        return handler
+
+    def test_resolved_reference(self):
+        seen = []
+        executor = JadeExecutor(transport=FakeTransport(body='{"n": 1}'))
+        executor.register_action("return_result", self.capture(seen))
+        executor.execute(http_skill())
+        assert seen == [{"result": '{"n": 1}'}]
+
+    def test_whole_template_keeps_type_and_inline_is_text(self):
+        seen = []
+        skill = http_skill()
+        skill.execution_dag.nodes[1].params = {
+            "status": "{{fetch.output.status_code}}",
+            "line": "got {{fetch.status_code}} for {{input.city}}",
+            "items": ["{{ input.missing | default:'none' }}"],
+        }
+        executor = JadeExecutor(transport=FakeTransport())
+        executor.register_action("return_result", self.capture(seen))
+        executor.execute(skill, inputs={"city": "Paris"})
+        assert seen == [{"status": 200, "line": "got 200 for Paris", "items": ["none"]}]
+
+    def test_url_interpolated_before_whitelist_check(self):
+        transport = FakeTransport()
+        skill = http_skill(url="https://{{input.host}}/v1")
+        with pytest.raises(ExecutionError) as exc:
+            JadeExecutor(transport=transport).execute(skill, inputs={"host": "evil.example.net"})
+        assert exc.value.code == "DOMAIN_NOT_WHITELISTED"
+        assert transport.calls == []
+
+    def test_env_reference_limited_to_whitelist(self):
+        seen = []
+        skill = http_skill()
+        skill.security.env_whitelist = ["API_REGION"]
+        skill.execution_dag.nodes[1].params = {"region": "{{env.API_REGION}}"}
+        env = {"API_REGION": "eu-west-1", "GITHUB_TOKEN": "secret"}
+        executor = JadeExecutor(transport=FakeTransport(), env=env)
+        executor.register_action("return_result", self.capture(seen))
+        executor.execute(skill)
+        assert seen == [{"region": "eu-west-1"}]
+
+        skill.execution_dag.nodes[1].params = {"token": "{{env.GITHUB_TOKEN}}"}
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(skill)
+        assert exc.value.code == "TEMPLATE_UNRESOLVED"
+
+    def test_unresolved_reference(self):
+        skill = http_skill()
+        skill.execution_dag.nodes[1].params = {"result": "{{fetch.output.missing}}"}
+        executor = JadeExecutor(transport=FakeTransport())
+        executor.register_action("return_result", passthrough)
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(skill)
+        assert exc.value.code == "TEMPLATE_UNRESOLVED"
+        assert exc.value.node_id == "done"
+
+    def test_forward_reference_unresolved(self):
+        skill = http_skill(url="https://api.example.com/{{done.output.id}}")
+        executor = JadeExecutor(transport=FakeTransport())
+        executor.register_action("return_result", passthrough)
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(skill)
+        assert exc.value.code == "TEMPLATE_UNRESOLVED"
+        assert exc.value.node_id == "fetch"
+
+    def test_template_functions(self):
+        seen = []
+        skill = http_skill()
+        skill.execution_dag.nodes[1].params = {
+            "loud": "{{input.city | upper}}",
+            "quiet": "city: {{ input.city | lower }}",
+            "status": "{{fetch.output.status_code | json}}",
+            "head": "{{fetch.output.body | slice:0:5}}",
+            "size": "{{fetch.output.body | length}}",
+            "fallback": "{{input.missing | default:'nice' | upper}}",
+            "host": "{{input.url | domain}}{{input.url | path}}",
+            "point": ["{{input.at | lat}}", "{{input.at | lon}}"],
+        }
+        executor = JadeExecutor(transport=FakeTransport())
+        executor.register_action("return_result", self.capture(seen))
+        inputs = {"city": "Paris", "url": "https://example.com/a/b?q=1", "at": "48.85, 2.35"}
+        executor.execute(skill, inputs=inputs)
+        assert seen == [{
+            "loud": "PARIS", "quiet": "city: paris", "status": "200", "head": '{"ok"', "size": 12,
+            "fallback": "NICE", "host": "example.com/a/b", "point": [48.85, 2.35],
+        }]
+
+    def test_template_function_failure(self):
+        skill = http_skill()
+        skill.execution_dag.nodes[1].params = {"result": "{{input.at | lat}}"}
+        executor = JadeExecutor(transport=FakeTransport())
+        executor.register_action("return_result", passthrough)
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(skill, inputs={"at": "Paris"})
+        assert exc.value.code == "TEMPLATE_UNRESOLVED"
+        assert "'lat'" in str(exc.value)
+
+    @pytest.mark.parametrize("template", ["{{input.n | domain}}", "{{input.n | path}}"])
+    def test_url_function_on_non_url(self, template):
+        skill = http_skill()
+        skill.execution_dag.nodes[1].params = {"result": template}
+        executor = JadeExecutor(transport=FakeTransport())
+        executor.register_action("return_result", passthrough)
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(skill, inputs={"n": 5})
+        assert exc.value.code == "TEMPLATE_UNRESOLVED"
+        assert "expected a URL" in str(exc.value)
+
+    def test_registered_function_errors_are_wrapped(self):
+        skill = http_skill()
+        skill.execution_dag.nodes[1].params = {"result": "{{input.city | lookup}}"}
+        executor = JadeExecutor(transport=FakeTransport())
+        executor.register_action("return_result", passthrough)
+        executor.register_template_function("lookup", lambda value: {}[value])
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(skill, inputs={"city": "Paris"})
+        assert exc.value.code == "TEMPLATE_UNRESOLVED"
+        assert "'lookup'" in str(exc.value)
+
+    def test_deeply_nested_literal(self):
+        skill = http_skill()
+        skill.execution_dag.nodes[1].params = {"result": "{{input.missing | default:" + "[" * 5000 + "]" * 5000 + "}}"}
+        executor = JadeExecutor(transport=FakeTransport())
+        executor.register_action("return_result", passthrough)
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(skill)
+        assert exc.value.code == "TEMPLATE_UNRESOLVED"
+        assert "deeper than 64" in str(exc.value)
+
+    def test_registered_template_function(self):
+        seen = []
+        skill = http_skill()
+        skill.execution_dag.nodes[1].params = {"result": "{{input.city | reverse}}"}
+        executor = JadeExecutor(transport=FakeTransport())
+        executor.register_action("return_result", self.capture(seen))
+        executor.register_template_function("reverse", lambda value: value[::-1])
+        executor.execute(skill, inputs={"city": "Paris"})
+        assert seen == [{"result": "siraP"}]
+
+    def test_unsupported_filter(self):
+        skill = http_skill()
+        skill.execution_dag.nodes[1].params = {"result": "{{fetch.output.body | shout}}"}
+        executor = JadeExecutor(transport=FakeTransport())
+        executor.register_action("return_result", passthrough)
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(skill)
+        assert exc.value.code == "TEMPLATE_UNSUPPORTED"
+
+
+class TestExecutorBranching:
+    """Test conditional edges and skipped branches."""
+
+    def branching_skill(self, status):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"] = {
+            "nodes": [
+                {"id": "check", "action": "probe", "params": {"status": status}},
+                {"id": "on_ok", "action": "record", "params": {}},
+                {"id": "on_error", "action": "record", "params": {}},
+                {"id": "done", "action": "record", "params": {}},
+            ],
+            "edges": [
+                {"from": "check", "to": "on_ok", "condition": 'check.output.status == "ok"'},
+                {"from": "check", "to": "on_error", "condition": 'check.output.status != "ok"'},
+                {"from": "on_ok", "to": "done"},
+                {"from": "on_error", "to": "done"},
+            ],
+            "entry_node": "check",
+            "exit_node": ["done"],
+        }
+        return JadeSkill.from_dict(skill_dict)
+
+    def executor(self):
+        executor = JadeExecutor(transport=FakeTransport())
+        executor.register_action("probe", lambda node, context: {"status": node.params["status"]})
+        executor.register_action("record", passthrough)
+        return executor
+
+    def test_ok_branch_runs_else_skipped(self):
+        result = self.executor().execute(self.branching_skill("ok"))
+        assert "on_ok" in result.outputs
+        assert "on_error" not in result.outputs
+        assert result.skipped == ["on_error"]
+        assert result.outputs["done"] == {"seen": ["check", "on_ok"]}
+
+    def test_dry_run_plan_lists_dependencies_in_topological_order(self):
+        skill = self.branching_skill("ok")
+        # Declare done's incoming edges against execution order
+        skill.execution_dag.edges[2:] = list(reversed(skill.execution_dag.edges[2:]))
+        skill.execution_dag.nodes[3].params = {"via": "{{on_ok.output.seen}} {{input.city}}"}
+        result = self.executor().execute(skill, dry_run=True)
+        assert result.outputs == {}
+        plan = {p.node_id: p for p in result.plan}
+        assert [p.node_id for p in result.plan] == result.order
+        assert plan["done"].depends_on == ["on_ok", "on_error"]
+        assert plan["done"].references == ["on_ok.output.seen", "input.city"]
+        assert plan["on_ok"].gated_by == [{"from": "check", "condition": 'check.output.status == "ok"'}]
+        assert [p.node_id for p in result.plan if p.conditional] == ["on_ok", "on_error", "done"]
+        assert not plan["done"].gated_by
+
+    def test_else_branch_runs(self):
+        result = self.executor().execute(self.branching_skill("error"))
+        assert "on_error" in result.outputs
+        assert result.skipped == ["on_ok"]
+
+    def test_unconditional_edge_always_taken(self):
+        executor = JadeExecutor(transport=FakeTransport())
+        executor.register_action("return_result", passthrough)
+        result = executor.execute(http_skill())
+        assert result.skipped == []
+        assert "done" in result.outputs
+
+    def test_skip_propagates_downstream(self):
+        skill = self.branching_skill("ok")
+        skill.execution_dag.edges[0].condition = "check.output.missing == 1"
+        skill.execution_dag.edges[1].condition = "check.output.missing == 2"
+        result = self.executor().execute(skill)
+        assert result.skipped == ["on_ok", "on_error", "done"]
+        assert list(result.outputs) == ["check"]
+
+    def input_gated_skill(self):
+        skill = self.branching_skill("ok")
+        skill.execution_dag.edges[0].condition = 'input.mode == "fast"'
+        skill.execution_dag.edges[1].condition = 'input.mode != "fast"'
+        return skill
+
+    def test_input_gated_edge_runs(self):
+        result = self.executor().execute(self.input_gated_skill(), inputs={"mode": "fast"})
+        assert "on_ok" in result.outputs
+        assert result.skipped == ["on_error"]
+
+    def test_input_gated_edge_skipped(self):
+        result = self.executor().execute(self.input_gated_skill(), inputs={"mode": "thorough"})
+        assert "on_error" in result.outputs
+        assert result.skipped == ["on_ok"]
+
+
+class TestExecutorParallel:
+    """Test concurrent execution of independent nodes."""
+
+    def fan_out_skill(self):
+        skill_dict = make_skill()
+        skill_dict["execution_dag"] = {
+            "nodes": [
+                {"id": "slow", "action": "sleep", "params": {"delay_s": 0.3}},
+                {"id": "fast", "action": "sleep", "params": {"delay_s": 0.0}},
+                {"id": "merge", "action": "record", "params": {}},
+            ],
+            "edges": [
+                {"from": "slow", "to": "merge"},
+                {"from": "fast", "to": "merge"},
+            ],
+            "entry_node": "slow",
+            "exit_node": ["merge"],
+        }
+        return JadeSkill.from_dict(skill_dict)
+
+    def executor(self, parallel):
+        executor = JadeExecutor(transport=FakeTransport(), parallel=parallel)
+        executor.register_action(
+            "sleep", lambda node, context: time.sleep(node.params["delay_s"]) or node.id
+        )
+        executor.register_action("record", passthrough)
+        return executor
+
+    def test_wave_runs_concurrently(self):
+        skill = self.fan_out_skill()
+        skill.execution_dag.nodes[1].params["delay_s"] = 0.3
+        start = time.monotonic()
+        self.executor(parallel=True).execute(skill)
+        assert time.monotonic() - start < 0.55
+
+    def test_output_order_is_deterministic(self):
+        # 'fast' finishes first, but outputs follow wave order
+        result = self.executor(parallel=True).execute(self.fan_out_skill())
+        assert list(result.outputs) == ["slow", "fast", "merge"]
+        assert result.outputs["merge"] == {"seen": ["fast", "slow"]}
+
+    def test_parallel_matches_sequential(self):
+        parallel = self.executor(parallel=True).execute(self.fan_out_skill())
+        sequential = self.executor(parallel=False).execute(self.fan_out_skill())
+        assert parallel.order == sequential.order
+        assert parallel.outputs == sequential.outputs
+        assert list(parallel.outputs) == list(sequential.outputs)
+
+    def test_first_failure_in_wave_order_raised(self):
+        def broken(node, context):
+            raise RuntimeError(node.id)
+
+        executor = self.executor(parallel=True)
+        executor.register_action("sleep", broken)
+        with pytest.raises(ExecutionError) as exc:
+            executor.execute(self.fan_out_skill())
+        assert exc.value.node_id == "slow"
@@ -0,0 +1,105 @@
+"""
+Test suite for JADE data models.
+Tests equality and hashing of parsed skills, nodes and policies.
+"""
+
+import copy
+import json
+import pytest
+from jade_core.models import (
+    DAGNode, JadeSkill, SandboxLevel, SecurityPolicy,
+    ValidationIssue, ValidationResult, ValidationSeverity,
+)
+from tests.conftest import make_skill
+
+
+class TestSkillEquality:
+    """Structural equality and hashing, for snapshots and dedup."""
+
+    def test_same_document_is_equal(self):
+        a, b = JadeSkill.from_dict(make_skill()), JadeSkill.from_dict(make_skill())
+        assert a == b and hash(a) == hash(b)
+        assert len({a, b}) == 1
+
+    def test_key_order_and_source_document_do_not_matter(self):
+        skill = make_skill()
+        reordered = json.loads(json.dumps(skill, sort_keys=True))
+        reordered["execution_dag"]["nodes"][0]["params"] = dict(
+            reversed(list(skill["execution_dag"]["nodes"][0]["params"].items()))
+        )
+        reordered["attestation"] = {"note": "not a parsed field"}
+        a, b = JadeSkill.from_dict(skill), JadeSkill.from_dict(reordered)
+        assert a.raw_data != b.raw_data
+        assert a == b and hash(a) == hash(b)
+
+    def test_changed_field_is_unequal(self):
+        skill = make_skill()
+        changed = copy.deepcopy(skill)
+        changed["execution_dag"]["nodes"][0]["params"]["input"] = "other"
+        assert JadeSkill.from_dict(skill) != JadeSkill.from_dict(changed)
+        swapped = copy.deepcopy(skill)
+        swapped["security"]["network_whitelist"] = ["b.example.com", "a.example.com"]
+        skill["security"]["network_whitelist"] = ["a.example.com", "b.example.com"]
+        # No normalization: whitelist order is part of the value
+        assert JadeSkill.from_dict(skill) != JadeSkill.from_dict(swapped)
+
+    def test_numbers_compare_as_python_values(self):
+        a = DAGNode("n", "json_parse", {"limit": 1, "nested": [{"x": 2.0}]}, timeout_ms=1000)
+        b = DAGNode("n", "json_parse", {"nested": [{"x": 2}], "limit": 1.0}, timeout_ms=1000)
+        assert a == b and hash(a) == hash(b)
+        assert a != DAGNode("n", "json_parse", {"limit": 1, "nested": [{"x": 2.0}]})
+
+    def test_policy_dedup(self):
+        def policy(level):
+            return SecurityPolicy(["api.example.com"], [], [], 30000, 0, level, [])
+        policies = {policy(SandboxLevel.STRICT), policy(SandboxLevel.STRICT), policy(SandboxLevel.STANDARD)}
+        assert len(policies) == 2
+
+
+class TestValidationSeverity:
+    """Severities order INFO < WARNING < ERROR and parse from their names."""
+
+    def test_ordering(self):
+        info, warning, error = ValidationSeverity.INFO, ValidationSeverity.WARNING, ValidationSeverity.ERROR
+        assert info < warning < error
+        assert error > warning > info
+        assert warning >= warning and warning <= warning
+        assert not error < warning
+        # Not the alphabetical order of the string values
+        assert sorted([warning, error, info]) == [info, warning, error]
+        assert max([info, error, warning]) is error
+
+    def test_compare_with_string_is_an_error(self):
+        with pytest.raises(TypeError):
+            ValidationSeverity.ERROR < "warning"
+
+    def test_equality_and_value_unchanged(self):
+        assert ValidationSeverity.WARNING == "warning"
+        assert ValidationSeverity.ERROR.value == "error"
+        assert len({ValidationSeverity.INFO, ValidationSeverity("info")}) == 1
+
+    @pytest.mark.parametrize("text, expected", [
+        ("error", ValidationSeverity.ERROR),
+        ("Warning", ValidationSeverity.WARNING),
+        (" INFO ", ValidationSeverity.INFO),
+    ])
+    def test_parse(self, text, expected):
+        assert ValidationSeverity.parse(text) is expected
+
+    @pytest.mark.parametrize("text", ["", "fatal", "warn"])
+    def test_parse_unknown(self, text):
+        with pytest.raises(ValueError, match="Unknown severity"):
+            ValidationSeverity.parse(text)
+
+    def test_result_at_least(self):
+        issues = [
+            ValidationIssue(ValidationSeverity.INFO, "A", "a", ""),
+            ValidationIssue(ValidationSeverity.ERROR, "B", "b", ""),
+            ValidationIssue(ValidationSeverity.WARNING, "C", "c", ""),
+        ]
+        result = ValidationResult(valid=False, issues=issues, layers_passed=[1])
+        assert [i.code for i in result.at_least(ValidationSeverity.WARNING).issues] == ["B", "C"]
+        only_errors = result.at_least(ValidationSeverity.ERROR)
+        assert [i.code for i in only_errors.issues] == ["B"]
+        assert only_errors.valid is False and only_errors.layers_passed == [1]
+        assert result.at_least(ValidationSeverity.INFO).issues == issues
@@ -7,6 +7,7 @@ import json
 import math
 import time
 import pytest
+from jade_core.errors import JadeParseError, NotFoundError
 from jade_core.registry import JadeRegistry, PRIOR_ALPHA, PRIOR_BETA
 from jade_core.models import (
     JadeSkill,
@@ -145,6 +146,11 @@ class TestBayesianConfidence:
         with pytest.raises(ValueError, match="not found"):
             registry.submit_attestation(att)
 
+    def test_attestation_for_nonexistent_skill_is_not_found_error(self, registry):
+        att = Attestation(skill_id="ghost_skill", skill_hash="", success=True, execution_time_ms=1)
+        with pytest.raises(NotFoundError):
+            registry.submit_attestation(att)
+
     def test_hash_mismatch_raises(self, registry):
         skill = JadeSkill.from_dict(make_skill())
         registry.register(skill)
@@ -212,6 +218,12 @@ class TestPersistence:
         new_registry = JadeRegistry(index_path=save_path)
         assert new_registry.size == 0
 
+    def test_load_malformed_file_raises_parse_error(self, tmp_path):
+        path = tmp_path / "registry.json"
+        path.write_text("{truncated")
+        with pytest.raises(JadeParseError):
+            JadeRegistry().load(str(path))
+
 
 class TestRegistrySearch:
     """Test skill search functionality."""
@@ -0,0 +1,238 @@
+"""
+Test suite for JADE validation reports.
+Snapshot tests of the rendered human, compact, JSON and SARIF formats.
+"""
+
+import json
+import pytest
+from jade_core.models import Suppression, ValidationSeverity
+from jade_core.report import ReportFormat, format_timings, render, sarif_log
+from jade_core.validator import JadeValidator
+from tests.conftest import make_skill
+
+
+def failing_result(validator):
+    skill = make_skill()
+    skill["execution_dag"]["edges"].append({"from": "step_one", "to": "step_one"})
+    return validator.validate_str(json.dumps(skill, indent=2))
+
+
+class TestHumanReport:
+    """The `jadegate verify` layout, with and without color."""
+
+    def test_pass(self, validator):
+        result = validator.validate_dict(make_skill())
+        assert render(result, name="ok.json", color=False) == "  ✅ PASS ok.json"
+        assert render(result, name="ok.json", color=True) == "  \033[32m✅ PASS\033[0m ok.json"
+
+    def test_pass_with_suppressions(self):
+        validator = JadeValidator(suppressions=[Suppression("DAG_SELF_LOOP"), Suppression("DAG_NO_ROOT")])
+        rendered = render(failing_result(validator), name="s.json", color=False)
+        assert rendered == "  ✅ PASS s.json  (2 suppressed)"
+
+    def test_hidden_issues_counted(self):
+        skill = make_skill()
+        skill["extra_field"] = 1
+        validator = JadeValidator(min_severity=ValidationSeverity.ERROR)
+        result = validator.validate_dict(skill)
+        assert render(result, name="ok.json", color=False) == "  ✅ PASS ok.json  (1 warning hidden)"
+        rendered = render(failing_result(validator), name="bad.json", color=False)
+        assert rendered.splitlines()[0] == "  ❌ FAIL bad.json"
+
+    def test_fail_lists_errors_with_location(self, validator):
+        rendered = render(failing_result(validator), name="bad.json", color=False)
+        lines = rendered.splitlines()
+        assert lines[0] == "  ❌ FAIL bad.json"
+        assert lines[1].startswith("    [DAG_SELF_LOOP] ")
+        assert lines[1].endswith("(line 66, column 7)")
+
+    def test_color_off_when_not_a_tty(self, validator):
+        import io
+        import sys
+        stdout, sys.stdout = sys.stdout, io.StringIO()
+        try:
+            rendered = render(failing_result(validator), name="bad.json")
+        finally:
+            sys.stdout = stdout
+        assert "\033[" not in rendered
+
+    def test_timings_line(self, validator):
+        result = validator.validate_dict(make_skill())
+        last = render(result, color=False, timings=True).splitlines()[-1]
+        assert last.startswith("    timings: L1 ")
+        assert format_timings({}) == "timings: no layers ran"
+
+
+class TestCompactAndJsonReport:
+    """Machine-oriented formats."""
+
+    def test_compact_one_line_per_issue(self, validator):
+        rendered = render(failing_result(validator), ReportFormat.COMPACT, name="bad.json")
+        assert rendered.splitlines()[0].startswith("bad.json:66:7: error: [DAG_SELF_LOOP] ")
+
+    def test_compact_valid(self, validator):
+        result = validator.validate_dict(make_skill())
+        assert render(result, "compact", name="ok.json") == "ok.json: ok"
+
+    def test_json_matches_to_dict(self, validator):
+        result = failing_result(validator)
+        assert json.loads(render(result, ReportFormat.JSON)) == json.loads(json.dumps(result.to_dict()))
+
+
+# The parts of the official SARIF 2.1.0 schema (sarif-schema-2.1.0.json)
+# covering every object the report emits: required members, enums and
+# bounds as published, with additionalProperties kept closed.
+SARIF_SCHEMA_SUBSET = {
+    "$schema": "http://json-schema.org/draft-07/schema#",
+    "type": "object",
+    "required": ["version", "runs"],
+    "additionalProperties": False,
+    "properties": {
+        "$schema": {"type": "string", "format": "uri"},
+        "version": {"enum": ["2.1.0"]},
+        "runs": {"type": ["array", "null"], "items": {"$ref": "#/definitions/run"}},
+    },
+    "definitions": {
+        "run": {
+            "type": "object",
+            "required": ["tool"],
+            "additionalProperties": False,
+            "properties": {
+                "tool": {"$ref": "#/definitions/tool"},
+                "results": {"type": ["array", "null"], "items": {"$ref": "#/definitions/result"}},
+            },
+        },
+        "tool": {
+            "type": "object",
+            "required": ["driver"],
+            "additionalProperties": False,
+            "properties": {"driver": {"$ref": "#/definitions/toolComponent"}},
+        },
+        "toolComponent": {
+            "type": "object",
+            "required": ["name"],
+            "additionalProperties": False,
+            "properties": {
+                "name": {"type": "string"},
+                "version": {"type": "string"},
+                "informationUri": {"type": "string", "format": "uri"},
+                "rules": {"type": "array", "uniqueItems": True,
+                          "items": {"$ref": "#/definitions/reportingDescriptor"}},
+            },
+        },
+        "reportingDescriptor": {
+            "type": "object",
+            "required": ["id"],
+            "additionalProperties": False,
+            "properties": {
+                "id": {"type": "string"},
+                "shortDescription": {"$ref": "#/definitions/multiformatMessageString"},
+            },
+        },
+        "multiformatMessageString": {
+            "type": "object",
+            "required": ["text"],
+            "additionalProperties": False,
+            "properties": {"text": {"type": "string"}},
+        },
+        "message": {
+            "type": "object",
+            "anyOf": [{"required": ["text"]}, {"required": ["id"]}],
+            "additionalProperties": False,
+            "properties": {"text": {"type": "string"}, "id": {"type": "string"}},
+        },
+        "result": {
+            "type": "object",
+            "required": ["message"],
+            "additionalProperties": False,
+            "properties": {
+                "ruleId": {"type": "string"},
+                "ruleIndex": {"type": "integer", "minimum": -1},
+                "level": {"enum": ["none", "note", "warning", "error"]},
+                "message": {"$ref": "#/definitions/message"},
+                "locations": {"type": "array", "items": {"$ref": "#/definitions/location"}},
+                "suppressions": {"type": "array", "uniqueItems": True,
+                                 "items": {"$ref": "#/definitions/suppression"}},
+                "properties": {"type": "object"},
+            },
+        },
+        "location": {
+            "type": "object",
+            "additionalProperties": False,
+            "properties": {
+                "physicalLocation": {"$ref": "#/definitions/physicalLocation"},
+                "logicalLocations": {"type": "array", "items": {"$ref": "#/definitions/logicalLocation"}},
+            },
+        },
+        "physicalLocation": {
+            "type": "object",
+            "anyOf": [{"required": ["address"]}, {"required": ["artifactLocation"]}],
+            "additionalProperties": False,
+            "properties": {
+                "address": {"type": "object"},
+                "artifactLocation": {"$ref": "#/definitions/artifactLocation"},
+                "region": {"$ref": "#/definitions/region"},
+            },
+        },
+        "artifactLocation": {
+            "type": "object",
+            "additionalProperties": False,
+            "properties": {"uri": {"type": "string", "format": "uri-reference"}},
+        },
+        "region": {
+            "type": "object",
+            "additionalProperties": False,
+            "properties": {
+                "startLine": {"type": "integer", "minimum": 1},
+                "startColumn": {"type": "integer", "minimum": 1},
+            },
+        },
+        "logicalLocation": {
+            "type": "object",
+            "additionalProperties": False,
+            "properties": {"fullyQualifiedName": {"type": "string"}},
+        },
+        "suppression": {
+            "type": "object",
+            "required": ["kind"],
+            "additionalProperties": False,
+            "properties": {"kind": {"enum": ["inSource", "external"]}},
+        },
+    },
+}
+
+
+class TestSarifReport:
+    """SARIF 2.1.0 output for code-scanning UIs."""
+
+    def sarif(self):
+        validator = JadeValidator(suppressions=[Suppression("DAG_NO_ROOT")])
+        return sarif_log([
+            ("skills/bad.json", failing_result(validator)),
+            ("skills/ok.json", validator.validate_dict(make_skill())),
+        ])
+
+    def test_matches_sarif_schema(self):
+        jsonschema = pytest.importorskip("jsonschema")
+        jsonschema.validate(self.sarif(), SARIF_SCHEMA_SUBSET)
+
+    def test_issue_mapping(self):
+        run = self.sarif()["runs"][0]
+        rules = [r["id"] for r in run["tool"]["driver"]["rules"]]
+        assert rules == ["DAG_SELF_LOOP", "DAG_NO_ROOT"]
+        loop, no_root = run["results"]
+        assert loop["ruleId"] == "DAG_SELF_LOOP" and loop["level"] == "error"
+        assert loop["locations"][0]["physicalLocation"] == {
+            "artifactLocation": {"uri": "skills/bad.json"},
+            "region": {"startLine": 66, "startColumn": 7},
+        }
+        assert loop["properties"] == {"layer": 2}
+        # Suppressed issues are reported as dismissed notes
+        assert no_root["level"] == "note"
+        assert no_root["suppressions"] == [{"kind": "external"}]
+        assert rules[no_root["ruleIndex"]] == "DAG_NO_ROOT"
+
+    def test_render_single_result(self, validator):
+        log = json.loads(render(validator.validate_dict(make_skill()), ReportFormat.SARIF, name="ok.json"))
+        assert log["version"] == "2.1.0"
+        assert log["runs"][0]["results"] == []
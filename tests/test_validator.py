@@ -3,12 +3,34 @@ Test suite for JADE Validator.
 Tests schema compliance, structural validation, and semantic checks.
 """
 
+import ast
+import copy
 import json
+import random
 import pytest
 from pathlib import Path
-from jade_core.validator import JadeValidator
-from jade_core.models import ValidationSeverity
-from tests.conftest import make_skill
+from jade_core.validator import (
+    MAX_TAG_LENGTH, SUPPORTED_JADE_VERSION_RANGE, JadeValidator, identifier_problem, normalize_tags,
+    parse_rfc3339, json_nesting_depth, parse_semver, semver_precedence, skill_id_matches_path, validate_str,
+)
+from jade_core.models import (
+    FreshnessPolicy,
+    IssueCode,
+    JadeSkill,
+    ResourceLimits,
+    SignerRole,
+    SkillIdPolicy,
+    Suppression,
+    TrustLevel,
+    TrustPolicy,
+    ValidationIssue,
+    ValidationLayer,
+    ValidationSeverity,
+)
+from jade_core.cache import MemoryValidationCache
+from jade_core.layers import CustomLayer
+from jade_core.crypto import JadeKeyPair, KeyRegistry, key_role, sign_skill as sign_skill_as
+from tests.conftest import PROJECT_ROOT, make_skill, sign_skill, cosign_skill
 
 
 class TestValidatorBasics:
@@ -36,6 +58,65 @@ class TestValidatorBasics:
         result = validator.validate_file(str(empty_file))
         assert not result.valid
 
+    def test_validate_file_with_bom(self, validator, minimal_skill_dict, tmp_path):
+        bom_file = tmp_path / "bom.json"
+        bom_file.write_bytes(b"\xef\xbb\xbf" + json.dumps(minimal_skill_dict).encode("utf-8"))
+        result = validator.validate_file(str(bom_file))
+        assert result.valid, f"Errors: {[i.message for i in result.errors]}"
+
+    def test_validate_reader(self, validator, minimal_skill_dict):
+        import io
+        stream = io.BytesIO(b"\xef\xbb\xbf" + json.dumps(minimal_skill_dict).encode("utf-8"))
+        assert validator.validate_reader(stream).valid
+        assert [i.code for i in validator.validate_reader(io.BytesIO(b"{")).issues] == ["INVALID_JSON"]
+
+    def test_validate_bytes_invalid_utf8(self, validator):
+        result = validator.validate_bytes(b'{"name": "\xff"}')
+        assert [i.code for i in result.issues] == ["INVALID_JSON"]
+        assert "UTF-8" in result.issues[0].message
+
+    def test_duplicate_security_key_rejected(self, validator, minimal_skill_dict, tmp_path):
+        text = json.dumps(minimal_skill_dict)
+        permissive = json.dumps({"network_whitelist": ["*"], "sandbox_level": "permissive"})
+        crafted = tmp_path / "crafted.json"
+        crafted.write_text(text[:-1] + f', "security": {permissive}}}', encoding="utf-8")
+        result = validator.validate_file(str(crafted))
+        assert not result.valid
+        [issue] = result.issues
+        assert issue.code == "DUPLICATE_KEY"
+        assert issue.path == "security"
+
+    def test_nested_duplicate_key_path(self, validator):
+        result = validator.validate_str('{"a": [{"b": {"c": 1, "c": 2}}]}')
+        assert [(i.code, i.path) for i in result.issues] == [("DUPLICATE_KEY", "a[0].b.c")]
+
+    def test_validate_str(self, validator, minimal_skill_dict):
+        assert validator.validate_str(json.dumps(minimal_skill_dict)).valid
+        result = validator.validate_str("[1, 2]")
+        assert [i.code for i in result.issues] == ["INVALID_JSON"]
+
+    def test_validate_str_round_trips_json(self, minimal_skill_dict):
+        report = json.loads(validate_str(json.dumps(minimal_skill_dict)))
+        assert report["valid"] is True
+        assert report["layers_passed"] == [1, 2, 3, 4, 5]
+        report = json.loads(validate_str("{invalid"))
+        assert report["valid"] is False
+        assert report["issues"][0]["code"] == "INVALID_JSON"
+
+    def test_in_memory_resources_need_no_files(self, minimal_skill_dict, monkeypatch):
+        schema_dir = Path(__file__).parent.parent / "jade_schema"
+        schema = json.loads((schema_dir / "jade-schema-v1.json").read_text(encoding="utf-8"))
+        catalog = json.loads((schema_dir / "allowed_atomic_actions.json").read_text(encoding="utf-8"))
+
+        def no_files(*args, **kwargs):
+            raise AssertionError("validator opened a file")
+        monkeypatch.setattr("builtins.open", no_files)
+        validator = JadeValidator(schema=schema, action_catalog=catalog)
+        skill = sign_skill(minimal_skill_dict, JadeKeyPair.generate())
+        result = validator.validate_dict(skill)
+        assert result.valid, f"Errors: {[i.message for i in result.errors]}"
+        assert "json_parse" in validator.allowed_actions
+
     def test_validate_dict(self, validator, minimal_skill_dict):
         result = validator.validate_dict(minimal_skill_dict)
         assert result.valid, f"Errors: {[i.message for i in result.errors]}"
@@ -55,6 +136,79 @@ class TestValidatorRequiredFields:
         assert any(i.code == "MISSING_FIELD" for i in result.errors)
 
 
+class TestValidatorUnknownFields:
+    """Keys the format does not define are warned about, with a suggestion."""
+
+    def test_misspelled_top_level_field(self, validator):
+        skill = make_skill()
+        skill["securty"] = skill.pop("security")
+        result = validator.validate_dict(skill)
+        unknown = [i for i in result.warnings if i.code == "UNKNOWN_FIELD"]
+        assert len(unknown) == 1
+        assert unknown[0].message == "Unknown field 'securty', did you mean 'security'?"
+        assert unknown[0].path == "securty"
+        # The real field is still missing
+        assert [i.code for i in result.errors] == ["MISSING_FIELD"]
+
+    def test_nested_fields_and_locations(self, validator):
+        skill = make_skill()
+        skill["security"]["sandbox_levl"] = "strict"
+        skill["execution_dag"]["nodes"][1]["acton"] = "return_result"
+        skill["made_up"] = True
+        result = validator.validate_str(json.dumps(skill, indent=2))
+        assert result.valid
+        by_path = {i.path: i for i in result.warnings if i.code == "UNKNOWN_FIELD"}
+        assert set(by_path) == {"security.sandbox_levl", "execution_dag.nodes[1].acton", "made_up"}
+        assert by_path["execution_dag.nodes[1].acton"].message.endswith("did you mean 'action'?")
+        assert by_path["execution_dag.nodes[1].acton"].node_id == "step_two"
+        assert by_path["made_up"].message == "Unknown field 'made_up'"
+        assert by_path["security.sandbox_levl"].location is not None
+
+    def test_free_form_values_not_checked(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][0]["params"]["anything"] = 1
+        skill["input_schema"] = {"required_params": [], "whatever": {}}
+        result = validator.validate_dict(skill)
+        assert not [i for i in result.issues if i.code == "UNKNOWN_FIELD"]
+
+
+class TestValidatorSandboxLevel:
+    """A missing sandbox_level defaults to strict; an unknown one is an error."""
+
+    def test_absent_defaults_to_strict_with_warning(self, validator):
+        skill = make_skill()
+        del skill["security"]["sandbox_level"]
+        result = validator.validate_dict(skill)
+        assert result.valid
+        [warning] = [i for i in result.warnings if i.code == "SANDBOX_MISSING"]
+        assert warning.message.startswith("security.sandbox_level is not set; defaulting to 'strict'")
+        assert warning.path == "security"
+
+    def test_empty_is_treated_as_missing(self, validator):
+        skill = make_skill()
+        skill["security"]["sandbox_level"] = ""
+        result = validator.validate_dict(skill)
+        assert result.valid
+        [warning] = [i for i in result.warnings if i.code == "SANDBOX_MISSING"]
+        assert "is empty" in warning.message
+        assert warning.path == "security.sandbox_level"
+
+    def test_typo_is_an_error_with_suggestion(self, validator):
+        skill = make_skill()
+        skill["security"]["sandbox_level"] = "stirct"
+        result = validator.validate_dict(skill)
+        assert [(i.code, i.message) for i in result.errors] == [
+            ("INVALID_SANDBOX_LEVEL", "Unknown sandbox level 'stirct', did you mean 'strict'?"),
+        ]
+
+    def test_unrelated_value_lists_levels(self, validator):
+        skill = make_skill()
+        skill["security"]["sandbox_level"] = 3
+        [error] = validator.validate_dict(skill).errors
+        assert error.code == "INVALID_SANDBOX_LEVEL"
+        assert error.message == "Unknown sandbox level 3 (expected one of: strict, standard, permissive)"
+
+
 class TestValidatorVersion:
     """Test version validation."""
 
@@ -74,6 +228,57 @@ class TestValidatorVersion:
         result = validator.validate_dict(skill)
         assert not result.valid
 
+    def test_newer_minor_version_warns(self, validator):
+        skill = make_skill(jade_version="1.3.0")
+        result = validator.validate_dict(skill)
+        assert result.valid
+        assert any(i.code == "JADE_VERSION_TOO_NEW" for i in result.warnings)
+
+    def test_newer_major_version_warns_and_fails(self, validator):
+        result = validator.validate_dict(make_skill(jade_version="2.0.0"))
+        codes = {i.code for i in result.issues}
+        assert {"JADE_VERSION_TOO_NEW", "UNSUPPORTED_VERSION"} <= codes
+
+    def test_older_version_rejected(self, validator):
+        result = validator.validate_dict(make_skill(jade_version="0.9.0"))
+        assert not result.valid
+
+    def test_upper_bound_pre_release_is_supported(self, validator):
+        result = validator.validate_dict(make_skill(jade_version="1.1.0-rc1"))
+        assert result.valid
+        assert not any(i.code == "JADE_VERSION_TOO_NEW" for i in result.issues)
+
+    def test_lower_bound_pre_release_rejected(self, validator):
+        result = validator.validate_dict(make_skill(jade_version="1.0.0-rc1"))
+        assert [i.code for i in result.errors] == ["UNSUPPORTED_VERSION"]
+
+    def test_semver_precedence(self):
+        ordered = ["1.0.0-alpha", "1.0.0-alpha.1", "1.0.0-alpha.beta", "1.0.0-beta", "1.0.0-beta.2",
+                   "1.0.0-beta.11", "1.0.0-rc.1", "1.0.0", "1.0.1-0", "1.0.1"]
+        keys = [semver_precedence(parse_semver(v)) for v in ordered]
+        assert keys == sorted(keys) and len(set(keys)) == len(keys)
+
+    def test_supported_range_is_exposed(self):
+        assert SUPPORTED_JADE_VERSION_RANGE == ("1.0.0", "1.1.0")
+
+    @pytest.mark.parametrize("version", ["1.0.0", "2.10.3", "1.0.0-beta.1", "1.0.0+build.5", "1.0.0-rc.1+sha.abc"])
+    def test_metadata_semver_accepted(self, validator, version):
+        skill = make_skill()
+        skill["metadata"]["version"] = version
+        result = validator.validate_dict(skill)
+        assert not any(i.code == "INVALID_METADATA_VERSION" for i in result.issues)
+
+    @pytest.mark.parametrize("version", ["latest", "1.0", "01.0.0", "1.0.0-", "v1.0.0", "1.0.0-beta..1"])
+    def test_metadata_semver_rejected(self, validator, version):
+        skill = make_skill()
+        skill["metadata"]["version"] = version
+        result = validator.validate_dict(skill)
+        assert any(i.code == "INVALID_METADATA_VERSION" for i in result.errors)
+
+    def test_parse_semver(self):
+        assert parse_semver("1.2.3-alpha+build") == (1, 2, 3, "alpha")
+        assert parse_semver("1.2") is None
+
 
 class TestValidatorSkillId:
     """Test skill_id format validation."""
@@ -109,6 +314,929 @@ class TestValidatorSkillId:
         result = validator.validate_dict(skill)
         assert not result.valid
 
+    @pytest.mark.parametrize("skill_id,default_ok,namespaced_ok", [
+        ("weather_api", True, False),
+        ("acme/weather_api", False, True),
+        ("acme-corp/weather_api", False, True),
+        ("acme/Weather", False, False),
+        ("acme/weather/extra", False, False),
+        ("/weather_api", False, False),
+        ("acme/", False, False),
+    ])
+    def test_skill_id_policies(self, skill_id, default_ok, namespaced_ok):
+        skill = make_skill(skill_id=skill_id)
+        for policy, ok in ((SkillIdPolicy(), default_ok), (SkillIdPolicy.namespaced(), namespaced_ok)):
+            result = JadeValidator(skill_id_policy=policy).validate_dict(skill)
+            flagged = any(i.code == "INVALID_SKILL_ID" for i in result.errors)
+            assert flagged != ok, (skill_id, policy.pattern)
+
+    def test_skill_id_matches_filename(self, tmp_path):
+        validator = JadeValidator(skill_id_policy=SkillIdPolicy(match_filename=True))
+        good = tmp_path / "test_skill.json"
+        good.write_text(json.dumps(make_skill()), encoding="utf-8")
+        assert validator.validate_file(str(good)).valid
+        bad = tmp_path / "renamed.json"
+        bad.write_text(json.dumps(make_skill()), encoding="utf-8")
+        result = validator.validate_file(str(bad))
+        assert [i.code for i in result.errors] == ["SKILL_ID_FILENAME_MISMATCH"]
+        # Not checked without a file
+        assert validator.validate_dict(make_skill()).valid
+
+    def test_namespaced_id_matches_directory(self):
+        assert skill_id_matches_path("acme/weather", "skills/acme/weather.json")
+        assert not skill_id_matches_path("acme/weather", "skills/other/weather.json")
+        assert skill_id_matches_path("weather", "skills/acme/weather.json")
+
+
+class TestValidatorLayers:
+    """Test that issues carry their layer and passing layers are reported."""
+
+    def test_clean_skill_passes_all_layers(self, validator):
+        result = validator.validate_dict(make_skill())
+        assert result.layers_passed == [1, 2, 3, 4, 5]
+
+    def test_issue_tagged_with_failing_layer(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["edges"].append({"from": "step_one", "to": "step_one"})
+        result = validator.validate_dict(skill)
+        self_loop = next(i for i in result.issues if i.code == "DAG_SELF_LOOP")
+        assert self_loop.layer == ValidationLayer.DAG
+        assert ValidationLayer.DAG not in result.layers_passed
+        assert ValidationLayer.SCHEMA in result.layers_passed
+
+    def test_layers_in_serialized_result(self, validator):
+        skill = make_skill(jade_version="abc")
+        data = json.loads(json.dumps(validator.validate_dict(skill).to_dict()))
+        assert 1 not in data["layers_passed"]
+        assert data["issues"][0]["layer"] == 1
+
+
+class TestValidatorLayerSelection:
+    """Test running a subset of layers."""
+
+    def test_dag_only_skips_other_layers(self, validator):
+        skill = make_skill(jade_version="abc")
+        result = validator.validate_with_layers(skill, [ValidationLayer.DAG])
+        assert result.valid
+        assert result.layers_passed == [2]
+        assert result.layers_skipped == [1, 3, 4, 5]
+
+    def test_skipped_layer_issues_not_reported(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][0]["params"]["input"] = "eval('x')"
+        result = validator.validate_with_layers(skill, [1, 2])
+        assert not any(i.code == "SEC_EXEC_CODE" for i in result.issues)
+        assert 3 in result.layers_skipped
+
+    def test_skip_signature_layer(self, validator):
+        skill = make_skill()
+        skill["jade_signature"] = {"public_key": "AAAA", "signature": "AAAA"}
+        assert not validator.validate_dict(skill).valid
+        result = validator.validate_with_layers(skill, [1, 2, 3, 4])
+        assert result.valid
+        assert result.layers_skipped == [5]
+
+    def test_parse_errors_reported_even_when_schema_skipped(self, validator):
+        skill = make_skill()
+        del skill["trigger"]
+        result = validator.validate_with_layers(skill, [ValidationLayer.DAG])
+        assert not result.valid
+        assert any(i.code == "MISSING_FIELD" for i in result.errors)
+
+    def test_unknown_layer_rejected(self, validator):
+        with pytest.raises(ValueError, match="Unknown validation layer"):
+            validator.validate_with_layers(make_skill(), [9])
+
+    def test_validate_dict_runs_every_layer(self, validator):
+        result = validator.validate_dict(make_skill())
+        assert result.layers_skipped == []
+
+    def test_timings_cover_layers_that_ran(self, validator):
+        result = validator.validate_with_layers(make_skill(), [1, 3])
+        assert sorted(result.timings_ms) == [1, 3]
+        assert all(ms >= 0 for ms in result.timings_ms.values())
+        assert set(result.to_dict()["timings_ms"]) == {"1", "3"}
+
+
+class CmdbDomainsLayer(CustomLayer):
+    """Example organization policy: whitelisted domains must be in the CMDB."""
+
+    name = "cmdb-domains"
+
+    def __init__(self, cmdb):
+        self.cmdb = set(cmdb)
+
+    def validate(self, skill, raw):
+        return [
+            ValidationIssue(
+                severity=ValidationSeverity.ERROR,
+                code="ORG_UNKNOWN_DOMAIN",
+                message=f"Domain '{domain}' is not in the CMDB",
+                path=f"security.network_whitelist[{i}]",
+            )
+            for i, domain in enumerate(skill.security.network_whitelist)
+            if domain not in self.cmdb
+        ]
+
+
+class TestValidatorCustomLayers:
+    """Caller-supplied layers run after the built-in five."""
+
+    def skill(self, *domains):
+        return make_skill(security={
+            "network_whitelist": list(domains),
+            "file_permissions": {"read": [], "write": []},
+            "max_execution_time_ms": 10000,
+            "max_retries": 0,
+            "sandbox_level": "strict",
+            "dangerous_patterns": [],
+        })
+
+    def test_custom_layer_numbered_after_builtins(self):
+        validator = JadeValidator(custom_layers=[CmdbDomainsLayer(["api.internal.example"])])
+        result = validator.validate_dict(self.skill("api.internal.example", "evil.example.com"))
+        assert not result.valid
+        [issue] = result.errors
+        assert (issue.code, issue.layer) == ("ORG_UNKNOWN_DOMAIN", 6)
+        assert result.layers_passed == [1, 2, 3, 4, 5]
+        assert 6 in result.timings_ms
+        assert validator.custom_layers[6].name == "cmdb-domains"
+
+    def test_passing_custom_layer(self):
+        validator = JadeValidator(custom_layers=[CmdbDomainsLayer(["api.internal.example"])])
+        result = validator.validate_dict(self.skill("api.internal.example"))
+        assert result.valid
+        assert result.layers_passed == [1, 2, 3, 4, 5, 6]
+
+    def test_custom_layer_selection_and_suppression(self):
+        validator = JadeValidator(
+            custom_layers=[CmdbDomainsLayer([])],
+            suppressions=[Suppression("ORG_UNKNOWN_DOMAIN")],
+        )
+        skill = self.skill("evil.example.com")
+        assert validator.validate_with_layers(skill, [1, 2]).layers_skipped == [3, 4, 5, 6]
+        result = validator.validate_dict(skill)
+        assert result.valid
+        assert [i.suppressed for i in result.issues if i.layer == 6] == [True]
+
+    def test_unknown_layer_lists_custom_layers(self):
+        validator = JadeValidator(custom_layers=[CmdbDomainsLayer([])])
+        with pytest.raises(ValueError, match=r"\[1, 2, 3, 4, 5, 6\]"):
+            validator.validate_with_layers(make_skill(), [7])
+
+
+class TestValidatorConcurrency:
+    """One validator shared by many threads, and cheap reconfigurable clones."""
+
+    def snapshot(self, result):
+        data = result.to_dict()
+        del data["checked_at"], data["timings_ms"]
+        return data
+
+    def test_shared_validator_across_threads(self):
+        import threading
+        validator = JadeValidator(suppressions=[Suppression("DAG_MULTIPLE_ROOTS")])
+        looped = make_skill()
+        looped["execution_dag"]["edges"].append({"from": "step_one", "to": "step_one"})
+        injected = make_skill()
+        injected["execution_dag"]["nodes"][0]["params"]["input"] = "eval('x')"
+        skills = [make_skill(), looped, injected, sign_skill(make_skill(), JadeKeyPair.generate())]
+        expected = [self.snapshot(validator.validate_dict(s)) for s in skills]
+
+        failures = []
+
+        def worker(offset):
+            for i in range(20):
+                k = (offset + i) % len(skills)
+                if self.snapshot(validator.validate_dict(skills[k])) != expected[k]:
+                    failures.append(k)
+
+        threads = [threading.Thread(target=worker, args=(n,)) for n in range(8)]
+        for t in threads:
+            t.start()
+        for t in threads:
+            t.join()
+        assert failures == []
+        assert [e["valid"] for e in expected] == [True, False, False, True]
+
+    def test_clone_is_independent(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][0]["action"] = "custom_action"
+        clone = validator.clone()
+        clone.set_allowed_actions(validator.allowed_actions + ["custom_action"])
+        clone.set_suppressions([Suppression("DAG_SELF_LOOP")])
+        assert clone.validate_dict(skill).valid
+        assert not validator.validate_dict(skill).valid
+        assert validator.suppressions == []
+
+
+class TestValidatorPreviousVersion:
+    """Policy relaxed relative to a trusted previous version."""
+
+    def downgraded(self):
+        skill = make_skill()
+        skill["metadata"]["version"] = "1.0.1"
+        skill["security"]["sandbox_level"] = "standard"
+        return skill
+
+    def test_strict_to_standard_is_flagged(self):
+        validator = JadeValidator(previous_version=JadeSkill.from_dict(make_skill()))
+        result = validator.validate_dict(self.downgraded())
+        assert not result.valid
+        [issue] = [i for i in result.errors if i.code == IssueCode.SEC_POLICY_RELAXED]
+        assert issue.path == "security.sandbox_level"
+        assert issue.layer == ValidationLayer.SECURITY
+        assert "Sandbox relaxed from strict to standard" in issue.message
+        assert "1.0.0" in issue.message
+        # Without a previous version the same skill is fine
+        assert JadeValidator().validate_dict(self.downgraded()).valid
+
+    def test_acknowledged_by_suppression(self):
+        validator = JadeValidator(
+            previous_version=JadeSkill.from_dict(make_skill()),
+            suppressions=[Suppression("SEC_POLICY_RELAXED")],
+        )
+        result = validator.validate_dict(self.downgraded())
+        assert result.valid
+        assert [i.code for i in result.issues if i.suppressed] == ["SEC_POLICY_RELAXED"]
+
+    def test_unchanged_or_tightened_policy_passes(self, validator):
+        validator.set_previous_version(JadeSkill.from_dict(self.downgraded()))
+        assert validator.validate_dict(self.downgraded()).valid
+        assert validator.validate_dict(make_skill()).valid
+        validator.set_previous_version(None)
+        assert validator.previous_version is None
+
+
+class SpyLayer(CustomLayer):
+    """Counts how often validation reaches the layers."""
+    name = "spy"
+
+    def __init__(self):
+        self.calls = 0
+
+    def validate(self, skill, raw):
+        self.calls += 1
+        return []
+
+    def cache_token(self):
+        return "spy"
+
+
+class CachedCmdbDomainsLayer(CmdbDomainsLayer):
+    """CmdbDomainsLayer whose verdicts may be cached, keyed by its CMDB."""
+
+    def cache_token(self):
+        return ",".join(sorted(self.cmdb))
+
+
+class TestValidatorNetworkWhitelist:
+    """IP and CIDR whitelist entries are judged by the range they cover."""
+
+    def skill(self, entry, url):
+        skill = make_skill(security={"network_whitelist": [entry], "sandbox_level": "strict"})
+        skill["execution_dag"]["nodes"][0] = {"id": "step_one", "action": "http_get", "params": {"url": url}}
+        return skill
+
+    @pytest.mark.parametrize("entry,url", [
+        ("8.8.8.0/24", "https://8.8.8.8/resolve"),
+        ("2606:4700::/32", "https://[2606:4700::1111]/resolve"),
+    ])
+    def test_public_cidr_passes_with_warning(self, validator, entry, url):
+        result = validator.validate_dict(self.skill(entry, url))
+        assert result.valid, [i.message for i in result.errors]
+        [issue] = result.issues
+        assert (issue.code, issue.severity) == ("SEC_SUSPICIOUS_NETWORK", ValidationSeverity.WARNING)
+        assert "public IP address or range" in issue.message
+
+    @pytest.mark.parametrize("entry,url,label", [
+        ("10.0.0.0/8", "https://10.1.2.3/", "private (RFC 1918) range"),
+        ("203.0.113.0/24", "https://203.0.113.5/", "documentation range"),
+        ("2001:db8::/32", "https://[2001:db8::5]/", "documentation range"),
+    ])
+    def test_non_public_cidr_is_error_in_strict_sandbox(self, validator, entry, url, label):
+        result = validator.validate_dict(self.skill(entry, url))
+        [issue] = result.errors
+        assert issue.code == "SEC_SUSPICIOUS_NETWORK"
+        assert label in issue.message
+
+
+class TestValidatorCache:
+    """Unchanged skill files are answered from the cache."""
+
+    def setup(self, **kwargs):
+        spy = SpyLayer()
+        cache = MemoryValidationCache()
+        return JadeValidator(custom_layers=[spy], cache=cache, **kwargs), spy, cache
+
+    def test_hit_skips_layers(self):
+        validator, spy, cache = self.setup()
+        text = json.dumps(make_skill())
+        first = validator.validate_str(text)
+        second = validator.validate_str(text)
+        assert first.valid and second.valid
+        assert spy.calls == 1
+        assert (cache.hits, cache.misses) == (1, 1)
+        assert {**second.to_dict(), "checked_at": None} == {**first.to_dict(), "checked_at": None}
+
+    def test_mutating_a_result_does_not_poison_the_cache(self):
+        validator, spy, cache = self.setup()
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][0]["params"]["hint"] = "password"
+        text = json.dumps(skill)
+        first = validator.validate_str(text)
+        assert first.valid and first.issues
+        first.valid = False
+        first.issues.clear()
+        second = validator.validate_str(text)
+        assert second.valid and second.issues
+        second.issues.clear()
+        assert validator.validate_str(text).issues
+        assert spy.calls == 1
+
+    def test_hit_is_stamped_with_lookup_time(self, monkeypatch):
+        import jade_core.validator as validator_module
+        validator, _, _ = self.setup()
+        text = json.dumps(make_skill())
+        first = validator.validate_str(text)
+        monkeypatch.setattr(validator_module.time, "time", lambda: first.checked_at + 60.0)
+        assert validator.validate_str(text).checked_at == first.checked_at + 60.0
+
+    def test_layer_configuration_is_part_of_the_key(self):
+        cache = MemoryValidationCache()
+        skill = json.dumps(make_skill(security={"network_whitelist": ["api.example.com"]}))
+        lax = JadeValidator(custom_layers=[CachedCmdbDomainsLayer(["api.example.com"])], cache=cache)
+        strict = JadeValidator(custom_layers=[CachedCmdbDomainsLayer([])], cache=cache)
+        assert lax.validate_str(skill).valid
+        result = strict.validate_str(skill)
+        assert [i.code for i in result.errors] == ["ORG_UNKNOWN_DOMAIN"]
+        assert (cache.hits, len(cache)) == (0, 1)
+
+    def test_layer_without_cache_token_disables_caching(self):
+        cache = MemoryValidationCache()
+        skill = json.dumps(make_skill(security={"network_whitelist": ["api.example.com"]}))
+        lax = JadeValidator(custom_layers=[CmdbDomainsLayer(["api.example.com"])], cache=cache)
+        strict = JadeValidator(custom_layers=[CmdbDomainsLayer([])], cache=cache)
+        assert lax.validate_str(skill).valid
+        assert not strict.validate_str(skill).valid
+        assert len(cache) == 0
+
+    def test_changed_content_or_settings_miss(self):
+        validator, spy, cache = self.setup()
+        skill = make_skill()
+        validator.validate_str(json.dumps(skill))
+        skill["metadata"]["description"] = "changed"
+        validator.validate_str(json.dumps(skill))
+        assert spy.calls == 2
+        # A new ruleset (here, suppressions) invalidates every entry
+        validator.set_suppressions([Suppression("DAG_MULTIPLE_ROOTS")])
+        validator.validate_str(json.dumps(skill))
+        assert spy.calls == 3
+        assert len(cache) == 3
+
+    def test_invalid_results_are_not_cached(self):
+        validator, spy, cache = self.setup()
+        skill = make_skill()
+        skill["execution_dag"]["edges"].append({"from": "step_one", "to": "step_one"})
+        text = json.dumps(skill)
+        assert not validator.validate_str(text).valid
+        assert not validator.validate_str(text).valid
+        assert spy.calls == 2
+        assert len(cache) == 0
+
+    def test_freshness_policy_bypasses_cache(self):
+        validator, spy, cache = self.setup(freshness_policy=FreshnessPolicy(max_age_s=86400.0))
+        text = json.dumps(make_skill())
+        validator.validate_str(text)
+        validator.validate_str(text)
+        assert spy.calls == 2
+        assert len(cache) == 0
+
+    def test_file_hit_uses_streamed_hash(self, tmp_path, monkeypatch):
+        validator, spy, cache = self.setup()
+        text = json.dumps(make_skill())
+        path = tmp_path / "skill.json"
+        path.write_bytes(b"\xef\xbb\xbf" + text.encode("utf-8"))
+        # The streamed hash of the file matches the hash of the same text
+        assert validator.validate_str(text).valid
+        monkeypatch.setattr(JadeValidator, "_validate_uncached", None)
+        assert validator.validate_file(str(path)).valid
+        assert spy.calls == 1
+        assert (cache.hits, cache.misses) == (1, 1)
+
+    def test_engine_version_is_part_of_the_key(self, monkeypatch):
+        import jade_core
+        validator, spy, cache = self.setup()
+        text = json.dumps(make_skill())
+        validator.validate_str(text)
+        monkeypatch.setattr(jade_core, "__version__", "99.0.0")
+        JadeValidator(custom_layers=[spy], cache=cache).validate_str(text)
+        assert spy.calls == 2
+
+
+class TestValidatorTrustedScan:
+    """With trust_signed_scan, trusted signed content skips re-scanning."""
+
+    def setup(self, monkeypatch, trusted=True):
+        keypair = JadeKeyPair.generate()
+        registry = KeyRegistry()
+        if trusted:
+            registry.add(keypair.public_key)
+        validator = JadeValidator(key_registry=registry, cache=MemoryValidationCache(), trust_signed_scan=True)
+        scans = []
+        check_all = validator._security_engine.check_all
+        monkeypatch.setattr(validator._security_engine, "check_all", lambda skill: scans.append(1) or check_all(skill))
+        return validator, keypair, scans
+
+    def warned_skill(self):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][0]["params"]["hint"] = "password"
+        return skill
+
+    def test_signed_content_scanned_once(self, monkeypatch):
+        validator, keypair, scans = self.setup(monkeypatch)
+        skill = sign_skill(self.warned_skill(), keypair)
+        first = validator.validate_dict(copy.deepcopy(skill))
+        second = validator.validate_dict(copy.deepcopy(skill))
+        assert first.valid and second.valid
+        assert len(scans) == 1
+        assert "SEC_DATA_EXFIL_RISK" in {i.code for i in first.warnings}
+        assert [(i.code, i.layer, i.path) for i in second.issues] == [(i.code, i.layer, i.path) for i in first.issues]
+        assert ValidationLayer.SECURITY in second.layers_passed
+
+    def test_tampered_content_rescanned(self, monkeypatch):
+        validator, keypair, scans = self.setup(monkeypatch)
+        skill = sign_skill(make_skill(), keypair)
+        assert validator.validate_dict(copy.deepcopy(skill)).valid
+        skill["execution_dag"]["nodes"][0]["params"]["input"] = "os.system('rm -rf /')"
+        result = validator.validate_dict(skill)
+        assert len(scans) == 2
+        codes = {i.code for i in result.errors}
+        assert "HASH_MISMATCH" in codes and any(c.startswith("SEC_") for c in codes)
+
+    def test_sha512_content_hash_trusted(self, monkeypatch):
+        from jade_core.crypto import HashAlgorithm
+        validator, keypair, scans = self.setup(monkeypatch)
+        skill = sign_skill_as(make_skill(), keypair.private_key, hash_algorithm=HashAlgorithm.SHA512)
+        assert validator.validate_dict(copy.deepcopy(skill)).valid
+        assert validator.validate_dict(copy.deepcopy(skill)).valid
+        assert len(scans) == 1
+
+    def test_untrusted_key_always_scanned(self, monkeypatch):
+        validator, keypair, scans = self.setup(monkeypatch, trusted=False)
+        skill = sign_skill(make_skill(), keypair)
+        validator.validate_dict(copy.deepcopy(skill))
+        validator.validate_dict(copy.deepcopy(skill))
+        assert len(scans) == 2
+
+    def test_needs_registry_and_cache(self):
+        with pytest.raises(ValueError, match="key_registry and a cache"):
+            JadeValidator(cache=MemoryValidationCache(), trust_signed_scan=True)
+        with pytest.raises(ValueError):
+            JadeValidator(key_registry=KeyRegistry(), trust_signed_scan=True)
+
+
+class TestValidatorValidateAll:
+    """Test continue-on-error validation."""
+
+    def test_collects_issues_past_missing_field(self, validator):
+        skill = make_skill()
+        del skill["trigger"]
+        skill["execution_dag"]["edges"].append({"from": "step_one", "to": "step_one"})
+        assert not any(i.code == "DAG_SELF_LOOP" for i in validator.validate_dict(skill).issues)
+
+        result = validator.validate_all(skill)
+        codes = {i.code for i in result.issues}
+        assert {"MISSING_FIELD", "DAG_SELF_LOOP"} <= codes
+        assert not result.valid
+
+    def test_malformed_section_skips_dependent_layers(self, validator):
+        skill = make_skill()
+        skill["security"]["sandbox_level"] = "bogus"
+        skill["metadata"]["version"] = "latest"
+        result = validator.validate_all(skill)
+        codes = {i.code for i in result.issues}
+        # The bad sandbox level gets its own error rather than a PARSE_ERROR
+        assert {"INVALID_SANDBOX_LEVEL", "INVALID_METADATA_VERSION"} <= codes
+        assert "PARSE_ERROR" not in codes
+        assert result.layers_skipped == [3, 4]
+        assert ValidationLayer.DAG in result.layers_passed
+
+    def test_clean_skill_matches_validate_dict(self, validator):
+        skill = make_skill()
+        full = validator.validate_all(skill)
+        assert full.valid
+        assert full.layers_passed == validator.validate_dict(skill).layers_passed
+
+
+class TestValidatorSerialization:
+    """Skills that cannot be canonicalized are flagged, not passed."""
+
+    def test_non_finite_number_is_serialize_error(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][0]["params"]["threshold"] = float("nan")
+        result = validator.validate_dict(skill)
+        assert not result.valid
+        errors = [i for i in result.errors if i.code == "SERIALIZE_ERROR"]
+        assert len(errors) == 1
+        assert errors[0].layer == ValidationLayer.SCHEMA
+        assert result.skill_hash == ""
+        assert result.content_hash is None
+        assert ValidationLayer.SIGNATURE in result.layers_skipped
+
+    def test_nan_from_json_text_is_flagged(self, validator):
+        skill = make_skill()
+        text = json.dumps(skill).replace('"input": "{{input.input_text}}"', '"input": NaN')
+        result = validator.validate_str(text)
+        assert any(i.code == "SERIALIZE_ERROR" for i in result.errors)
+
+    def test_unserializable_value_is_flagged(self, validator):
+        skill = make_skill()
+        skill["metadata"]["tags"] = {"not", "a", "list"}
+        result = validator.validate_all(skill)
+        assert any(i.code == "SERIALIZE_ERROR" for i in result.errors)
+
+
+class TestValidatorValue:
+    """Already-parsed JSON values validate like the text they came from."""
+
+    def test_valid_value(self, validator):
+        value = json.loads(json.dumps(make_skill()))
+        result = validator.validate_value(value)
+        assert result.valid, f"Errors: {[i.message for i in result.errors]}"
+        assert result.content_hash == validator.validate_str(json.dumps(value)).content_hash
+
+    def test_structurally_invalid_value(self, validator):
+        value = make_skill()
+        del value["execution_dag"]
+        value["security"]["sandbox_level"] = "lax"
+        result = validator.validate_value(value)
+        codes = {i.code for i in result.errors}
+        assert {"MISSING_FIELD", "INVALID_SANDBOX_LEVEL"} <= codes
+        [missing] = [i for i in result.errors if i.code == "MISSING_FIELD"]
+        assert missing.layer == ValidationLayer.SCHEMA
+
+    @pytest.mark.parametrize("value,reason", [
+        ([make_skill()], "top-level value must be an object, got list"),
+        ("skill", "top-level value must be an object, got str"),
+        (None, "top-level value must be an object, got NoneType"),
+        ({"skill_id": b"bytes"}, "Not a JSON value"),
+        ({"tags": {"a", "set"}}, "Not a JSON value"),
+    ])
+    def test_not_a_skill_is_parse_error(self, validator, value, reason):
+        result = validator.validate_value(value)
+        [issue] = result.issues
+        assert issue.code == "INVALID_JSON" and issue.layer == ValidationLayer.SCHEMA
+        assert reason in issue.message
+
+    def test_circular_value(self, validator):
+        value = make_skill()
+        value["metadata"]["self"] = value
+        [issue] = validator.validate_value(value).issues
+        assert issue.code == "INVALID_JSON" and "Circular reference" in issue.message
+
+    def test_limits_apply(self):
+        validator = JadeValidator(resource_limits=ResourceLimits(max_bytes=100))
+        [issue] = validator.validate_value(make_skill()).issues
+        assert issue.code == "RESOURCE_LIMIT"
+
+
+class TestValidatorStreaming:
+    """Test the lazy issue stream behind validate_with_layers."""
+
+    def noisy_skill(self):
+        skill = make_skill(jade_version="abc")
+        skill["execution_dag"]["edges"].append({"from": "step_one", "to": "step_one"})
+        skill["execution_dag"]["nodes"][0]["params"]["input"] = "eval('x')"
+        return skill
+
+    def test_stream_matches_validate_dict(self, validator):
+        skill = self.noisy_skill()
+        streamed = [(i.code, i.layer) for i in validator.validate_streaming(skill)]
+        assert streamed == [(i.code, i.layer) for i in validator.validate_dict(skill).issues]
+        layers = [layer for _, layer in streamed]
+        assert layers == sorted(layers)
+
+    def test_early_abort_skips_later_layers(self, validator, monkeypatch):
+        def fail(skill):
+            raise AssertionError("security layer should not run")
+        monkeypatch.setattr(validator._security_engine, "check_all", fail)
+        stream = validator.validate_streaming(self.noisy_skill())
+        first_error = next(i for i in stream if i.severity == ValidationSeverity.ERROR)
+        assert first_error.layer == ValidationLayer.SCHEMA
+
+    def test_stream_respects_layers(self, validator):
+        issues = list(validator.validate_streaming(self.noisy_skill(), layers=[2]))
+        assert "DAG_SELF_LOOP" in {i.code for i in issues}
+        assert {i.layer for i in issues} == {ValidationLayer.DAG}
+
+    def test_stream_ends_at_missing_field(self, validator):
+        skill = self.noisy_skill()
+        del skill["trigger"]
+        codes = {i.code for i in validator.validate_streaming(skill)}
+        assert codes == {"MISSING_FIELD"}
+        assert "DAG_SELF_LOOP" in {i.code for i in validator.validate_streaming(skill, continue_on_error=True)}
+
+
+class TestValidatorIssueOrder:
+    """Issue order is stable and follows declaration order."""
+
+    def multi_orphan_skill(self):
+        skill = make_skill()
+        names = ["orphan_zeta", "orphan_alpha", "orphan_mid", "orphan_beta", "orphan_omega"]
+        skill["execution_dag"]["nodes"] += [
+            {"id": name, "action": "json_parse", "params": {}} for name in names
+        ]
+        return skill, names
+
+    def test_same_order_across_runs(self, validator):
+        skill, _ = self.multi_orphan_skill()
+        first = [(i.code, i.path) for i in validator.validate_all(skill).issues]
+        second = [(i.code, i.path) for i in validator.validate_all(skill).issues]
+        assert first == second
+
+    def test_orphans_in_declaration_order(self, validator):
+        skill, names = self.multi_orphan_skill()
+        issues = validator.validate_all(skill).issues
+        for code in ("DAG_UNREACHABLE_NODE", "DAG_NO_INCOMING", "DAG_DEAD_END"):
+            flagged = [i.node_id for i in issues if i.code == code]
+            assert flagged == names, code
+
+
+class TestValidatorProperties:
+    """Validation is idempotent and does not depend on node or edge order."""
+
+    ACTIONS = ["json_parse", "return_result", "http_get", "condition_check", "", "bogus_action"]
+    CONDITIONS = [None, None, "success", "failure", "n1.output.x == 1", 'n0.status == "ok"', "(("]
+
+    def random_skill(self, rng):
+        skill = make_skill()
+        ids = [f"n{i}" for i in range(rng.randint(1, 7))]
+        if rng.random() < 0.1:
+            ids.append(ids[0])
+        nodes = [
+            {"id": i, "action": rng.choice(self.ACTIONS),
+             "params": {"input": rng.choice(["x", "{{n0.output.data}}", "{{n3.output}}"])}}
+            for i in ids
+        ]
+        edges = []
+        for _ in range(rng.randint(0, 2 * len(ids))):
+            edge = {"from": rng.choice(ids + ["ghost"]), "to": rng.choice(ids)}
+            condition = rng.choice(self.CONDITIONS)
+            if condition:
+                edge["condition"] = condition
+            edges.append(edge)
+        skill["execution_dag"] = {
+            "nodes": nodes, "edges": edges, "entry_node": rng.choice(ids),
+            "exit_node": rng.sample(ids, rng.randint(1, min(2, len(ids)))),
+        }
+        return skill
+
+    @staticmethod
+    def report(result):
+        data = result.to_dict()
+        del data["timings_ms"], data["checked_at"]
+        return data
+
+    @staticmethod
+    def outcome(result):
+        # Messages and paths name nodes and edges in declaration order, so
+        # only what was found, where, and how bad is compared
+        found = sorted((i.code, i.severity.value, i.layer, i.node_id or "") for i in result.issues)
+        return result.valid, found
+
+    @pytest.mark.parametrize("seed", range(200))
+    def test_idempotent(self, validator, seed):
+        skill = self.random_skill(random.Random(seed))
+        first = validator.validate_dict(copy.deepcopy(skill))
+        assert self.report(validator.validate_dict(copy.deepcopy(skill))) == self.report(first)
+
+    @pytest.mark.parametrize("seed", range(200))
+    def test_order_independent(self, validator, seed):
+        rng = random.Random(seed)
+        skill = self.random_skill(rng)
+        shuffled = copy.deepcopy(skill)
+        rng.shuffle(shuffled["execution_dag"]["nodes"])
+        rng.shuffle(shuffled["execution_dag"]["edges"])
+        assert self.outcome(validator.validate_dict(shuffled)) == self.outcome(validator.validate_dict(skill))
+
+    def test_duplicate_exit_id_not_checked_against_output(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"].append(
+            {"id": "step_two", "action": "condition_check", "params": {"input": "x"}}
+        )
+        codes = [i.code for i in validator.validate_dict(skill).issues]
+        reordered = copy.deepcopy(skill)
+        reordered["execution_dag"]["nodes"].reverse()
+        assert "DAG_DUPLICATE_NODE" in codes
+        assert "OUTPUT_SCHEMA_MISMATCH" not in codes
+        assert sorted(codes) == sorted(i.code for i in validator.validate_dict(reordered).issues)
+
+
+class TestValidatorResourceLimits:
+    """Hostile documents are rejected before the layers recurse into them."""
+
+    def deep_schema_skill(self, depth):
+        nested = {"type": "string"}
+        for _ in range(depth):
+            nested = {"type": "object", "properties": {"inner": nested}}
+        return make_skill(input_schema={"type": "object", "properties": {"x": nested}})
+
+    def test_pathologically_deep_schema_file(self, validator, tmp_path):
+        # Too deep for json.dumps, so assembled as text
+        deep = '{"type": "object", "properties": {"inner": ' * 5000 + '{"type": "string"}' + "}}" * 5000
+        path = tmp_path / "deep.json"
+        path.write_text(json.dumps(make_skill(input_schema="DEEP")).replace('"DEEP"', deep), encoding="utf-8")
+        result = validator.validate_file(str(path))
+        assert [i.code for i in result.issues] == ["RESOURCE_LIMIT"]
+        assert "deeper than 64" in result.issues[0].message
+
+    def test_deep_dict_stops_even_with_continue_on_error(self, validator):
+        result = validator.validate_all(self.deep_schema_skill(5000))
+        assert [i.code for i in result.issues] == ["RESOURCE_LIMIT"]
+
+    def test_node_and_size_limits(self, minimal_skill_dict):
+        text = json.dumps(minimal_skill_dict)
+        small = JadeValidator(resource_limits=ResourceLimits(max_bytes=len(text) - 1))
+        assert [i.code for i in small.validate_str(text).issues] == ["RESOURCE_LIMIT"]
+        one_node = JadeValidator(resource_limits=ResourceLimits(max_nodes=1))
+        [issue] = one_node.validate_dict(minimal_skill_dict).issues
+        assert "2 nodes" in issue.message
+
+    def test_reader_stops_at_size_limit(self, minimal_skill_dict):
+        import io
+        data = json.dumps(minimal_skill_dict).encode("utf-8")
+        stream = io.BytesIO(data + b" " * 10000)
+        small = JadeValidator(resource_limits=ResourceLimits(max_bytes=len(data)))
+        assert [i.code for i in small.validate_reader(stream).issues] == ["RESOURCE_LIMIT"]
+        assert stream.tell() == len(data) + 1
+
+    def test_reader_size_excludes_byte_order_mark(self, minimal_skill_dict):
+        import io
+        data = json.dumps(minimal_skill_dict).encode("utf-8")
+        exact = JadeValidator(resource_limits=ResourceLimits(max_bytes=len(data)))
+        assert exact.validate_reader(io.BytesIO(b"\xef\xbb\xbf" + data)).valid
+        stream = io.BytesIO(b"\xef\xbb\xbf" + data + b" ")
+        assert [i.code for i in exact.validate_reader(stream).issues] == ["RESOURCE_LIMIT"]
+
+    def test_deeply_nested_condition_in_small_file(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["edges"][0]["condition"] = "!" * 1200 + "(" * 1200 + "success" + ")" * 1200
+        text = json.dumps(skill)
+        assert len(text) < ResourceLimits().max_bytes
+        result = validator.validate_str(text)
+        assert [i.code for i in result.errors] == ["DAG_INVALID_CONDITION"]
+        assert "nested too deeply" in result.errors[0].message
+
+    def test_limits_can_be_disabled(self):
+        skill = self.deep_schema_skill(70)
+        assert not JadeValidator().validate_dict(skill).valid
+        unlimited = JadeValidator(resource_limits=ResourceLimits(max_depth=None))
+        assert unlimited.validate_dict(skill).valid
+
+    def test_json_nesting_depth_ignores_strings(self):
+        assert json_nesting_depth('{"a": [1, {"b": "[[[{{"}]}') == 3
+
+
+class TestValidatorReservedNames:
+    """Node ids may not shadow the input and env reference roots."""
+
+    def test_node_id_env(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][0]["id"] = "env"
+        skill["execution_dag"]["edges"][0]["from"] = "env"
+        skill["execution_dag"]["entry_node"] = "env"
+        result = validator.validate_dict(skill)
+        [issue] = [i for i in result.errors if i.code == "RESERVED_NAME"]
+        assert issue.layer == ValidationLayer.SCHEMA
+        assert issue.path == "execution_dag.nodes[0].id" and issue.node_id == "env"
+        assert "environment" in issue.message
+
+    def test_node_id_input(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][1]["id"] = "input"
+        skill["execution_dag"]["edges"][0]["to"] = "input"
+        skill["execution_dag"]["exit_node"] = ["input"]
+        codes = [i.code for i in validator.validate_dict(skill).errors]
+        assert codes == ["RESERVED_NAME"]
+
+    def test_param_keys_are_not_reserved(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][0]["params"].update({"node": "x", "env": "prod"})
+        result = validator.validate_dict(skill)
+        assert result.valid, f"Errors: {[i.message for i in result.errors]}"
+
+
+class TestValidatorIdentifiers:
+    """Identifiers may not hold control or bidirectional formatting characters."""
+
+    def test_node_id_with_nul(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][0]["id"] = "step\x00one"
+        skill["execution_dag"]["edges"][0]["from"] = "step\x00one"
+        skill["execution_dag"]["entry_node"] = "step\x00one"
+        result = validator.validate_dict(skill)
+        [issue] = result.errors
+        assert issue.code == "INVALID_IDENTIFIER" and issue.layer == ValidationLayer.SCHEMA
+        assert issue.path == "execution_dag.nodes[0].id" and issue.node_id == "step\x00one"
+        assert "U+0000" in issue.message and "\x00" not in issue.message
+
+    def test_name_with_right_to_left_override(self, validator):
+        skill = make_skill()
+        skill["metadata"]["name"] = "Invoice \u202efdp.exe"
+        [issue] = validator.validate_dict(skill).errors
+        assert (issue.code, issue.path) == ("INVALID_IDENTIFIER", "metadata.name")
+        assert "bidirectional formatting character U+202E" in issue.message
+
+    def test_skill_id_with_trailing_newline(self, validator):
+        skill = make_skill()
+        skill["skill_id"] += "\n"  # Slips past the skill_id pattern, whose '$' allows it
+        [issue] = validator.validate_dict(skill).errors
+        assert (issue.code, issue.path) == ("INVALID_IDENTIFIER", "skill_id")
+
+    @pytest.mark.parametrize("value, problem", [
+        ("weather api", None),
+        ("Météo ☀ 天气", None),
+        ("tab\there", "control character U+0009"),
+        ("esc\x1b[31m", "control character U+001B"),
+        ("c1\x85", "control character U+0085"),
+        ("isolate\u2066", "bidirectional formatting character U+2066"),
+    ])
+    def test_identifier_problem(self, value, problem):
+        assert identifier_problem(value) == problem
+
+
+class TestValidatorParamsWithoutAction:
+    """Params on a node without an action are flagged, but don't fail validation."""
+
+    def placeholder_skill(self, params):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"].append({"id": "placeholder", "action": "", "params": params})
+        skill["execution_dag"]["edges"].append({"from": "step_one", "to": "placeholder"})
+        skill["execution_dag"]["exit_node"].append("placeholder")
+        return skill
+
+    def test_params_without_action_warns(self, validator):
+        result = validator.validate_dict(self.placeholder_skill({"format": "json"}))
+        assert result.valid and not result.errors
+        [issue] = [i for i in result.warnings if i.code == "PARAMS_WITHOUT_ACTION"]
+        assert issue.layer == ValidationLayer.SCHEMA and issue.node_id == "placeholder"
+        assert issue.path == "execution_dag.nodes.placeholder.params"
+        assert "'placeholder'" in issue.message and "format" in issue.message
+
+    def test_inert_placeholder_is_clean(self, validator):
+        result = validator.validate_dict(self.placeholder_skill({}))
+        assert "PARAMS_WITHOUT_ACTION" not in {i.code for i in result.issues}
+
+
+class TestValidatorLocations:
+    """Issues from JSON text carry the line and column they point at."""
+
+    def locate(self, text, needle, occurrence=1):
+        """1-based (line, column) of the n-th occurrence of `needle` in `text`."""
+        offset = -1
+        for _ in range(occurrence):
+            offset = text.index(needle, offset + 1)
+        line = text.count("\n", 0, offset) + 1
+        return line, offset - (text.rfind("\n", 0, offset) + 1) + 1
+
+    def test_parse_error_location(self, validator):
+        text = '{\n  "skill_id": "x",\n  "metadata": {,}\n}'
+        [issue] = validator.validate_str(text).issues
+        assert issue.code == "INVALID_JSON"
+        assert issue.location == (3, 16)
+        assert issue.to_dict()["location"] == [3, 16]
+
+    def test_duplicate_node_id_location(self, validator):
+        skill = make_skill()
+        nodes = skill["execution_dag"]["nodes"]
+        nodes.append(dict(nodes[0]))
+        text = json.dumps(skill, indent=2)
+        [issue] = [i for i in validator.validate_str(text).errors if i.code == "DAG_DUPLICATE_NODE"]
+        # The second node's "id" key
+        second_node = self.locate(text, '"id"', occurrence=len(nodes))
+        assert issue.location == second_node
+
+    def test_unknown_edge_reference_location(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["edges"][0]["to"] = "ghost"
+        text = json.dumps(skill, indent=2)
+        [issue] = [i for i in validator.validate_str(text).errors if i.code == "DAG_INVALID_EDGE_DST"]
+        assert issue.location == self.locate(text, '"to"')
+
+    def test_node_keyed_path(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][1]["action"] = "no_such_action"
+        text = json.dumps(skill, indent=2)
+        [issue] = [i for i in validator.validate_str(text).issues if i.code == "UNKNOWN_ACTION"]
+        assert issue.path == "execution_dag.nodes.step_two.action"
+        assert issue.location == self.locate(text, '"action"', occurrence=2)
+
+    def test_dict_input_has_no_location(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["edges"][0]["to"] = "ghost"
+        assert all(i.location is None for i in validator.validate_dict(skill).issues)
+
 
 class TestValidatorMetadata:
     """Test metadata validation."""
@@ -125,6 +1253,29 @@ class TestValidatorMetadata:
         result = validator.validate_dict(skill)
         assert not result.valid
 
+    def test_case_insensitive_duplicate_tags(self, validator):
+        skill = make_skill()
+        skill["metadata"]["tags"] = ["Weather", "api", " weather "]
+        result = validator.validate_dict(skill)
+        assert result.valid
+        [dup] = [i for i in result.warnings if i.code == "DUPLICATE_TAGS"]
+        assert "weather" in dup.message
+        assert any(i.code == "TAG_WHITESPACE" and i.path == "metadata.tags[2]" for i in result.warnings)
+
+    def test_empty_tag_rejected(self, validator):
+        skill = make_skill()
+        skill["metadata"]["tags"] = ["test", "  "]
+        result = validator.validate_dict(skill)
+        assert [(i.code, i.path) for i in result.errors] == [("EMPTY_TAG", "metadata.tags[1]")]
+
+    def test_long_tag_warns(self, validator):
+        skill = make_skill()
+        skill["metadata"]["tags"] = ["x" * (MAX_TAG_LENGTH + 1)]
+        assert [i.code for i in validator.validate_dict(skill).warnings] == ["TAG_TOO_LONG"]
+
+    def test_normalize_tags(self):
+        assert normalize_tags([" Weather", "API", "weather", "", "api "]) == ["weather", "api"]
+
     def test_invalid_version_in_metadata(self, validator):
         skill = make_skill()
         skill["metadata"]["version"] = "not-a-version"
@@ -132,6 +1283,64 @@ class TestValidatorMetadata:
         assert not result.valid
 
 
+class TestValidatorIOSchemas:
+    """Test input_schema / output_schema validation."""
+
+    def _io_issues(self, validator, **schemas):
+        skill = make_skill()
+        skill.update(schemas)
+        result = validator.validate_dict(skill)
+        return [i for i in result.issues if i.code == "INVALID_IO_SCHEMA"]
+
+    def test_non_object_schema_rejected(self, validator):
+        issues = self._io_issues(validator, input_schema=42)
+        assert len(issues) == 1
+        assert "must be a JSON object" in issues[0].message
+
+    def test_param_list_form_accepted(self, validator):
+        assert self._io_issues(validator, input_schema={
+            "required_params": [{"name": "city", "type": "string", "description": "City"}],
+            "optional_params": [{"name": "units", "type": "string", "description": "Units"}],
+        }, output_schema={
+            "fields": [{"name": "temp", "type": "number", "description": "Temperature"}],
+        }) == []
+
+    def test_param_list_bad_type_and_duplicate(self, validator):
+        issues = self._io_issues(validator, input_schema={
+            "required_params": [{"name": "city", "type": "text", "description": "City"}],
+            "optional_params": [{"name": "city", "type": "string", "description": "Again"}],
+        })
+        messages = " ".join(i.message for i in issues)
+        assert "unrecognized type 'text'" in messages
+        assert "Duplicate parameter name 'city'" in messages
+
+    def test_json_schema_form_accepted(self, validator):
+        assert self._io_issues(validator, input_schema={
+            "type": "object",
+            "properties": {"city": {"type": "string"}},
+            "required": ["city"],
+        }) == []
+
+    def test_json_schema_unknown_type(self, validator):
+        issues = self._io_issues(validator, output_schema={"type": "map"})
+        assert [i.path for i in issues] == ["output_schema.type"]
+
+    def test_json_schema_required_must_reference_properties(self, validator):
+        issues = self._io_issues(validator, input_schema={
+            "type": "object",
+            "properties": {"city": {"type": "string"}},
+            "required": ["country"],
+        })
+        assert len(issues) == 1
+        assert "'country'" in issues[0].message
+
+    def test_json_schema_properties_must_be_object(self, validator):
+        result = validator.validate_dict({**make_skill(), "input_schema": {
+            "type": "object", "properties": ["city"],
+        }})
+        assert not result.valid
+
+
 class TestValidateGoldenSkills:
     """Validate all 5 golden skills pass validation."""
 
@@ -160,6 +1369,24 @@ class TestValidateGoldenSkills:
         )
 
 
+class TestValidateBundledSkills:
+    """Every skill shipped under skills/ and converted_skills/ validates."""
+
+    BUNDLED = sorted(
+        str(path.relative_to(PROJECT_ROOT))
+        for pattern in ("skills/**/*.json", "converted_skills/*.json")
+        for path in PROJECT_ROOT.glob(pattern)
+    )
+
+    def test_corpus_found(self):
+        assert len(self.BUNDLED) > 50
+
+    @pytest.mark.parametrize("skill_path", BUNDLED)
+    def test_bundled_skill_valid(self, validator, skill_path):
+        result = validator.validate_file(str(PROJECT_ROOT / skill_path))
+        assert result.valid, [(i.code, i.message) for i in result.errors]
+
+
 class TestValidatorHashComputation:
     """Test that skill hashes are computed correctly."""
 
@@ -175,3 +1402,633 @@ class TestValidatorHashComputation:
         r1 = validator.validate_dict(skill1)
         r2 = validator.validate_dict(skill2)
         assert r1.skill_hash != r2.skill_hash
+
+
+class TestValidatorSignature:
+    """Test root signature verification over canonical content."""
+
+    def test_signed_skill_passes(self, validator):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        result = validator.validate_dict(skill)
+        assert result.valid, f"Errors: {[i.message for i in result.errors]}"
+
+    def test_tampered_skill_fails(self, validator):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        skill["security"]["network_whitelist"] = ["evil.example.com"]
+        result = validator.validate_dict(skill)
+        assert not result.valid
+        assert any(i.code == "SIG_INVALID" for i in result.errors)
+
+    def test_shuffled_keys_same_hash_and_verify(self, validator):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        # Re-emit every object with reversed key order
+        def reverse(obj):
+            if isinstance(obj, dict):
+                return {k: reverse(obj[k]) for k in reversed(list(obj))}
+            if isinstance(obj, list):
+                return [reverse(v) for v in obj]
+            return obj
+        shuffled = json.loads(json.dumps(reverse(skill), indent=4))
+        r1 = validator.validate_dict(skill)
+        r2 = validator.validate_dict(shuffled)
+        assert r1.skill_hash == r2.skill_hash
+        assert r2.valid, f"Errors: {[i.message for i in r2.errors]}"
+
+    def test_content_hash_exposed(self, validator):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        result = validator.validate_dict(skill)
+        assert result.content_hash == skill["jade_signature"]["content_hash"]
+        assert result.content_hash.startswith("sha256:")
+        assert result.to_dict()["content_hash"] == result.content_hash
+
+    def test_stale_content_hash_fails(self, validator):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        skill["jade_signature"]["content_hash"] = "sha256:" + "0" * 64
+        result = validator.validate_dict(skill)
+        assert not result.valid
+        assert any(i.code == "HASH_MISMATCH" for i in result.errors)
+        # The signature itself still verifies; only the hash is stale
+        assert not any(i.code == "SIG_INVALID" for i in result.errors)
+
+    @pytest.mark.parametrize("declared, fragment", [
+        ("sha256:" + "0" * 63, "64 lowercase hex characters"),
+        ("sha256:" + "A" * 64, "64 lowercase hex characters"),
+        ("md5:" + "0" * 32, "unsupported hash algorithm 'md5'"),
+        ("blake3:" + "0" * 64, "unsupported hash algorithm 'blake3'"),
+        ("SHA512:" + "0" * 128, "unsupported hash algorithm 'SHA512'"),
+        ("sha512:" + "0" * 64, "128 lowercase hex characters"),
+        ("0" * 64, "expected '<algorithm>:<hex digest>'"),
+    ])
+    def test_malformed_content_hash(self, validator, declared, fragment):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        skill["jade_signature"]["content_hash"] = declared
+        result = validator.validate_dict(skill)
+        assert [i.code for i in result.errors] == ["HASH_FORMAT_INVALID"]
+        assert fragment in result.errors[0].message
+        assert result.errors[0].layer == ValidationLayer.SIGNATURE
+
+    def test_well_formed_content_hash(self):
+        from jade_core.crypto import HashAlgorithm, parse_content_hash
+        assert parse_content_hash("sha256:" + "ab" * 32) == ("sha256", "ab" * 32)
+        algorithm, digest = parse_content_hash("sha512:" + "cd" * 64)
+        assert algorithm is HashAlgorithm.SHA512 and digest == "cd" * 64
+
+    def test_signature_records_for_audit(self, validator):
+        root, co = JadeKeyPair.generate(), JadeKeyPair.generate()
+        skill = cosign_skill(sign_skill(make_skill(), root), co)
+        skill["community_signatures"].append(dict(skill["community_signatures"][0], signature="AAAA"))
+        result = validator.validate_dict(skill)
+        assert [(r.kind, r.fingerprint, r.algorithm, r.verified) for r in result.signatures] == [
+            ("root", root.fingerprint, "ed25519", True),
+            ("community", co.fingerprint, "ed25519", True),
+            ("community", co.fingerprint, "ed25519", False),
+        ]
+        assert result.to_dict()["signatures"][0]["signed_at"] == "2026-02-21T00:00:00Z"
+
+    def test_failed_root_signature_still_recorded(self, validator):
+        key = JadeKeyPair.generate()
+        skill = sign_skill(make_skill(), key)
+        skill["metadata"]["description"] = "tampered"
+        result = validator.validate_dict(skill)
+        assert not result.valid
+        [record] = result.signatures
+        assert (record.kind, record.fingerprint, record.verified) == ("root", key.fingerprint, False)
+
+
+class TestValidatorDetachedSignature:
+    """Signatures kept in a sidecar verify like embedded ones."""
+
+    def split(self, skill):
+        sig = skill.pop("jade_signature")
+        return json.dumps(skill, indent=2).encode("utf-8"), sig["signature"], sig["public_key"]
+
+    def test_parity_with_embedded(self, validator):
+        key = JadeKeyPair.generate()
+        signed = sign_skill(make_skill(), key)
+        embedded = validator.validate_dict(signed)
+        detached = validator.verify_detached(*self.split(dict(signed)))
+        assert embedded.valid and detached.valid
+        assert detached.content_hash == embedded.content_hash
+        assert [(r.kind, r.fingerprint, r.verified) for r in detached.signatures] == [
+            ("root", key.fingerprint, True),
+        ]
+
+    def test_tampered_content_fails_like_embedded(self, validator):
+        signed = sign_skill(make_skill(), JadeKeyPair.generate())
+        signed["metadata"]["description"] = "tampered"
+        assert "SIG_INVALID" in {i.code for i in validator.validate_dict(signed).errors}
+        result = validator.verify_detached(*self.split(dict(signed)))
+        [issue] = result.errors
+        assert (issue.code, issue.path, issue.location) == ("SIG_INVALID", "jade_signature.signature", None)
+
+    def test_embedded_signature_is_ignored(self, validator):
+        key, other = JadeKeyPair.generate(), JadeKeyPair.generate()
+        signed = sign_skill(make_skill(), key)
+        data, signature, _ = self.split(dict(signed))
+        # The file keeps a valid embedded block, but the sidecar key is wrong
+        with_block = json.dumps(signed).encode("utf-8")
+        result = validator.verify_detached(with_block, signature, other.public_key)
+        assert not result.valid
+        assert result.signatures[0].fingerprint == other.fingerprint
+
+    def test_pem_key_and_bom(self, validator):
+        import base64
+        from jade_core.crypto import public_key_to_pem
+        key = JadeKeyPair.generate()
+        data, signature, public_key = self.split(sign_skill(make_skill(), key))
+        result = validator.verify_detached(b"\xef\xbb\xbf" + data, signature, public_key_to_pem(base64.b64decode(public_key)))
+        assert result.valid, f"Errors: {[i.message for i in result.errors]}"
+
+
+class TestValidatorSignatureAlgorithms:
+    """Test routing of jade_signature.algorithm to the right verifier."""
+
+    def secp256k1_signed(self):
+        import base64
+        from jade_core.crypto import (
+            canonical_json, content_hash, secp256k1_public_key, secp256k1_sign,
+        )
+        skill = make_skill()
+        content = canonical_json(skill)
+        secret = bytes(range(1, 33))
+        skill["jade_signature"] = {
+            "signer": "k1-signer",
+            "algorithm": "ecdsa-secp256k1",
+            "public_key": base64.b64encode(secp256k1_public_key(secret)).decode(),
+            "content_hash": content_hash(content),
+            "signature": base64.b64encode(secp256k1_sign(secret, content.encode("utf-8"))).decode(),
+            "signed_at": "2026-02-21T00:00:00Z",
+        }
+        return skill
+
+    def test_secp256k1_signature_passes(self, validator):
+        result = validator.validate_dict(self.secp256k1_signed())
+        assert result.valid, f"Errors: {[i.message for i in result.errors]}"
+
+    def test_secp256k1_tamper_detected(self, validator):
+        skill = self.secp256k1_signed()
+        skill["metadata"]["name"] = "Tampered"
+        assert "SIG_INVALID" in [i.code for i in validator.validate_dict(skill).errors]
+
+    @pytest.mark.parametrize("algorithm", ["ed448", "rsa-pss"])
+    def test_unsupported_algorithm(self, validator, algorithm):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        skill["jade_signature"]["algorithm"] = algorithm
+        codes = [i.code for i in validator.validate_dict(skill).errors]
+        assert "UNSUPPORTED_ALGORITHM" in codes
+        assert "SIG_INVALID" not in codes
+
+    def test_ed25519_key_claimed_as_secp256k1(self, validator):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        skill["jade_signature"]["algorithm"] = "ecdsa-secp256k1"
+        assert "SIG_INVALID" in [i.code for i in validator.validate_dict(skill).errors]
+
+
+class TestValidatorCommunitySignatures:
+    """Test community co-signature verification."""
+
+    def test_valid_cosigners_counted(self, validator):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        cosign_skill(skill, JadeKeyPair.generate(), signer="alice")
+        cosign_skill(skill, JadeKeyPair.generate(), signer="bob", trust_level="verified")
+        result = validator.validate_dict(skill)
+        assert result.valid
+        assert result.valid_community_signers == 2
+        assert [r.signer for r in result.community_results] == ["alice", "bob"]
+        assert result.community_results[1].trust_level == "verified"
+        assert all(len(r.fingerprint) == 16 for r in result.community_results)
+
+    def test_invalid_cosigner_is_warning(self, validator):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        cosign_skill(skill, JadeKeyPair.generate(), signer="alice")
+        cosign_skill(skill, JadeKeyPair.generate(), signer="mallory")
+        skill["community_signatures"][1]["signature"] = skill["community_signatures"][0]["signature"]
+        result = validator.validate_dict(skill)
+        assert result.valid
+        assert result.valid_community_signers == 1
+        warnings = [i for i in result.warnings if i.code == "COMMUNITY_SIG_INVALID"]
+        assert len(warnings) == 1
+        assert "mallory" in warnings[0].message
+
+
+class TestValidatorTrustPolicy:
+    """Test trust-threshold policies over community signatures."""
+
+    def test_trust_level_ordering(self):
+        assert TrustLevel.UNTRUSTED < TrustLevel.COMMUNITY < TrustLevel.VERIFIED < TrustLevel.OFFICIAL
+        assert TrustLevel.parse("Verified") == TrustLevel.VERIFIED
+        with pytest.raises(ValueError):
+            TrustLevel.parse("godlike")
+
+    def test_policy_met(self):
+        policy = TrustPolicy(min_community_signers=2, min_trust_level=TrustLevel.VERIFIED)
+        validator = JadeValidator.with_trust_policy(policy)
+        skill = make_skill()
+        cosign_skill(skill, JadeKeyPair.generate(), trust_level="verified")
+        cosign_skill(skill, JadeKeyPair.generate(), trust_level="official")
+        result = validator.validate_dict(skill)
+        assert result.valid, f"Errors: {[i.message for i in result.errors]}"
+
+    def test_policy_unmet_by_low_trust(self):
+        policy = TrustPolicy(min_community_signers=2, min_trust_level=TrustLevel.VERIFIED)
+        validator = JadeValidator.with_trust_policy(policy)
+        skill = make_skill()
+        cosign_skill(skill, JadeKeyPair.generate(), trust_level="verified")
+        cosign_skill(skill, JadeKeyPair.generate(), trust_level="community")
+        result = validator.validate_dict(skill)
+        assert not result.valid
+        assert any(i.code == "TRUST_POLICY_UNMET" for i in result.errors)
+
+    def test_policy_requires_root_signature(self):
+        validator = JadeValidator(trust_policy=TrustPolicy(require_root_signature=True))
+        result = validator.validate_dict(make_skill())
+        assert any(i.code == "SIGNATURE_REQUIRED" for i in result.errors)
+        signed = sign_skill(make_skill(), JadeKeyPair.generate())
+        assert validator.validate_dict(signed).valid
+
+    def test_unknown_trust_level_is_error(self, validator):
+        skill = cosign_skill(make_skill(), JadeKeyPair.generate(), trust_level="godlike")
+        result = validator.validate_dict(skill)
+        assert any(i.code == "UNKNOWN_TRUST_LEVEL" for i in result.errors)
+
+    def test_key_prefix_roles(self):
+        kp = JadeKeyPair.generate()
+        assert key_role("jade-pk-root-" + kp.public_key) == SignerRole.ROOT
+        assert key_role("jade-pk-ci-" + kp.public_key) == SignerRole.CI
+        assert key_role(kp.public_key) == SignerRole.UNKNOWN
+        assert SignerRole.UNKNOWN < SignerRole.CI < SignerRole.ROOT
+
+    def registered(self, role):
+        """A key pair and a validator requiring root signers, with the key registered as `role`."""
+        keypair = JadeKeyPair.generate()
+        registry = KeyRegistry()
+        registry.add(keypair.public_key, role=role)
+        policy = TrustPolicy(min_signer_role=SignerRole.ROOT)
+        return keypair, JadeValidator.with_key_registry(registry, trust_policy=policy)
+
+    def test_ci_signature_fails_root_required_policy(self):
+        keypair, validator = self.registered("ci")
+        result = validator.validate_dict(sign_skill_as(make_skill(), keypair.private_key, role="ci"))
+        assert result.signer_role == SignerRole.CI
+        assert [i.code for i in result.errors] == ["SIGNER_ROLE_TOO_LOW"]
+
+        keypair, validator = self.registered("root")
+        assert validator.validate_dict(sign_skill_as(make_skill(), keypair.private_key, role="root")).valid
+
+    def test_prefix_cannot_raise_registered_role(self):
+        keypair, validator = self.registered("ci")
+        result = validator.validate_dict(sign_skill_as(make_skill(), keypair.private_key, role="root"))
+        assert not result.valid
+        assert result.signer_role is None
+        assert [i.code for i in result.errors] == ["SIGNER_ROLE_MISMATCH", "SIGNATURE_REQUIRED"]
+
+    def test_role_from_registry_without_prefix(self):
+        keypair, validator = self.registered("root")
+        result = validator.validate_dict(sign_skill(make_skill(), keypair))
+        assert result.valid and result.signer_role == SignerRole.ROOT
+
+    def test_prefix_role_ignored_without_registry(self):
+        signed = sign_skill_as(make_skill(), JadeKeyPair.generate().private_key, role="root")
+        validator = JadeValidator(trust_policy=TrustPolicy(min_signer_role=SignerRole.CI))
+        result = validator.validate_dict(signed)
+        assert result.signer_role == SignerRole.UNKNOWN
+        assert [i.code for i in result.errors] == ["SIGNER_ROLE_TOO_LOW"]
+
+    def test_ci_signature_passes_ci_allowed_policy(self):
+        keypair = JadeKeyPair.generate()
+        registry = KeyRegistry()
+        registry.add(keypair.public_key, role="ci")
+        validator = JadeValidator.with_key_registry(registry, trust_policy=TrustPolicy(min_signer_role=SignerRole.CI))
+        result = validator.validate_dict(sign_skill_as(make_skill(), keypair.private_key, role="ci"))
+        assert result.valid, f"Errors: {[i.message for i in result.errors]}"
+        assert result.to_dict()["signer_role"] == "ci"
+
+    def test_min_role_requires_signature(self):
+        validator = JadeValidator(trust_policy=TrustPolicy(min_signer_role=SignerRole.CI))
+        result = validator.validate_dict(make_skill())
+        assert [i.code for i in result.errors] == ["SIGNATURE_REQUIRED"]
+        assert result.signer_role is None
+
+    def permissive_skill(self):
+        return make_skill(security={"network_whitelist": ["*"], "sandbox_level": "standard"})
+
+    def test_capability_requires_signature(self):
+        policy = TrustPolicy(require_signature_when=["wildcard_egress", "sensitive_env", "relaxed_sandbox"])
+        validator = JadeValidator(trust_policy=policy)
+        result = validator.validate_dict(self.permissive_skill())
+        [issue] = result.errors
+        assert issue.code == "SIGNATURE_REQUIRED" and issue.layer == ValidationLayer.SIGNATURE
+        assert "wildcard_egress, relaxed_sandbox" in issue.message
+
+        signed = sign_skill(self.permissive_skill(), JadeKeyPair.generate())
+        assert validator.validate_dict(signed).valid
+
+    def test_locked_down_skill_may_stay_unsigned(self):
+        policy = TrustPolicy(require_signature_when=["wildcard_egress", "sensitive_env", "relaxed_sandbox"])
+        assert JadeValidator(trust_policy=policy).validate_dict(make_skill()).valid
+        # Only the listed capabilities count
+        relaxed = make_skill(security={"sandbox_level": "standard"})
+        validator = JadeValidator(trust_policy=TrustPolicy(require_signature_when=["wildcard_egress"]))
+        assert validator.validate_dict(relaxed).valid
+
+    def test_unknown_capability_rejected(self):
+        with pytest.raises(ValueError, match="wildcard"):
+            JadeValidator(trust_policy=TrustPolicy(require_signature_when=["wildcard"]))
+
+
+class TestValidatorKeyRegistry:
+    """Test pinning root signers to a trusted key registry."""
+
+    def test_registered_signer_passes(self):
+        kp = JadeKeyPair.generate()
+        registry = KeyRegistry()
+        registry.add(kp.public_key)
+        validator = JadeValidator.with_key_registry(registry)
+        result = validator.validate_dict(sign_skill(make_skill(), kp))
+        assert result.valid, f"Errors: {[i.message for i in result.errors]}"
+
+    def test_unknown_signer_rejected(self):
+        registry = KeyRegistry()
+        registry.add(JadeKeyPair.generate().public_key)
+        validator = JadeValidator.with_key_registry(registry)
+        stranger = JadeKeyPair.generate()
+        result = validator.validate_dict(sign_skill(make_skill(), stranger, signer="mallory"))
+        issues = [i for i in result.errors if i.code == "UNKNOWN_SIGNER"]
+        assert len(issues) == 1
+        assert issues[0].layer == ValidationLayer.SIGNATURE
+        assert stranger.fingerprint in issues[0].message
+
+    def test_invalid_signature_not_also_unknown(self):
+        validator = JadeValidator.with_key_registry(KeyRegistry())
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        skill["metadata"]["name"] = "Tampered"
+        codes = [i.code for i in validator.validate_dict(skill).errors]
+        assert "SIG_INVALID" in codes
+        assert "UNKNOWN_SIGNER" not in codes
+
+    def test_no_registry_accepts_any_key(self, validator):
+        result = validator.validate_dict(sign_skill(make_skill(), JadeKeyPair.generate()))
+        assert result.valid
+
+
+# sign_skill/cosign_skill stamp signed_at = 2026-02-21T00:00:00Z
+SIGNED_AT = 1771632000.0
+DAY = 86400.0
+
+
+class TestValidatorSignatureFreshness:
+    """Test signed_at expiry and future-dating with an injected clock."""
+
+    def validator(self, now, max_age_s=30 * DAY, clock_skew_s=300.0, **kwargs):
+        policy = FreshnessPolicy(max_age_s=max_age_s, clock_skew_s=clock_skew_s)
+        return JadeValidator(freshness_policy=policy, clock=lambda: now, **kwargs)
+
+    def codes(self, result):
+        return [i.code for i in result.issues]
+
+    def test_parse_rfc3339(self):
+        assert parse_rfc3339("2026-02-21T00:00:00Z") == SIGNED_AT
+        assert parse_rfc3339("2026-02-21T01:30:00+01:30") == SIGNED_AT
+        assert parse_rfc3339("2026-02-21T00:00:00.250Z") == SIGNED_AT + 0.25
+        assert parse_rfc3339("2026-02-21T00:00:00") is None  # offset required
+        assert parse_rfc3339("2026-02-30T00:00:00Z") is None
+        assert parse_rfc3339("yesterday") is None
+
+    def test_fresh_signature_passes(self):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        result = self.validator(now=SIGNED_AT + DAY).validate_dict(skill)
+        assert result.valid, f"Errors: {[i.message for i in result.errors]}"
+
+    def test_expired_signature_rejected(self):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        result = self.validator(now=SIGNED_AT + 31 * DAY).validate_dict(skill)
+        expired = [i for i in result.errors if i.code == "SIG_EXPIRED"]
+        assert len(expired) == 1
+        assert expired[0].layer == ValidationLayer.SIGNATURE
+        assert expired[0].path == "jade_signature.signed_at"
+
+    def test_future_dated_signature_rejected(self):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        result = self.validator(now=SIGNED_AT - 3600).validate_dict(skill)
+        assert "SIG_FUTURE_DATED" in [i.code for i in result.errors]
+
+    def test_clock_skew_allowance(self):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        assert self.validator(now=SIGNED_AT - 60).validate_dict(skill).valid
+        assert not self.validator(now=SIGNED_AT - 60, clock_skew_s=10).validate_dict(skill).valid
+
+    def test_no_max_age_never_expires(self):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        assert self.validator(now=SIGNED_AT + 3650 * DAY, max_age_s=None).validate_dict(skill).valid
+
+    def test_unparseable_signed_at(self):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        skill["jade_signature"]["signed_at"] = "last tuesday"
+        result = self.validator(now=SIGNED_AT).validate_dict(skill)
+        assert "SIG_INVALID_TIMESTAMP" in [i.code for i in result.errors]
+
+    def test_no_policy_ignores_signed_at(self, validator):
+        skill = sign_skill(make_skill(), JadeKeyPair.generate())
+        skill["jade_signature"]["signed_at"] = "2001-01-01T00:00:00Z"
+        assert validator.validate_dict(skill).valid
+
+    def test_stale_cosignature_warns_and_does_not_count(self):
+        skill = cosign_skill(make_skill(), JadeKeyPair.generate(), trust_level="verified")
+        validator = self.validator(
+            now=SIGNED_AT + 31 * DAY,
+            trust_policy=TrustPolicy(min_community_signers=1, min_trust_level=TrustLevel.VERIFIED),
+        )
+        result = validator.validate_dict(skill)
+        warnings = [i for i in result.warnings if i.code == "SIG_EXPIRED"]
+        assert len(warnings) == 1
+        assert warnings[0].path == "community_signatures[0]"
+        assert "TRUST_POLICY_UNMET" in [i.code for i in result.errors]
+
+
+class TestIssueCodes:
+    """Stable issue codes and suppressing them by code."""
+
+    def test_code_values_match_names(self):
+        for code in IssueCode:
+            assert code.value == code.name
+
+    def test_issue_code_is_plain_string(self, validator):
+        result = validator.validate_file("/nonexistent/path.json")
+        issue = result.issues[0]
+        assert type(issue.code) is str
+        assert f"[{issue.code}]" == "[FILE_NOT_FOUND]"
+        assert json.loads(json.dumps(result.to_dict()))["issues"][0]["code"] == "FILE_NOT_FOUND"
+
+    def test_core_modules_use_registry(self):
+        # New findings must be added to IssueCode rather than written as literals
+        core = Path(__file__).parent.parent / "jade_core"
+        for path in core.glob("*.py"):
+            for node in ast.walk(ast.parse(path.read_text(encoding="utf-8"))):
+                if isinstance(node, ast.Call) and getattr(node.func, "id", "") == "ValidationIssue":
+                    code = [k.value for k in node.keywords if k.arg == "code"][0]
+                    assert not isinstance(code, ast.Constant), f"{path.name}:{node.lineno}"
+
+    def test_ignoring_error_revalidates(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][0]["params"]["cmd"] = "rm -rf /"
+        result = validator.validate_dict(skill)
+        assert not result.valid
+        assert ValidationLayer.SECURITY not in result.layers_passed
+
+        ignored = result.ignoring([IssueCode.SEC_DANGEROUS_CMD])
+        assert ignored.valid
+        assert ignored.issues == []
+        assert ignored.layers_passed == [1, 2, 3, 4, 5]
+        assert not result.valid  # Original is unchanged
+
+    def test_ignoring_other_code_keeps_failure(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][0]["params"]["cmd"] = "rm -rf /"
+        ignored = validator.validate_dict(skill).ignoring(["SEC_WILDCARD_NETWORK"])
+        assert not ignored.valid
+        assert [i.code for i in ignored.errors] == ["SEC_DANGEROUS_CMD"]
+
+    def test_ignoring_early_stop_stays_invalid(self, validator):
+        result = validator.validate_str("{not json")
+        assert not result.ignoring(["INVALID_JSON"]).valid
+
+
+class TestValidatorSuppressions:
+    """Suppressed issues stay in the result as INFO instead of failing it."""
+
+    @staticmethod
+    def dangerous_skill():
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][1]["params"]["cmd"] = "rm -rf /"
+        return skill
+
+    def test_suppressed_error_is_info(self):
+        validator = JadeValidator(suppressions=[Suppression(IssueCode.SEC_DANGEROUS_CMD)])
+        result = validator.validate_dict(self.dangerous_skill())
+        assert result.valid
+        assert ValidationLayer.SECURITY in result.layers_passed
+        [issue] = result.issues
+        assert issue.suppressed
+        assert issue.severity == ValidationSeverity.INFO
+        assert issue.to_dict()["suppressed"] is True
+
+    def test_node_scope(self):
+        skill = self.dangerous_skill()
+        other = JadeValidator(suppressions=[Suppression.parse("SEC_DANGEROUS_CMD@step_one")])
+        assert not other.validate_dict(skill).valid
+        same = JadeValidator(suppressions=[Suppression.parse("SEC_DANGEROUS_CMD@step_two")])
+        assert same.validate_dict(skill).valid
+
+    def test_node_id_from_positional_path(self, validator):
+        [issue] = validator.validate_dict(self.dangerous_skill()).issues
+        assert issue.path.startswith("execution_dag.nodes[1]")
+        assert issue.node_id == "step_two"
+
+    def test_node_id_from_named_path(self, validator):
+        skill = make_skill()
+        skill["execution_dag"]["edges"] = []
+        result = validator.validate_all(skill)
+        dead = [i for i in result.issues if i.code == "DAG_NO_INCOMING"]
+        assert [i.node_id for i in dead] == ["step_two"]
+
+    def test_streaming_applies_suppressions(self):
+        validator = JadeValidator(suppressions=[Suppression("SEC_DANGEROUS_CMD")])
+        issues = list(validator.validate_streaming(self.dangerous_skill()))
+        assert all(i.suppressed for i in issues)
+
+    def test_set_suppressions_replaces(self):
+        validator = JadeValidator(suppressions=[Suppression("SEC_DANGEROUS_CMD")])
+        validator.set_suppressions([])
+        assert not validator.validate_dict(self.dangerous_skill()).valid
+
+    def test_parse_and_round_trip(self):
+        parsed = Suppression.parse("DAG_DEAD_END@fetch")
+        assert (parsed.code, parsed.node_id) == ("DAG_DEAD_END", "fetch")
+        assert Suppression.parse("DAG_DEAD_END").node_id is None
+        assert Suppression.from_dict(parsed.to_dict()) == parsed
+
+
+class TestValidatorMinSeverity:
+    """min_severity drops less severe issues from results but counts them."""
+
+    def mixed_skill(self):
+        # One warning, one info (a suppressed warning) and two errors
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][0]["params"]["hint"] = "password"
+        skill["extra_field"] = 1
+        skill["execution_dag"]["edges"].append({"from": "step_one", "to": "step_one"})
+        return skill
+
+    def validate(self, min_severity, skill=None):
+        validator = JadeValidator(suppressions=[Suppression("UNKNOWN_FIELD")], min_severity=min_severity)
+        return validator.validate_dict(skill or self.mixed_skill())
+
+    def severities(self, result):
+        return sorted(i.severity.value for i in result.issues)
+
+    def test_info_reports_everything(self):
+        result = self.validate(ValidationSeverity.INFO)
+        assert self.severities(result) == ["error", "error", "info", "warning"]
+        assert result.hidden == {}
+        assert result.to_dict()["hidden_counts"] == {}
+
+    def test_warning_hides_info(self):
+        result = self.validate(ValidationSeverity.WARNING)
+        assert self.severities(result) == ["error", "error", "warning"]
+        assert result.hidden == {"info": 1}
+
+    def test_error_hides_warnings_and_info(self):
+        result = self.validate(ValidationSeverity.ERROR)
+        assert [i.code for i in result.issues] == ["DAG_SELF_LOOP", "DAG_NO_ROOT"]
+        assert result.to_dict()["hidden_counts"] == {"warning": 1, "info": 1}
+
+    def test_valid_still_means_no_errors(self):
+        skill = self.mixed_skill()
+        skill["execution_dag"]["edges"].pop()
+        for severity in ValidationSeverity:
+            result = self.validate(severity, skill)
+            assert result.valid and result.layers_passed == [1, 2, 3, 4, 5]
+        assert self.validate(ValidationSeverity.ERROR, skill).issues == []
+        assert not self.validate(ValidationSeverity.ERROR).valid
+
+    def test_streaming_skips_hidden_issues(self):
+        validator = JadeValidator(min_severity=ValidationSeverity.ERROR)
+        issues = list(validator.validate_streaming(self.mixed_skill()))
+        assert {i.severity for i in issues} == {ValidationSeverity.ERROR}
+
+    def test_part_of_cache_namespace(self):
+        cache = MemoryValidationCache()
+        skill = self.mixed_skill()
+        skill["execution_dag"]["edges"].pop()
+        text = json.dumps(skill)
+        assert JadeValidator(cache=cache).validate_str(text).warnings
+        errors_only = JadeValidator(cache=cache, min_severity=ValidationSeverity.ERROR).validate_str(text)
+        assert errors_only.warnings == [] and errors_only.hidden == {"warning": 2}
+
+
+class TestValidatorRetryPolicy:
+    """Layer 1 checks on a node's retry policy."""
+
+    @pytest.mark.parametrize("retry,valid", [
+        ({"max_attempts": 3, "backoff_ms": 200, "retry_on_status": [502, 503]}, True),
+        ({"max_attempts": 1}, True),
+        ({"max_attempts": 5, "backoff_ms": 60000}, True),
+        ({"max_attempts": 0}, False),
+        ({"max_attempts": 6}, False),
+        ({"max_attempts": 50}, False),
+        ({"max_attempts": 2, "backoff_ms": -1}, False),
+        ({"max_attempts": 2, "backoff_ms": 60001}, False),
+        ({"max_attempts": "3"}, False),
+        ({"max_attempts": 2, "retry_on_status": [42]}, False),
+        ({"max_attempts": 2, "retry_on_status": 503}, False),
+    ])
+    def test_retry_policy(self, validator, retry, valid):
+        skill = make_skill()
+        skill["execution_dag"]["nodes"][0]["retry"] = retry
+        result = validator.validate_dict(skill)
+        codes = [i.code for i in result.errors]
+        assert ("INVALID_RETRY" not in codes) is valid
+        if not valid:
+            [issue] = [i for i in result.errors if i.code == "INVALID_RETRY"]
+            assert issue.layer == ValidationLayer.SCHEMA
+            assert issue.path == "execution_dag.nodes.step_one.retry"